@@ -1,6 +1,10 @@
 use serde::{Serialize, Deserialize};
 use bevy::prelude::*;
+use bevy::render::mesh::{PrimitiveTopology, VertexAttributeValues};
 use bevy_rapier3d::prelude::Collider;
+use bevy_rapier3d::utils::iso_to_transform;
+use crate::event_system::generation_error::{GenerationError, GenerationErrorKind, PendingErrors};
+use crate::spawning::euler_transform::EulerTransform;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum ColliderBehaviour {
@@ -14,11 +18,39 @@ pub struct ColliderInfo {
     pub collider_type: ColliderType,
     pub priority: i8,
     pub behaviour: ColliderBehaviour,
+    // What happens to this object when it loses a priority contact
+    #[serde(default)]
+    pub overlap: OverlapResolution,
 }
 
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ColliderPriority(pub i8);
 
+// How the lower-priority side of an overlap is resolved (see resolve_collider_overlaps)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub enum OverlapResolution {
+    // Remove the object and its children (the original behaviour)
+    #[default]
+    Despawn,
+    // Push the object out along the penetration vector, moving it at most max_distance in total.
+    // Falls back to Despawn when the budget runs out.
+    Nudge {
+        max_distance: f32,
+        #[serde(default = "default_overlap_attempts")]
+        max_attempts: u8,
+    },
+    // Ask the spawner that placed the object (Rand, RandDistDir) for another point.
+    // Falls back to Despawn when the object has no such spawner or keeps overlapping.
+    Resample,
+    // Leave the overlap in place
+    Keep,
+}
+
+fn default_overlap_attempts() -> u8 { 4 }
+
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct OverlapStrategy(pub OverlapResolution);
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum ColliderType {
     None,
@@ -86,9 +118,140 @@ pub enum ColliderType {
         c: Vec3,
         border_radius: f32,
     },
+    // Shapes computed from the render geometry once it is available (generated mesh or loaded glTF scene).
+    // Note that rapier does not generate trimesh-vs-trimesh contacts.
+    ConvexHullFromMesh,
+    TrimeshFromMesh,
+    ConvexDecomposition,
+    // Several shapes, each placed relative to the owner (scale is applied to the child shape)
+    Compound(Vec<(ColliderType, EulerTransform)>),
 }
 
-pub fn create_collider(collider_type: &ColliderType) -> Option<Collider> {
+impl ColliderType {
+    // Whether the collider can only be built once mesh geometry is known
+    pub fn requires_mesh(&self) -> bool {
+        match self {
+            ColliderType::ConvexHullFromMesh
+            | ColliderType::TrimeshFromMesh
+            | ColliderType::ConvexDecomposition => true,
+            ColliderType::Compound(parts) => parts.iter().any(|(part, _)| part.requires_mesh()),
+            _ => false,
+        }
+    }
+
+    // Axis-aligned (min, max) bounds in the owner's local space. None for shapes whose extent is
+    // only known once the mesh exists (and for ColliderType::None).
+    pub fn local_bounds(&self) -> Option<(Vec3, Vec3)> {
+        let symmetric = |half: Vec3| Some((-half, half));
+        let points = |pts: &[Vec3], margin: f32| {
+            let min = pts.iter().copied().reduce(Vec3::min)?;
+            let max = pts.iter().copied().reduce(Vec3::max)?;
+            Some((min - Vec3::splat(margin), max + Vec3::splat(margin)))
+        };
+        match self {
+            ColliderType::None
+            | ColliderType::ConvexHullFromMesh
+            | ColliderType::TrimeshFromMesh
+            | ColliderType::ConvexDecomposition => None,
+            ColliderType::Ball { radius } => symmetric(Vec3::splat(*radius)),
+            ColliderType::Cylinder { half_height, radius }
+            | ColliderType::Cone { half_height, radius } => symmetric(Vec3::new(*radius, *half_height, *radius)),
+            ColliderType::RoundCylinder { half_height, radius, border_radius }
+            | ColliderType::RoundCone { half_height, radius, border_radius } => {
+                symmetric(Vec3::new(*radius, *half_height, *radius) + Vec3::splat(*border_radius))
+            }
+            ColliderType::Capsule { start, end, radius } => points(&[*start, *end], *radius),
+            ColliderType::CapsuleX { half_height, radius } => symmetric(Vec3::new(half_height + radius, *radius, *radius)),
+            ColliderType::CapsuleY { half_height, radius } => symmetric(Vec3::new(*radius, half_height + radius, *radius)),
+            ColliderType::CapsuleZ { half_height, radius } => symmetric(Vec3::new(*radius, *radius, half_height + radius)),
+            ColliderType::Cuboid { hx, hy, hz } => symmetric(Vec3::new(*hx, *hy, *hz)),
+            ColliderType::RoundCuboid { half_x, half_y, half_z, border_radius } => {
+                symmetric(Vec3::new(*half_x, *half_y, *half_z) + Vec3::splat(*border_radius))
+            }
+            ColliderType::Segment { a, b } => points(&[*a, *b], 0.0),
+            ColliderType::Triangle { a, b, c } => points(&[*a, *b, *c], 0.0),
+            ColliderType::RoundTriangle { a, b, c, border_radius } => points(&[*a, *b, *c], *border_radius),
+            ColliderType::Compound(parts) => {
+                let corners: Vec<Vec3> = parts
+                    .iter()
+                    .filter_map(|(part, transform)| {
+                        let (min, max) = part.local_bounds()?;
+                        Some(transformed_corners(min, max, &Transform::from(transform.clone())))
+                    })
+                    .flatten()
+                    .collect();
+                points(&corners, 0.0)
+            }
+        }
+    }
+}
+
+// The eight corners of a (min, max) box after applying `transform`
+pub fn transformed_corners(min: Vec3, max: Vec3, transform: &Transform) -> [Vec3; 8] {
+    let mut corners = [Vec3::ZERO; 8];
+    for (i, corner) in corners.iter_mut().enumerate() {
+        let local = Vec3::new(
+            if i & 1 == 0 { min.x } else { max.x },
+            if i & 2 == 0 { min.y } else { max.y },
+            if i & 4 == 0 { min.z } else { max.z },
+        );
+        *corner = transform.transform_point(local);
+    }
+    corners
+}
+
+// Triangles gathered from one or more meshes, expressed in the local space of the collider owner
+#[derive(Debug, Clone, Default)]
+pub struct ColliderGeometry {
+    pub vertices: Vec<Vec3>,
+    pub indices: Vec<[u32; 3]>,
+}
+
+impl ColliderGeometry {
+    pub fn from_mesh(mesh: &Mesh) -> Self {
+        let mut geometry = ColliderGeometry::default();
+        geometry.append_mesh(mesh, &Transform::IDENTITY);
+        geometry
+    }
+
+    // Append a triangle-list mesh, moving its vertices by `transform` first. Other topologies are skipped.
+    pub fn append_mesh(&mut self, mesh: &Mesh, transform: &Transform) {
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return;
+        }
+        let Some(VertexAttributeValues::Float32x3(positions)) = mesh.attribute(Mesh::ATTRIBUTE_POSITION) else {
+            return;
+        };
+
+        let base = self.vertices.len() as u32;
+        let matrix = transform.compute_matrix();
+        self.vertices.extend(positions.iter().map(|p| matrix.transform_point3(Vec3::from(*p))));
+
+        let local_indices: Vec<u32> = match mesh.indices() {
+            Some(indices) => indices.iter().map(|i| i as u32).collect(),
+            None => (0..positions.len() as u32).collect(),
+        };
+        self.indices.extend(
+            local_indices
+                .chunks_exact(3)
+                .map(|tri| [base + tri[0], base + tri[1], base + tri[2]]),
+        );
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+pub fn create_collider(collider_type: &ColliderType, errors: &PendingErrors) -> Option<Collider> {
+    create_collider_from_geometry(collider_type, None, errors)
+}
+
+// Like create_collider, but able to build mesh-derived shapes. Returns None for those when no
+// (or empty) geometry is given, or when the geometry cannot form a valid shape. Compounds that cannot
+// be built are reported to `errors`.
+pub fn create_collider_from_geometry(collider_type: &ColliderType, geometry: Option<&ColliderGeometry>, errors: &PendingErrors) -> Option<Collider> {
+    let geometry = geometry.filter(|g| !g.is_empty());
     match collider_type {
         ColliderType::None => None,
         ColliderType::Ball { radius } => Some(Collider::ball(*radius)),
@@ -109,5 +272,106 @@ pub fn create_collider(collider_type: &ColliderType) -> Option<Collider> {
         ColliderType::Triangle { a, b, c } => Some(Collider::triangle(*a, *b, *c)),
         ColliderType::RoundTriangle { a, b, c, border_radius } =>
             Some(Collider::round_triangle(*a, *b, *c, *border_radius)),
+        ColliderType::ConvexHullFromMesh => geometry.and_then(|g| Collider::convex_hull(&g.vertices)),
+        ColliderType::TrimeshFromMesh => geometry.map(|g| Collider::trimesh(g.vertices.clone(), g.indices.clone())),
+        ColliderType::ConvexDecomposition => geometry.map(|g| Collider::convex_decomposition(&g.vertices, &g.indices)),
+        ColliderType::Compound(parts) => {
+            if parts.is_empty() {
+                GenerationError::new(GenerationErrorKind::ColliderBuild, "Compound collider has no parts").report(errors);
+                return None;
+            }
+            let mut shapes = Vec::new();
+            compound_shapes(parts, geometry, &Transform::IDENTITY, &mut shapes, errors);
+            if shapes.is_empty() {
+                GenerationError::new(GenerationErrorKind::ColliderBuild, format!("None of the {} compound collider parts could be built", parts.len())).report(errors);
+                return None;
+            }
+            Some(Collider::compound(shapes))
+        }
+    }
+}
+
+// Flatten compound parts into (position, rotation, shape) relative to the owner. Parry does not allow
+// composite shapes inside a compound, so nested compounds and convex decompositions are unpacked into
+// their pieces and trimeshes are rejected.
+fn compound_shapes(
+    parts: &[(ColliderType, EulerTransform)],
+    geometry: Option<&ColliderGeometry>,
+    parent: &Transform,
+    shapes: &mut Vec<(Vec3, Quat, Collider)>,
+    errors: &PendingErrors,
+) {
+    for (part, euler) in parts.iter() {
+        let transform = parent.mul_transform(Transform::from(euler.clone()));
+        match part {
+            ColliderType::Compound(nested) => compound_shapes(nested, geometry, &transform, shapes, errors),
+            ColliderType::TrimeshFromMesh => {
+                GenerationError::new(
+                    GenerationErrorKind::ColliderBuild,
+                    "TrimeshFromMesh cannot be a compound collider part; use ConvexHullFromMesh or ConvexDecomposition",
+                ).report(errors);
+            }
+            _ => {
+                let Some(mut collider) = create_collider_from_geometry(part, geometry, errors) else { continue; };
+                if transform.scale != Vec3::ONE {
+                    collider.set_scale(transform.scale, 20);
+                }
+                match collider.raw.as_compound() {
+                    // Convex decompositions come back as compounds of convex pieces
+                    Some(compound) => {
+                        for (iso, piece) in compound.shapes() {
+                            let local = iso_to_transform(iso);
+                            shapes.push((
+                                transform.translation + transform.rotation * local.translation,
+                                transform.rotation * local.rotation,
+                                Collider::from(piece.clone()),
+                            ));
+                        }
+                    }
+                    None => shapes.push((transform.translation, transform.rotation, collider)),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cuboid(h: f32) -> ColliderType {
+        ColliderType::Cuboid { hx: h, hy: h, hz: h }
+    }
+
+    fn at(x: f32) -> EulerTransform {
+        EulerTransform::from(Transform::from_xyz(x, 0.0, 0.0))
+    }
+
+    fn cube_geometry() -> ColliderGeometry {
+        ColliderGeometry::from_mesh(&Mesh::from(Cuboid::new(1.0, 1.0, 1.0)))
+    }
+
+    #[test]
+    fn nested_compound_is_flattened() {
+        let nested = ColliderType::Compound(vec![(cuboid(0.5), at(1.0)), (cuboid(0.5), at(-1.0))]);
+        let compound = ColliderType::Compound(vec![(cuboid(1.0), at(0.0)), (nested, at(10.0))]);
+        let collider = create_collider(&compound, &PendingErrors::default()).unwrap();
+        let shapes = collider.raw.as_compound().unwrap().shapes();
+        assert_eq!(shapes.len(), 3);
+        let xs: Vec<f32> = shapes.iter().map(|(iso, _)| iso.translation.vector.x).collect();
+        assert_eq!(xs, vec![0.0, 11.0, 9.0]);
+    }
+
+    #[test]
+    fn trimesh_and_empty_parts_are_rejected() {
+        let errors = PendingErrors::default();
+        assert!(create_collider(&ColliderType::Compound(Vec::new()), &errors).is_none());
+        let only_trimesh = ColliderType::Compound(vec![(ColliderType::TrimeshFromMesh, at(0.0))]);
+        assert!(create_collider_from_geometry(&only_trimesh, Some(&cube_geometry()), &errors).is_none());
+        let mixed = ColliderType::Compound(vec![(ColliderType::TrimeshFromMesh, at(0.0)), (cuboid(1.0), at(0.0))]);
+        let collider = create_collider_from_geometry(&mixed, Some(&cube_geometry()), &errors).unwrap();
+        assert_eq!(collider.raw.as_compound().unwrap().shapes().len(), 1);
+        // Empty compound, the lone trimesh part and the compound built from none of its parts, then the mixed trimesh
+        assert_eq!(errors.len(), 4);
     }
 }
@@ -0,0 +1,68 @@
+use serde::{Serialize, Deserialize};
+use crate::core::path_placement::PathPlacement;
+use crate::core::spread_data::SpreadData;
+use crate::core::structure_reference::StructureReference;
+
+// Reachability check run after the last navmesh build of a run, e.g.
+// `ConnectivityConfig { tags: vec!["Spawn".into(), "Objective".into()], remedy: ConnectivityRemedy::Fail, ..default() }`.
+// Every entity carrying one of `tags` must be able to walk to every other one.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ConnectivityConfig {
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub remedy: ConnectivityRemedy,
+    // Remedies applied per run before the check gives up and reports the isolated entities
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    // How far from a tagged entity the nearest navmesh polygon may be
+    #[serde(default = "default_search_radius")]
+    pub search_radius: f32,
+}
+
+// What to do about entities that cannot reach the others. Each isolated entity is joined along the
+// straight line to the nearest entity of the largest connected group.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub enum ConnectivityRemedy {
+    // Despawn the navmesh affectors with the lowest ColliderPriority in a corridor along the line,
+    // then check again once the navmesh has rebuilt
+    DespawnBlockers {
+        #[serde(default = "default_corridor_width")]
+        corridor_width: f32,
+        // Height of the corridor's centre line above the two entities. It is raised to half the
+        // corridor width (plus a few centimetres) when lower, so the corridor never reaches the ground
+        #[serde(default = "default_probe_height")]
+        probe_height: f32,
+        // Colliders above this priority are never despawned
+        #[serde(default)]
+        max_priority: Option<i8>,
+    },
+    // Spawn a structure along the line as a PathSpawn, generated by running the current pass again
+    // (e.g. a ramp or bridge piece with a high priority, so overlap resolution clears what it lands on)
+    CarvePath {
+        reference: StructureReference,
+        spread: SpreadData,
+        #[serde(default)]
+        count: u32,
+        #[serde(default)]
+        placement: Option<PathPlacement>,
+    },
+    // End the run with a GenerationErrorKind::Unreachable naming the isolated entities
+    #[default]
+    Fail,
+}
+
+fn default_max_attempts() -> u32 { 3 }
+fn default_search_radius() -> f32 { 2.0 }
+fn default_corridor_width() -> f32 { 2.0 }
+fn default_probe_height() -> f32 { 0.5 }
+
+impl Default for ConnectivityConfig {
+    fn default() -> Self {
+        ConnectivityConfig {
+            tags: Vec::new(),
+            remedy: ConnectivityRemedy::default(),
+            max_attempts: default_max_attempts(),
+            search_radius: default_search_radius(),
+        }
+    }
+}
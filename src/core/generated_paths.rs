@@ -0,0 +1,183 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use crate::core::components::{PathPolyline, PathPolylineList};
+use crate::core::tags::Tags;
+use crate::spawning::transformation::polyline_point_at;
+
+// One stored polyline: the holder entity and its position within the holder's list
+// (a PathPolyline, when present, comes first)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PathRef {
+    pub holder: Entity,
+    pub index: usize,
+}
+
+// Closest point on a stored polyline to some query position
+#[derive(Clone, Copy, Debug)]
+pub struct PathHit {
+    pub path: PathRef,
+    pub point: Vec3,
+    pub distance: f32,
+    // Distance along the polyline from its first point to `point`
+    pub arclength: f32,
+}
+
+type PathHolder = (Entity, &'static Tags, Option<&'static PathPolyline>, Option<&'static PathPolylineList>);
+type PathHolderFilter = Or<(With<PathPolyline>, With<PathPolylineList>)>;
+
+// Read access to the world-space polylines stored by path keys (`store_as`), looked up by label:
+// `fn patrol(paths: GeneratedPaths, ..) { let Some(p) = paths.sample_at("main_road", 40.0) else { return }; .. }`
+#[derive(SystemParam)]
+pub struct GeneratedPaths<'w, 's> {
+    holders: Query<'w, 's, PathHolder, PathHolderFilter>,
+}
+
+impl GeneratedPaths<'_, '_> {
+    // Every stored polyline with the tags of its holder
+    pub fn iter(&self) -> impl Iterator<Item = (PathRef, &Tags, &[Vec3])> {
+        self.holders.iter().flat_map(|(holder, tags, single, list)| {
+            single.map(|p| p.0.as_slice()).into_iter()
+                .chain(list.into_iter().flat_map(|l| l.0.iter().map(|p| p.as_slice())))
+                .enumerate()
+                .map(move |(index, points)| (PathRef { holder, index }, tags, points))
+        })
+    }
+
+    // Polylines stored under `label`, in the order they were resolved
+    pub fn get(&self, label: &str) -> Vec<&[Vec3]> {
+        self.iter().filter(|(_, tags, _)| tags.contains(label)).map(|(_, _, points)| points).collect()
+    }
+
+    pub fn polyline(&self, path: PathRef) -> Option<&[Vec3]> {
+        self.iter().find(|(r, _, _)| *r == path).map(|(_, _, points)| points)
+    }
+
+    // Closest point on any stored polyline
+    pub fn nearest_point(&self, pos: Vec3) -> Option<PathHit> {
+        self.iter()
+            .filter_map(|(path, _, points)| {
+                closest_point_on_polyline(points, pos).map(|(point, distance, arclength)| PathHit { path, point, distance, arclength })
+            })
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+
+    pub fn distance_to_path(&self, pos: Vec3) -> Option<f32> {
+        self.nearest_point(pos).map(|hit| hit.distance)
+    }
+
+    // Point `arclength` meters along the polylines stored under `label`, walked one after another;
+    // clamped to the ends
+    pub fn sample_at(&self, label: &str, arclength: f32) -> Option<Vec3> {
+        let polylines = self.get(label);
+        let mut remaining = arclength.max(0.0);
+        for points in polylines.iter() {
+            let length = polyline_length(points);
+            if remaining <= length { return points.first().map(|_| polyline_point_at(points, remaining)); }
+            remaining -= length;
+        }
+        polylines.last().and_then(|points| points.last().copied())
+    }
+
+    // Polylines that touch or cross `path` within `tolerance` meters
+    pub fn neighbours(&self, path: PathRef, tolerance: f32) -> Vec<PathRef> {
+        let Some(points) = self.polyline(path) else { return Vec::new(); };
+        self.iter()
+            .filter(|(other, _, other_points)| *other != path && polylines_touch(points, other_points, tolerance))
+            .map(|(other, _, _)| other)
+            .collect()
+    }
+
+    // Every pair of stored polylines that touch or cross within `tolerance` meters, each pair once
+    pub fn adjacency(&self, tolerance: f32) -> Vec<(PathRef, PathRef)> {
+        let all: Vec<(PathRef, &[Vec3])> = self.iter().map(|(path, _, points)| (path, points)).collect();
+        let mut pairs = Vec::new();
+        for (i, (a, a_points)) in all.iter().enumerate() {
+            for (b, b_points) in all.iter().skip(i + 1) {
+                if polylines_touch(a_points, b_points, tolerance) { pairs.push((*a, *b)); }
+            }
+        }
+        pairs
+    }
+}
+
+pub fn polyline_length(points: &[Vec3]) -> f32 {
+    points.windows(2).map(|w| w[0].distance(w[1])).sum()
+}
+
+// Closest point on a polyline to `pos`, its distance, and its arclength from the first point
+pub fn closest_point_on_polyline(points: &[Vec3], pos: Vec3) -> Option<(Vec3, f32, f32)> {
+    if points.len() == 1 { return Some((points[0], points[0].distance(pos), 0.0)); }
+    let mut best: Option<(Vec3, f32, f32)> = None;
+    let mut walked = 0.0;
+    for w in points.windows(2) {
+        let ab = w[1] - w[0];
+        let t = if ab.length_squared() > 1.0e-8 { ((pos - w[0]).dot(ab) / ab.length_squared()).clamp(0.0, 1.0) } else { 0.0 };
+        let p = w[0] + ab * t;
+        let d = p.distance(pos);
+        if best.is_none_or(|(_, bd, _)| d < bd) { best = Some((p, d, walked + ab.length() * t)); }
+        walked += ab.length();
+    }
+    best
+}
+
+// Whether two polylines meet: a point of one lies within `tolerance` of the other, or their segments
+// cross in plan at heights within `tolerance` of each other (so a bridge over a road does not count)
+pub fn polylines_touch(a: &[Vec3], b: &[Vec3], tolerance: f32) -> bool {
+    let near = |points: &[Vec3], other: &[Vec3]| {
+        points.iter().any(|p| closest_point_on_polyline(other, *p).is_some_and(|(_, d, _)| d <= tolerance))
+    };
+    if near(a, b) || near(b, a) { return true; }
+    for sa in a.windows(2) {
+        for sb in b.windows(2) {
+            let (p, r) = (sa[0].xz(), sa[1].xz() - sa[0].xz());
+            let (q, s) = (sb[0].xz(), sb[1].xz() - sb[0].xz());
+            let denom = r.perp_dot(s);
+            if denom.abs() <= 1.0e-8 { continue; }
+            let t = (q - p).perp_dot(s) / denom;
+            let u = (q - p).perp_dot(r) / denom;
+            if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)
+                && (sa[0].lerp(sa[1], t).y - sb[0].lerp(sb[1], u).y).abs() <= tolerance
+            {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crossing_paths_touch() {
+        let a = [Vec3::new(-5.0, 0.0, 0.0), Vec3::new(5.0, 0.0, 0.0)];
+        let b = [Vec3::new(0.0, 0.2, -5.0), Vec3::new(0.0, 0.2, 5.0)];
+        assert!(polylines_touch(&a, &b, 0.5));
+        assert!(polylines_touch(&b, &a, 0.5));
+    }
+
+    #[test]
+    fn bridge_over_a_path_does_not_touch() {
+        let road = [Vec3::new(-5.0, 0.0, 0.0), Vec3::new(5.0, 0.0, 0.0)];
+        let bridge = [Vec3::new(0.0, 4.0, -5.0), Vec3::new(0.0, 4.0, 5.0)];
+        assert!(!polylines_touch(&road, &bridge, 0.5));
+    }
+
+    #[test]
+    fn paths_touch_within_tolerance_of_an_end() {
+        // A spur ending just short of a road, without crossing it
+        let road = [Vec3::new(-5.0, 0.0, 0.0), Vec3::new(5.0, 0.0, 0.0)];
+        let spur = [Vec3::new(0.0, 0.0, 0.3), Vec3::new(0.0, 0.0, 5.0)];
+        assert!(polylines_touch(&road, &spur, 0.5));
+        assert!(!polylines_touch(&road, &spur, 0.2));
+    }
+
+    #[test]
+    fn parallel_paths_apart_do_not_touch() {
+        let a = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(10.0, 0.0, 0.0)];
+        let b = [Vec3::new(0.0, 0.0, 2.0), Vec3::new(10.0, 0.0, 2.0)];
+        assert!(!polylines_touch(&a, &b, 1.0));
+        assert!(polylines_touch(&a, &b, 2.0));
+    }
+}
@@ -0,0 +1,97 @@
+use serde::{Serialize, Deserialize};
+use bevy::prelude::*;
+
+// One named generation pass and the phases it needs after its Generating phase.
+// Decoration-style passes can turn phases off so the whole cycle is not repeated for them.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(from = "PassDeclaration")]
+pub struct PassConfig {
+    pub name: String,
+    pub collision_despawn: bool,
+    pub rebuild_navmesh: bool,
+    pub resolve_paths: bool,
+}
+
+impl PassConfig {
+    // A pass that runs every phase (the behaviour of plain numeric passes)
+    pub fn full(name: impl Into<String>) -> Self {
+        PassConfig {
+            name: name.into(),
+            collision_despawn: true,
+            rebuild_navmesh: true,
+            resolve_paths: true,
+        }
+    }
+}
+
+fn default_true() -> bool { true }
+
+// Passes can be declared as a bare name ("props") or with explicit phase flags
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PassDeclaration {
+    Name(String),
+    Config {
+        name: String,
+        #[serde(default = "default_true")]
+        collision_despawn: bool,
+        #[serde(default = "default_true")]
+        rebuild_navmesh: bool,
+        #[serde(default = "default_true")]
+        resolve_paths: bool,
+    },
+}
+
+impl From<PassDeclaration> for PassConfig {
+    fn from(declaration: PassDeclaration) -> Self {
+        match declaration {
+            PassDeclaration::Name(name) => PassConfig::full(name),
+            PassDeclaration::Config { name, collision_despawn, rebuild_navmesh, resolve_paths } => {
+                PassConfig { name, collision_despawn, rebuild_navmesh, resolve_paths }
+            }
+        }
+    }
+}
+
+// Ordered list of named passes. Pass N of the generation cycle is `passes[N]`; structures refer to
+// passes by name through `StructureKey::InPass { pass: Some("roads"), .. }`.
+// An empty pipeline keeps the numeric `InPass { index }` behaviour with every phase enabled.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GenerationPipeline {
+    pub passes: Vec<PassConfig>,
+}
+
+impl GenerationPipeline {
+    pub fn new(passes: Vec<PassConfig>) -> Self {
+        GenerationPipeline { passes }
+    }
+
+    // Pipeline of full passes, e.g. ["terrain", "landmarks", "roads", "props", "decals"]
+    pub fn from_names(names: &[&str]) -> Self {
+        GenerationPipeline { passes: names.iter().map(|name| PassConfig::full(*name)).collect() }
+    }
+
+    pub fn from_ron(source: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::from_str(source)
+    }
+
+    pub fn index_of(&self, name: &str) -> Option<u8> {
+        self.passes.iter().position(|p| p.name == name).map(|i| i as u8)
+    }
+
+    pub fn name_of(&self, index: u8) -> Option<&str> {
+        self.passes.get(index as usize).map(|p| p.name.as_str())
+    }
+
+    // Configuration for a pass index; indices beyond the declared passes run every phase
+    pub fn pass(&self, index: u8) -> PassConfig {
+        self.passes
+            .get(index as usize)
+            .cloned()
+            .unwrap_or_else(|| PassConfig::full(format!("pass{}", index)))
+    }
+
+    pub fn last_index(&self) -> Option<u8> {
+        self.passes.len().checked_sub(1).map(|i| i as u8)
+    }
+}
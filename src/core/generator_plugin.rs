@@ -1,23 +1,52 @@
-use bevy::app::{App, Plugin};
+use bevy::app::{App, Plugin, Update};
+use bevy::prelude::IntoSystemConfigs;
 
 use crate::spawning::helpers::GenRng;
 use crate::core::tags::Tags;
 use crate::serialization::caching::MaterialCache;
 use crate::core::components::{PathPolyline, PathPolylineList};
 use crate::management::material_autoloader::MaterialAutoloader;
+use crate::spawning::object_logic::TeamPalette;
+use crate::core::generation_pipeline::GenerationPipeline;
+use crate::core::navmesh_config::{NavMeshAuthoring, NavMeshConfig};
+use crate::core::connectivity_config::ConnectivityConfig;
+use crate::event_system::connectivity_check::ConnectivityPhase;
+use crate::event_system::generation_phases::AddGenerationPhase;
+use crate::spawning::pathfinding::{follow_pathfinder_paths, poll_pathfinder_tasks, replan_on_tile_rebuild, request_pathfinder_goals, PathfinderGoal};
 
-pub struct GeneratorPlugin;
+// `GeneratorPlugin::default()`, or with navmesh authoring:
+// `GeneratorPlugin { navmesh: NavMeshConfig { agent_radius: Some(0.4), ..default() }, ..default() }`
+#[derive(Default)]
+pub struct GeneratorPlugin {
+    pub navmesh: NavMeshConfig,
+    // Reachability check between tagged entities after each navmesh build
+    pub connectivity: Option<ConnectivityConfig>,
+}
 
 impl Plugin for GeneratorPlugin {
     fn build(&self, app: &mut App) {
         app
             .insert_resource(GenRng::new(132))
+            .insert_resource(NavMeshAuthoring { base: self.navmesh.clone(), overrides: Vec::new() })
             .insert_resource(MaterialCache::new())
+            .init_resource::<TeamPalette>()
+            .init_resource::<GenerationPipeline>()
             .add_plugins(MaterialAutoloader)
             .add_plugins(crate::materials::path_blend::PathBlendPlugin)
             .add_plugins(crate::event_system::event_system_plugin::EventSystemPlugin)
             .register_type::<Tags>()
             .register_type::<PathPolyline>()
-            .register_type::<PathPolylineList>();
+            .register_type::<PathPolylineList>()
+            // Pathfinder units move whenever the generator runs, with or without ObjectLogicPlugin
+            .add_event::<PathfinderGoal>()
+            .add_systems(Update, (
+                request_pathfinder_goals,
+                replan_on_tile_rebuild,
+                poll_pathfinder_tasks,
+                follow_pathfinder_paths,
+            ).chain());
+        if let Some(connectivity) = self.connectivity.clone() {
+            app.add_generation_phase(ConnectivityPhase::new(connectivity));
+        }
     }
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 pub mod structure_key;
 pub mod collider;
+pub mod primitive_shape;
 pub mod structure_reference;
 pub mod rand_data;
 pub mod spread_data;
@@ -12,4 +13,12 @@ pub mod structure_error;
 pub mod tmaterial;
 pub mod components;
 pub mod generator_plugin;
-pub mod wobble;
\ No newline at end of file
+pub mod generation_pipeline;
+pub mod wobble;
+pub mod path_fallback;
+pub mod path_cost;
+pub mod path_shaping;
+pub mod path_placement;
+pub mod generated_paths;
+pub mod navmesh_config;
+pub mod connectivity_config;
\ No newline at end of file
@@ -0,0 +1,157 @@
+use bevy::prelude::*;
+use oxidized_navigation::NavMeshSettings;
+use serde::{Serialize, Deserialize};
+use crate::core::tags::Tags;
+use crate::spawning::object_logic::ObjectType;
+
+// Navmesh authoring, set on GeneratorPlugin and overridden per run by StructureKey::NavMeshSettings.
+// Lengths are in meters; unset fields keep the value of the NavMeshSettings the app inserted.
+// Cell and tile sizes only take effect before the first navmesh build.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct NavMeshConfig {
+    #[serde(default)]
+    pub agent_radius: Option<f32>,
+    #[serde(default)]
+    pub agent_height: Option<f32>,
+    // Highest ledge an agent can step up
+    #[serde(default)]
+    pub step_height: Option<f32>,
+    #[serde(default)]
+    pub max_slope_degrees: Option<f32>,
+    #[serde(default)]
+    pub cell_width: Option<f32>,
+    #[serde(default)]
+    pub cell_height: Option<f32>,
+    // Tile side in cells
+    #[serde(default)]
+    pub tile_width: Option<u16>,
+    // Replaces NavMeshPriorityThreshold: colliders with a lower priority do not affect the navmesh
+    #[serde(default)]
+    pub priority_threshold: Option<i8>,
+    #[serde(default)]
+    pub affectors: Option<NavMeshAffectorFilter>,
+    // Extra navmeshes for other agent sizes, selected by name with `agent` on PathToTag/PathToAllTags
+    #[serde(default)]
+    pub agent_profiles: Vec<AgentProfile>,
+}
+
+// Which colliders count as navmesh affectors. An empty list does not filter.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct NavMeshAffectorFilter {
+    #[serde(default)]
+    pub object_types: Vec<ObjectType>,
+    // The collider entity needs at least one of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub exclude_tags: Vec<String>,
+}
+
+// A second navmesh for agents of another size, e.g. `(name: "vehicle", radius: 1.5, height: 2.5, cell_width: Some(0.5))`.
+// It is built from the same affectors as a single tile over the generated area, so keep its cells coarse.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AgentProfile {
+    pub name: String,
+    pub radius: f32,
+    pub height: f32,
+    #[serde(default)]
+    pub step_height: Option<f32>,
+    #[serde(default)]
+    pub max_slope_degrees: Option<f32>,
+    #[serde(default)]
+    pub cell_width: Option<f32>,
+    #[serde(default)]
+    pub cell_height: Option<f32>,
+}
+
+impl NavMeshConfig {
+    // Set fields of `other` win; profiles replace those of the same name
+    pub fn merge(&mut self, other: &NavMeshConfig) {
+        fn take<T: Clone>(into: &mut Option<T>, from: &Option<T>) {
+            if from.is_some() { *into = from.clone(); }
+        }
+        take(&mut self.agent_radius, &other.agent_radius);
+        take(&mut self.agent_height, &other.agent_height);
+        take(&mut self.step_height, &other.step_height);
+        take(&mut self.max_slope_degrees, &other.max_slope_degrees);
+        take(&mut self.cell_width, &other.cell_width);
+        take(&mut self.cell_height, &other.cell_height);
+        take(&mut self.tile_width, &other.tile_width);
+        take(&mut self.priority_threshold, &other.priority_threshold);
+        take(&mut self.affectors, &other.affectors);
+        for profile in other.agent_profiles.iter() {
+            match self.agent_profiles.iter_mut().find(|p| p.name == profile.name) {
+                Some(existing) => *existing = profile.clone(),
+                None => self.agent_profiles.push(profile.clone()),
+            }
+        }
+    }
+
+    // Write the grid fields (cell and tile sizes) into `settings`
+    pub fn apply_grid(&self, settings: &mut NavMeshSettings) {
+        if let Some(cell_width) = self.cell_width { settings.cell_width = cell_width.max(0.01); }
+        if let Some(cell_height) = self.cell_height { settings.cell_height = cell_height.max(0.01); }
+        if let Some(tile_width) = self.tile_width { settings.tile_width = tile_width.max(8); }
+    }
+
+    // Write the agent fields into `settings`, converted to cells of its current grid
+    pub fn apply_agent(&self, settings: &mut NavMeshSettings) {
+        set_agent(settings, self.agent_radius, self.agent_height, self.step_height, self.max_slope_degrees);
+    }
+}
+
+impl AgentProfile {
+    // Settings for this profile's navmesh, starting from the main navmesh settings
+    pub fn settings(&self, base: &NavMeshSettings) -> NavMeshSettings {
+        let mut settings = base.clone();
+        if let Some(cell_width) = self.cell_width { settings.cell_width = cell_width.max(0.01); }
+        if let Some(cell_height) = self.cell_height { settings.cell_height = cell_height.max(0.01); }
+        set_agent(&mut settings, Some(self.radius), Some(self.height), self.step_height, self.max_slope_degrees);
+        settings
+    }
+}
+
+fn set_agent(settings: &mut NavMeshSettings, radius: Option<f32>, height: Option<f32>, step: Option<f32>, slope_degrees: Option<f32>) {
+    if let Some(radius) = radius { settings.walkable_radius = (radius / settings.cell_width).ceil().max(0.0) as u16; }
+    if let Some(height) = height { settings.walkable_height = (height / settings.cell_height).ceil().max(1.0) as u16; }
+    if let Some(step) = step { settings.step_height = (step / settings.cell_height).floor().max(0.0) as u16; }
+    if let Some(slope) = slope_degrees { settings.max_traversable_slope_radians = slope.clamp(0.0, 89.0).to_radians(); }
+}
+
+impl NavMeshAffectorFilter {
+    pub fn matches(&self, object_type: Option<&ObjectType>, tags: Option<&Tags>) -> bool {
+        if !self.object_types.is_empty() && !object_type.is_some_and(|t| self.object_types.contains(t)) {
+            return false;
+        }
+        let has = |tag: &String| tags.is_some_and(|tags| tags.contains(tag));
+        if !self.tags.is_empty() && !self.tags.iter().any(has) {
+            return false;
+        }
+        !self.exclude_tags.iter().any(has)
+    }
+}
+
+// The plugin's navmesh config and the overrides structures added this run, in dispatch order
+#[derive(Resource, Default, Clone)]
+pub struct NavMeshAuthoring {
+    pub base: NavMeshConfig,
+    pub overrides: Vec<NavMeshConfig>,
+}
+
+impl NavMeshAuthoring {
+    pub fn effective(&self) -> NavMeshConfig {
+        let mut config = self.base.clone();
+        for o in self.overrides.iter() { config.merge(o); }
+        config
+    }
+}
+
+// Area of the navmesh a StructureKey::NavArea volume marks
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavAreaType {
+    // Walkable, with this area id (0 is the default ground area). Path costs per id come from the
+    // NavArea keys' `cost`.
+    Area(u16),
+    // Not walkable
+    Blocked,
+}
@@ -0,0 +1,56 @@
+use serde::{Serialize, Deserialize};
+use crate::core::fbm_data::FBMData;
+
+// Cost model for a PathToTag/PathToAllTags key. When set, the path is searched with A* over a grid laid
+// on the navmesh instead of taking the shortest navmesh route. Every weight defaults to off.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PathCost {
+    // Grid cell size in meters (raised automatically for long paths to bound the search)
+    #[serde(default = "default_cell_size")]
+    pub cell_size: f32,
+    // Extra room around the start/end bounding box the path may detour through, in meters
+    #[serde(default = "default_margin")]
+    pub margin: f32,
+    // Cost added per unit of rise over run
+    #[serde(default)]
+    pub slope: f32,
+    // Keep-away costs around tagged entities, e.g. stay 3m from "building"
+    #[serde(default)]
+    pub clearances: Vec<TagClearance>,
+    // 0..1 discount on cells already covered by an earlier path of this run, so paths merge into roads
+    #[serde(default)]
+    pub road_reuse: f32,
+    #[serde(default)]
+    pub noise: Option<NoiseCost>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TagClearance {
+    pub tag: String,
+    // Distance under which the cost applies; it falls off linearly from `weight` at the entity to 0 here
+    pub distance: f32,
+    pub weight: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NoiseCost {
+    pub fbm: FBMData,
+    // Cost added where the noise (mapped to 0..1) is highest
+    pub weight: f32,
+}
+
+fn default_cell_size() -> f32 { 0.5 }
+fn default_margin() -> f32 { 8.0 }
+
+impl Default for PathCost {
+    fn default() -> Self {
+        PathCost {
+            cell_size: default_cell_size(),
+            margin: default_margin(),
+            slope: 0.0,
+            clearances: Vec::new(),
+            road_reuse: 0.0,
+            noise: None,
+        }
+    }
+}
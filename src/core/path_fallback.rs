@@ -0,0 +1,18 @@
+use serde::{Serialize, Deserialize};
+use crate::core::structure_reference::StructureReference;
+
+// Frames a PathToTag/PathToAllTags request is retried (tag not spawned yet, navmesh not ready, no
+// path from the start) before it gives up, when the key sets no max_retries
+pub const DEFAULT_PATH_RETRIES: u32 = 30;
+
+// What a path request does once its retries are spent
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub enum PathFallback {
+    // Spawn nothing along the path
+    #[default]
+    Skip,
+    // Spawn along a straight line from the start to the target (needs a tagged target)
+    StraightLine,
+    // Spawn this structure at the key's transform instead
+    SpawnAlternate(StructureReference),
+}
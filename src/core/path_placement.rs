@@ -0,0 +1,42 @@
+use serde::{Serialize, Deserialize};
+
+// Where spawns go relative to a path, e.g. lamp posts 3m to both sides of a road facing it:
+// `(lateral_offset: 3.0, sides: Both, facing: TowardPath)`
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PathPlacement {
+    // Sideways distance from the path in meters
+    #[serde(default)]
+    pub lateral_offset: f32,
+    #[serde(default)]
+    pub sides: PathSides,
+    #[serde(default)]
+    pub facing: PathFacing,
+    // Path length in meters left empty at the start and at the end
+    #[serde(default)]
+    pub start_inset: f32,
+    #[serde(default)]
+    pub end_inset: f32,
+}
+
+// Left/right as seen walking from the start of the path to its end
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathSides {
+    // On the path itself, ignoring lateral_offset; TowardPath and AwayFromPath face along the tangent
+    #[default]
+    Center,
+    Left,
+    Right,
+    // One spawn on each side per position; a single one on the path when lateral_offset is 0
+    Both,
+    // Left, right, left, ... per position
+    Alternate,
+}
+
+// Which way a spawn's +Z axis points
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathFacing {
+    #[default]
+    AlongTangent,
+    TowardPath,
+    AwayFromPath,
+}
@@ -0,0 +1,35 @@
+use serde::{Serialize, Deserialize};
+
+// Post-processing of a path polyline before spawning along it. Steps run in field order:
+// simplify, round corners, smooth, enforce the turn radius, resample. Every step defaults to off.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PathShaping {
+    // Douglas-Peucker tolerance in meters; drops points that deviate less than this from the line
+    #[serde(default)]
+    pub simplify_tolerance: f32,
+    // Replaces each corner with an arc of this radius (shrunk where the segments are too short)
+    #[serde(default)]
+    pub corner_radius: f32,
+    #[serde(default)]
+    pub smoothing: PathSmoothing,
+    // Straightens bends tighter than this radius by dropping their corner points
+    #[serde(default)]
+    pub min_turn_radius: f32,
+    // Even spacing of the output points in meters
+    #[serde(default)]
+    pub resample_spacing: f32,
+    // Distance the shaped curve must keep from the navmesh edge. Resolved paths (PathToTag/PathToAllTags)
+    // are checked against the navmesh and fall back to gentler shaping, then none, when they leave it.
+    #[serde(default)]
+    pub clearance: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub enum PathSmoothing {
+    #[default]
+    None,
+    // Corner cutting; each iteration doubles the point count
+    Chaikin { iterations: u8 },
+    // Catmull-Rom through the points, sampled this many times per segment
+    CatmullRom { samples_per_segment: u8 },
+}
@@ -0,0 +1,125 @@
+use serde::{Serialize, Deserialize};
+use bevy::prelude::*;
+use crate::core::collider::{ColliderInfo, ColliderType};
+
+// Procedural primitives that can be authored directly in .arch files (blockout floors, walls, pillars).
+// All shapes are centred on their origin; flat shapes (Plane, Circle) lie in the XZ plane facing +Y.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum PrimitiveShape {
+    Cuboid {
+        size: Vec3,
+    },
+    Cylinder {
+        radius: f32,
+        height: f32,
+    },
+    Plane {
+        size: Vec2,
+    },
+    Sphere {
+        radius: f32,
+    },
+    Circle {
+        radius: f32,
+    },
+    Torus {
+        minor_radius: f32,
+        major_radius: f32,
+    },
+    Capsule {
+        radius: f32,
+        // Length of the cylindrical middle section, excluding the hemispheres
+        length: f32,
+    },
+}
+
+// Collider for a StructureKey::Mesh: either derived from the primitive or given explicitly
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub enum MeshCollider {
+    #[default]
+    Auto,
+    Explicit(ColliderInfo),
+}
+
+// Thickness given to the colliders of flat shapes so they can be stood on
+const FLAT_COLLIDER_HALF_THICKNESS: f32 = 0.01;
+
+impl PrimitiveShape {
+    pub fn to_mesh(&self) -> Mesh {
+        match self {
+            PrimitiveShape::Cuboid { size } => Cuboid::from_size(*size).into(),
+            PrimitiveShape::Cylinder { radius, height } => Cylinder::new(*radius, *height).into(),
+            PrimitiveShape::Plane { size } => Plane3d::default().mesh().size(size.x, size.y).into(),
+            PrimitiveShape::Sphere { radius } => Sphere::new(*radius).mesh().uv(32, 18),
+            // Bevy builds circles in the XY plane; turn it to face up like Plane
+            PrimitiveShape::Circle { radius } => Mesh::from(Circle::new(*radius))
+                .rotated_by(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)),
+            PrimitiveShape::Torus { minor_radius, major_radius } => Torus::new(
+                major_radius - minor_radius,
+                major_radius + minor_radius,
+            ).into(),
+            PrimitiveShape::Capsule { radius, length } => Capsule3d::new(*radius, *length).into(),
+        }
+    }
+
+    // Collider matching the primitive. Tori have no rapier counterpart and use a trimesh of the generated
+    // mesh, so the hole stays open.
+    pub fn auto_collider_type(&self) -> ColliderType {
+        match self {
+            PrimitiveShape::Cuboid { size } => ColliderType::Cuboid {
+                hx: size.x * 0.5,
+                hy: size.y * 0.5,
+                hz: size.z * 0.5,
+            },
+            PrimitiveShape::Cylinder { radius, height } => ColliderType::Cylinder {
+                half_height: height * 0.5,
+                radius: *radius,
+            },
+            PrimitiveShape::Plane { size } => ColliderType::Cuboid {
+                hx: size.x * 0.5,
+                hy: FLAT_COLLIDER_HALF_THICKNESS,
+                hz: size.y * 0.5,
+            },
+            PrimitiveShape::Sphere { radius } => ColliderType::Ball { radius: *radius },
+            PrimitiveShape::Circle { radius } => ColliderType::Cylinder {
+                half_height: FLAT_COLLIDER_HALF_THICKNESS,
+                radius: *radius,
+            },
+            PrimitiveShape::Torus { .. } => ColliderType::TrimeshFromMesh,
+            PrimitiveShape::Capsule { radius, length } => ColliderType::CapsuleY {
+                half_height: length * 0.5,
+                radius: *radius,
+            },
+        }
+    }
+
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            PrimitiveShape::Cuboid { .. } => "Cuboid",
+            PrimitiveShape::Cylinder { .. } => "Cylinder",
+            PrimitiveShape::Plane { .. } => "Plane",
+            PrimitiveShape::Sphere { .. } => "Sphere",
+            PrimitiveShape::Circle { .. } => "Circle",
+            PrimitiveShape::Torus { .. } => "Torus",
+            PrimitiveShape::Capsule { .. } => "Capsule",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::collider::{create_collider_from_geometry, ColliderGeometry};
+    use crate::event_system::generation_error::PendingErrors;
+
+    #[test]
+    fn torus_collider_leaves_the_hole_open() {
+        let torus = PrimitiveShape::Torus { minor_radius: 0.25, major_radius: 1.0 };
+        let geometry = ColliderGeometry::from_mesh(&torus.to_mesh());
+        let collider = create_collider_from_geometry(&torus.auto_collider_type(), Some(&geometry), &PendingErrors::default())
+            .expect("torus mesh builds a trimesh");
+        let hits = |x: f32| collider.cast_ray(Vec3::ZERO, Quat::IDENTITY, Vec3::new(x, 5.0, 0.0), Vec3::NEG_Y, 10.0, true).is_some();
+        assert!(!hits(0.0), "a ray through the hole hits nothing");
+        assert!(hits(1.0), "a ray onto the ring hits it");
+    }
+}
@@ -5,6 +5,7 @@ use serde::{Serialize, Deserialize};
 use rand::prelude::SliceRandom;
 use crate::core::structure_key::StructureKey;
 use crate::core::structure_reference::StructureReference;
+use crate::core::path_fallback::PathFallback;
 
 use crate::core::structure_error::StructureError;
 use crate::management::structure_management::import_structure;
@@ -82,11 +83,17 @@ pub(crate) fn propagate_team_ownership(structure: &mut Structure, team_id: u8) {
             StructureKey::NestingLoop { reference, .. } |
             StructureKey::NoiseSpawn { reference, .. } |
             StructureKey::PathSpawn { reference, .. } |
-            StructureKey::PathToTag { reference, .. } |
             StructureKey::RandDistDir { reference, .. } |
             StructureKey::Reflection { reference, .. } => {
                 update_ownership(reference, team_id);
             }
+            StructureKey::PathToTag { reference, fallback, .. } |
+            StructureKey::PathToAllTags { reference, fallback, .. } => {
+                update_ownership(reference, team_id);
+                if let PathFallback::SpawnAlternate(alternate) = fallback {
+                    update_ownership(alternate, team_id);
+                }
+            }
             StructureKey::Object { ownership, .. } => {
                 if let Ownership::Inherit = ownership {
                     *ownership = Ownership::Team(team_id);
@@ -1,3 +1,5 @@
+use std::fmt;
+
 #[derive(Debug)]
 pub enum StructureError {
     CycleDetected(String),
@@ -11,3 +13,16 @@ impl From<&str> for StructureError {
         StructureError::Other(error.to_string())
     }
 }
+
+impl fmt::Display for StructureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StructureError::CycleDetected(msg)
+            | StructureError::ImportFailed(msg)
+            | StructureError::Other(msg)
+            | StructureError::InheritOwnershipAtTopLevel(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for StructureError {}
@@ -7,16 +7,27 @@ use serde::{Serialize, Deserialize};
 use bevy::prelude::*;
 use crate::core::collider::ColliderInfo;
 use crate::core::fbm_data::FBMData;
+use crate::core::primitive_shape::{MeshCollider, PrimitiveShape};
 use crate::core::rand_data::RandData;
 use crate::core::sample_size::SampleSize;
 use crate::core::spread_data::SpreadData;
 use crate::core::structure_reference::StructureReference;
+use crate::core::tmaterial::{MaterialOverride, TMaterial};
 use crate::core::wobble::WobbleParams;
+use crate::core::path_fallback::PathFallback;
+use crate::core::path_cost::PathCost;
+use crate::core::path_shaping::PathShaping;
+use crate::core::path_placement::PathPlacement;
+use crate::core::navmesh_config::{NavAreaType, NavMeshConfig};
 use crate::event_system::spawn_events::*;
+use crate::event_system::work_tickets::send_tracked;
+use crate::event_system::generation_log::SPAWN;
+use crate::event_system::generation_error::{GenerationError, GenerationErrorKind, PendingErrors};
 use crate::management::structure_management::import_structure;
 use crate::spawning::euler_transform::EulerTransform;
 use crate::spawning::object_logic::{ObjectType, Ownership};
 use bevy::ecs::world::World;
+use crate::spawning::occupancy::OccupancyClaimed;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum VisibilityMode {
@@ -36,6 +47,14 @@ pub enum StructureKey {
         object_type: ObjectType,
         #[serde(default)]
         visibility: Option<VisibilityMode>,
+        #[serde(default)]
+        material_overrides: Vec<MaterialOverride>,
+    },
+    Mesh {
+        shape: PrimitiveShape,
+        material: TMaterial,
+        #[serde(default)]
+        collider: MeshCollider,
     },
     #[serde(with = "SerializablePointLight")]
     PointLight(PointLight),
@@ -51,6 +70,26 @@ pub enum StructureKey {
     #[serde(with = "SerializableDistanceFog")]
     DistanceFog(DistanceFog),
     BackgroundMusic(String),
+    // Navmesh agent dimensions, grid and affector rules for this run (see NavMeshConfig)
+    NavMeshSettings(NavMeshConfig),
+    // Off-mesh connection for bridges, ladders and doors, between two points local to the structure.
+    // Path searches may cross it at `cost` times its length.
+    NavLink {
+        from: Vec3,
+        to: Vec3,
+        #[serde(default = "default_true")]
+        bidirectional: bool,
+        #[serde(default = "default_link_cost")]
+        cost: f32,
+    },
+    // Gives the navmesh under the top surface of `shape` an area type, e.g. a road Plane laid on the
+    // ground with `(area_type: Area(1), cost: Some(0.5))`. `cost` sets the path cost of that area id.
+    NavArea {
+        shape: PrimitiveShape,
+        area_type: NavAreaType,
+        #[serde(default)]
+        cost: Option<f32>,
+    },
     AtmosphereNishita {
         sun_position: Vec3,
         rayleigh_multiplier: Vec3,
@@ -75,7 +114,11 @@ pub enum StructureKey {
         probability: f32,
     },
     InPass {
+        #[serde(default)]
         index: u8,
+        // Refer to a pass declared in the GenerationPipeline by name instead of by index
+        #[serde(default)]
+        pass: Option<String>,
         reference: StructureReference,
     },
     Loop {
@@ -113,6 +156,10 @@ pub enum StructureKey {
         tension: f32,
         spread: SpreadData,
         count: u32,
+        #[serde(default)]
+        shaping: Option<PathShaping>,
+        #[serde(default)]
+        placement: Option<PathPlacement>,
     },
     PathToTag {
         reference: StructureReference,
@@ -124,6 +171,21 @@ pub enum StructureKey {
         count: u32,
         wobble: Option<WobbleParams>,
         store_as: Option<String>,
+        // Frames to keep retrying an unresolved path; DEFAULT_PATH_RETRIES when unset
+        #[serde(default)]
+        max_retries: Option<u32>,
+        #[serde(default)]
+        fallback: PathFallback,
+        // Cost model for the route; None takes the shortest navmesh path
+        #[serde(default)]
+        cost: Option<PathCost>,
+        #[serde(default)]
+        shaping: Option<PathShaping>,
+        #[serde(default)]
+        placement: Option<PathPlacement>,
+        // Agent profile whose navmesh to path on; the main navmesh when unset
+        #[serde(default)]
+        agent: Option<String>,
     },
     PathToAllTags {
         reference: StructureReference,
@@ -135,6 +197,21 @@ pub enum StructureKey {
         count: u32,
         wobble: Option<WobbleParams>,
         store_as: Option<String>,
+        // Frames to keep retrying an unresolved path; DEFAULT_PATH_RETRIES when unset
+        #[serde(default)]
+        max_retries: Option<u32>,
+        #[serde(default)]
+        fallback: PathFallback,
+        // Cost model for the route; None takes the shortest navmesh path
+        #[serde(default)]
+        cost: Option<PathCost>,
+        #[serde(default)]
+        shaping: Option<PathShaping>,
+        #[serde(default)]
+        placement: Option<PathPlacement>,
+        // Agent profile whose navmesh to path on; the main navmesh when unset
+        #[serde(default)]
+        agent: Option<String>,
     },
     RandDistDir {
         reference: StructureReference,
@@ -158,6 +235,9 @@ pub enum StructureKey {
     },
 }
 
+fn default_true() -> bool { true }
+fn default_link_cost() -> f32 { 1.0 }
+
 impl StructureKey {
     pub fn variant_name(&self) -> String {
         match self {
@@ -166,6 +246,7 @@ impl StructureKey {
                 StructureReference::Raw { structure, .. } => structure.structure_name.clone(),
                 StructureReference::Ref { structure, .. } => structure.clone(),
             },
+            StructureKey::Mesh { shape, .. } => format!("Mesh {}", shape.variant_name()),
             StructureKey::PointLight { .. } => "PointLight".to_string(),
             StructureKey::ProbabilitySpawn { reference, .. } => match reference {
                 StructureReference::Raw { structure, .. } => format!("Prob {:?}", structure.structure_name.clone()),
@@ -191,6 +272,9 @@ impl StructureKey {
             StructureKey::MainDirectionalLight { .. } => "MainDirectionalLight".to_string(),
             StructureKey::AmbientLight { .. } => "AmbientLight".to_string(),
             StructureKey::DistanceFog { .. } => "FogSettings".to_string(),
+            StructureKey::NavMeshSettings(..) => "NavMeshSettings".to_string(),
+            StructureKey::NavLink { .. } => "NavLink".to_string(),
+            StructureKey::NavArea { shape, .. } => format!("NavArea {}", shape.variant_name()),
             StructureKey::AtmosphereNishita { .. } => "AtmosphereNishita".to_string(),
             StructureKey::BackgroundMusic { .. } => "BackgroundMusic".to_string(),
             StructureKey::SoundEffect { .. } => "SoundEffect".to_string(),
@@ -231,47 +315,55 @@ impl StructureKey {
                 StructureReference::Raw { structure, .. } => format!("SelectiveReplacement {:?}", structure.structure_name.clone()),
                 StructureReference::Ref { structure, .. } => format!("SelectiveReplacement {:?}", structure.clone()),
             },
-            StructureKey::InPass { index, reference } => match reference {
-                StructureReference::Raw { structure, .. } => format!("Pass{} {:?}", index, structure.structure_name.clone()),
-                StructureReference::Ref { structure, .. } => format!("Pass{} {:?}", index, structure.clone()),
-            },
+            StructureKey::InPass { index, pass, reference } => {
+                let label = pass.clone().unwrap_or_else(|| format!("Pass{}", index));
+                match reference {
+                    StructureReference::Raw { structure, .. } => format!("{} {:?}", label, structure.structure_name.clone()),
+                    StructureReference::Ref { structure, .. } => format!("{} {:?}", label, structure.clone()),
+                }
+            }
         }
     }
 
-    pub fn get_tags(&self) -> Option<Vec<String>> {
+    pub fn get_tags(&self, errors: &PendingErrors) -> Option<Vec<String>> {
         let tags = match self {
-            StructureKey::Nest(reference) => Self::extract_tags(reference),
-            StructureKey::Choose { list } => Self::extract_tags(list),
-            StructureKey::ChooseSome { list, .. } => Self::extract_tags(list),
-            StructureKey::Rand { reference, .. } => Self::extract_tags(reference),
-            StructureKey::ProbabilitySpawn { reference, .. } => Self::extract_tags(reference),
-            StructureKey::InPass { reference, .. } => Self::extract_tags(reference),
-            StructureKey::Loop { reference, .. } => Self::extract_tags(reference),
-            StructureKey::LoopParam { reference, .. } => Self::extract_tags(reference),
-            StructureKey::NestingLoop { reference, .. } => Self::extract_tags(reference),
-            StructureKey::NoiseSpawn { reference, .. } => Self::extract_tags(reference),
-            StructureKey::PathSpawn { reference, .. } => Self::extract_tags(reference),
-            StructureKey::PathToTag { reference, .. } => Self::extract_tags(reference),
-            StructureKey::PathToAllTags { reference, .. } => Self::extract_tags(reference),
-            StructureKey::Reflection { reference, .. } => Self::extract_tags(reference),
-            StructureKey::RandDistDir { reference, .. } => Self::extract_tags(reference),
+            StructureKey::Nest(reference) => Self::extract_tags(reference, errors),
+            StructureKey::Choose { list } => Self::extract_tags(list, errors),
+            StructureKey::ChooseSome { list, .. } => Self::extract_tags(list, errors),
+            StructureKey::Rand { reference, .. } => Self::extract_tags(reference, errors),
+            StructureKey::ProbabilitySpawn { reference, .. } => Self::extract_tags(reference, errors),
+            StructureKey::InPass { reference, .. } => Self::extract_tags(reference, errors),
+            StructureKey::Loop { reference, .. } => Self::extract_tags(reference, errors),
+            StructureKey::LoopParam { reference, .. } => Self::extract_tags(reference, errors),
+            StructureKey::NestingLoop { reference, .. } => Self::extract_tags(reference, errors),
+            StructureKey::NoiseSpawn { reference, .. } => Self::extract_tags(reference, errors),
+            StructureKey::PathSpawn { reference, .. } => Self::extract_tags(reference, errors),
+            StructureKey::PathToTag { reference, .. } => Self::extract_tags(reference, errors),
+            StructureKey::PathToAllTags { reference, .. } => Self::extract_tags(reference, errors),
+            StructureKey::Reflection { reference, .. } => Self::extract_tags(reference, errors),
+            StructureKey::RandDistDir { reference, .. } => Self::extract_tags(reference, errors),
             _ => Vec::new(), // Other variants do not contain a StructureReference
         };
 
-        if tags.len() == 0 {
+        if tags.is_empty() {
             None
         } else {
             Some(tags)
         }
     }
 
-    fn extract_tags(reference: &StructureReference) -> Vec<String> {
+    fn extract_tags(reference: &StructureReference, errors: &PendingErrors) -> Vec<String> {
         match reference {
             StructureReference::Raw { structure, .. } => structure.tags.clone(),
             StructureReference::Ref { structure, .. } => {
                 match import_structure(structure.clone()) {
                     Ok(imported_structure) => imported_structure.tags,
-                    Err(_) => vec!["Error".to_string()], // Insert "Error" tag if import fails
+                    Err(e) => {
+                        GenerationError::new(GenerationErrorKind::ImportFailed, format!("Failed to import structure '{}': {}", structure, e))
+                            .with_structure(structure.clone())
+                            .report(errors);
+                        Vec::new()
+                    }
                 }
             }
         }
@@ -285,32 +377,56 @@ impl StructureKey {
         commands.queue(move |world: &mut World| {
             match event_key {
                 StructureKey::Object { .. } => {
-                    world.send_event(SceneSpawnEvent {
+                    send_tracked(world, SceneSpawnEvent {
                         data: event_key,
                         transform,
                         parent
                     });
                 }
+                StructureKey::Mesh { shape, material, collider } => {
+                    let (collider_type, priority, overlap) = match collider {
+                        MeshCollider::Auto => (shape.auto_collider_type(), None, Default::default()),
+                        MeshCollider::Explicit(info) => (info.collider_type, Some(info.priority), info.overlap),
+                    };
+                    send_tracked(world, MeshSpawnEvent {
+                        mesh: shape.to_mesh(),
+                        transform,
+                        material,
+                        collider_type: Some(collider_type),
+                        priority,
+                        overlap,
+                        parent,
+                    });
+                }
                 StructureKey::PointLight(light) => {
-                    world.send_event(PointLightSpawnEvent { light, transform, parent });
+                    send_tracked(world, PointLightSpawnEvent { light, transform, parent });
                 }
                 StructureKey::SpotLight(light) => {
-                    world.send_event(SpotLightSpawnEvent { light, transform, parent });
+                    send_tracked(world, SpotLightSpawnEvent { light, transform, parent });
                 }
                 StructureKey::DirectionalLight(light) => {
-                    world.send_event(DirectionalLightSpawnEvent { light, transform, parent });
+                    send_tracked(world, DirectionalLightSpawnEvent { light, transform, parent });
                 }
                 StructureKey::MainDirectionalLight(light) => {
-                    world.send_event(MainDirectionalLightSpawnEvent { light, transform, parent });
+                    send_tracked(world, MainDirectionalLightSpawnEvent { light, transform, parent });
                 }
                 StructureKey::AmbientLight(light) => {
-                    world.send_event(AmbientLightSpawnEvent { light, transform, parent });
+                    send_tracked(world, AmbientLightSpawnEvent { light, transform, parent });
                 }
                 StructureKey::DistanceFog(fog) => {
-                    world.send_event(DistanceFogSpawnEvent { fog });
+                    send_tracked(world, DistanceFogSpawnEvent { fog });
+                }
+                StructureKey::NavMeshSettings(config) => {
+                    send_tracked(world, NavMeshSettingsSpawnEvent { config });
+                }
+                StructureKey::NavLink { from, to, bidirectional, cost } => {
+                    send_tracked(world, NavLinkSpawnEvent { from, to, bidirectional, cost, transform, parent });
+                }
+                StructureKey::NavArea { shape, area_type, cost } => {
+                    send_tracked(world, NavAreaSpawnEvent { shape, area_type, cost, transform, parent });
                 }
                 StructureKey::AtmosphereNishita { sun_position, rayleigh_multiplier, mie_multiplier, mie_direction, align_to_main_light } => {
-                    world.send_event(AtmosphereNishitaSpawnEvent {
+                    send_tracked(world, AtmosphereNishitaSpawnEvent {
                         sun_position,
                         rayleigh_multiplier,
                         mie_multiplier,
@@ -319,28 +435,31 @@ impl StructureKey {
                     });
                 }
                 StructureKey::SoundEffect(file) => {
-                    world.send_event(SoundEffectSpawnEvent { file });
+                    send_tracked(world, SoundEffectSpawnEvent { file });
                 }
                 StructureKey::BackgroundMusic(file) => {
-                    world.send_event(BackgroundMusicSpawnEvent { file });
+                    send_tracked(world, BackgroundMusicSpawnEvent { file });
                 }
                 StructureKey::Nest(reference) => {
-                    world.send_event(NestSpawnEvent { reference, transform, parent });
+                    let claimed = parent
+                        .and_then(|p| world.get::<OccupancyClaimed>(p))
+                        .and_then(OccupancyClaimed::nested);
+                    send_tracked(world, NestSpawnEvent { reference, transform, parent, sampler: None, claimed });
                 }
                 StructureKey::Choose { list } => {
-                    world.send_event(ChooseSpawnEvent { list, transform, parent });
+                    send_tracked(world, ChooseSpawnEvent { list, transform, parent });
                 }
                 StructureKey::ChooseSome { list, count } => {
-                    world.send_event(ChooseSomeSpawnEvent { list, count, transform, parent });
+                    send_tracked(world, ChooseSomeSpawnEvent { list, count, transform, parent });
                 }
                 StructureKey::Rand { reference, rand } => {
-                    world.send_event(RandSpawnEvent { reference, rand, transform, parent });
+                    send_tracked(world, RandSpawnEvent { reference, rand, transform, parent });
                 }
                 StructureKey::ProbabilitySpawn { reference, probability } => {
-                    world.send_event(ProbabilitySpawnEvent { reference, probability, transform, parent });
+                    send_tracked(world, ProbabilitySpawnEvent { reference, probability, transform, parent });
                 }
                 StructureKey::Loop { reference, shift_transform, child_transform, count } => {
-                    world.send_event(LoopSpawnEvent {
+                    send_tracked(world, LoopSpawnEvent {
                         reference,
                         shift_transform,
                         child_transform,
@@ -350,7 +469,7 @@ impl StructureKey {
                     });
                 }
                 StructureKey::LoopParam { reference, origin, rotation, distance, child_position, child_rotation, child_scale, count } => {
-                    world.send_event(LoopParamSpawnEvent {
+                    send_tracked(world, LoopParamSpawnEvent {
                         reference,
                         origin,
                         rotation,
@@ -364,7 +483,7 @@ impl StructureKey {
                     });
                 }
                 StructureKey::NestingLoop { reference, repeated_transform, count } => {
-                    world.send_event(NestingLoopSpawnEvent {
+                    send_tracked(world, NestingLoopSpawnEvent {
                         reference,
                         repeated_transform,
                         count,
@@ -373,7 +492,7 @@ impl StructureKey {
                     });
                 }
                 StructureKey::NoiseSpawn { reference, fbm, sample_size, count, exclusivity_radius, resolution_modifier } => {
-                    world.send_event(NoiseSpawnEvent {
+                    send_tracked(world, NoiseSpawnEvent {
                         reference,
                         fbm,
                         sample_size,
@@ -384,19 +503,21 @@ impl StructureKey {
                         parent,
                     });
                 }
-                StructureKey::PathSpawn { reference, points, tension, spread, count } => {
-                    world.send_event(PathSpawnEvent {
+                StructureKey::PathSpawn { reference, points, tension, spread, count, shaping, placement } => {
+                    send_tracked(world, PathSpawnEvent {
                         reference,
                         points,
                         tension,
                         spread,
                         count,
+                        shaping,
+                        placement,
                         transform,
                         parent,
                     });
                 }
-                StructureKey::PathToTag { reference, start, manual_points, tag, tension, spread, count, wobble, store_as } => {
-                    world.send_event(PathToTagSpawnEvent {
+                StructureKey::PathToTag { reference, start, manual_points, tag, tension, spread, count, wobble, store_as, max_retries, fallback, cost, shaping, placement, agent } => {
+                    send_tracked(world, PathToTagSpawnEvent {
                         reference,
                         start,
                         manual_points,
@@ -406,12 +527,19 @@ impl StructureKey {
                         count,
                         wobble,
                         store_as,
+                        max_retries,
+                        fallback,
+                        cost,
+                        shaping,
+                        placement,
+                        agent,
+                        retries: 0,
                         transform,
                         parent,
                     });
                 }
-                StructureKey::PathToAllTags { reference, start, manual_points, tag, tension, spread, count, wobble, store_as } => {
-                    world.send_event(PathToAllTagsSpawnEvent {
+                StructureKey::PathToAllTags { reference, start, manual_points, tag, tension, spread, count, wobble, store_as, max_retries, fallback, cost, shaping, placement, agent } => {
+                    send_tracked(world, PathToAllTagsSpawnEvent {
                         reference,
                         start,
                         manual_points,
@@ -421,12 +549,19 @@ impl StructureKey {
                         count,
                         wobble,
                         store_as,
+                        max_retries,
+                        fallback,
+                        cost,
+                        shaping,
+                        placement,
+                        agent,
+                        retries: 0,
                         transform,
                         parent,
                     });
                 }
                 StructureKey::Reflection { reference, reflection_plane, reflection_point, reflect_child } => {
-                    world.send_event(ReflectionSpawnEvent {
+                    send_tracked(world, ReflectionSpawnEvent {
                         reference,
                         reflection_plane,
                         reflection_point,
@@ -435,16 +570,17 @@ impl StructureKey {
                         parent,
                     });
                 }
-                StructureKey::InPass { index, reference } => {
-                    world.send_event(InPassSpawnEvent { index, reference, transform, parent });
+                StructureKey::InPass { index, pass, reference } => {
+                    send_tracked(world, InPassSpawnEvent { index, pass, reference, transform, parent });
                 }
                 StructureKey::RandDistDir { reference, dist_min, dist_max, angle_min_deg, angle_max_deg, y } => {
-                    #[cfg(feature = "debug")]
-                    println!(
-                        "[StructureKey::dispatch_event] Emitting RandDistDir: dist=[{:.2},{:.2}] angle=[{:.1},{:.1}] y={:.2}",
-                        dist_min, dist_max, angle_min_deg, angle_max_deg, y
+                    trace!(
+                        target: SPAWN,
+                        structure = reference.name(),
+                        dist_min, dist_max, angle_min_deg, angle_max_deg, y,
+                        "dispatch RandDistDir"
                     );
-                    world.send_event(RandDistDirSpawnEvent {
+                    send_tracked(world, RandDistDirSpawnEvent {
                         reference,
                         dist_min,
                         dist_max,
@@ -456,7 +592,7 @@ impl StructureKey {
                     });
                 }
                 StructureKey::SelectiveReplacement { initial_reference, replacement_reference, tags, replace_count } => {
-                    world.send_event(SelectiveReplacementSpawnEvent {
+                    send_tracked(world, SelectiveReplacementSpawnEvent {
                         initial_reference,
                         replacement_reference,
                         tags,
@@ -13,3 +13,13 @@ pub enum StructureReference {
         ownership: Ownership,
     },
 }
+
+impl StructureReference {
+    // Structure name for logs and errors: the referenced file name, or the inline structure's name
+    pub fn name(&self) -> &str {
+        match self {
+            StructureReference::Raw { structure, .. } => &structure.structure_name,
+            StructureReference::Ref { structure, .. } => structure,
+        }
+    }
+}
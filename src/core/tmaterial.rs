@@ -1,5 +1,8 @@
+use std::collections::HashMap;
 use serde::{Serialize, Deserialize};
-use bevy::prelude::Vec2;
+use bevy::prelude::*;
+use crate::serialization::caching::MaterialCache;
+use crate::serialization::serialization::SerializableAlphaMode;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum TMaterial {
@@ -24,4 +27,191 @@ pub enum TMaterial {
         // Optional secondary ambient occlusion texture path
         near_ao_path: Option<String>,
     },
+    // A material defined entirely in data; no MaterialCache entry is needed.
+    Inline {
+        base_color: Color,
+        #[serde(default)]
+        metallic: f32,
+        #[serde(default = "default_inline_roughness")]
+        roughness: f32,
+        #[serde(default = "default_inline_emissive")]
+        emissive: Color,
+        #[serde(default)]
+        textures: InlineTextures,
+        #[serde(default, with = "SerializableAlphaMode")]
+        alpha_mode: AlphaMode,
+        #[serde(default)]
+        double_sided: bool,
+    },
+}
+
+// Optional texture asset paths for an inline material (e.g., "materials/Grass/grass_albedo.png")
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct InlineTextures {
+    pub base_color: Option<String>,
+    pub normal: Option<String>,
+    pub metallic_roughness: Option<String>,
+    pub occlusion: Option<String>,
+    pub emissive: Option<String>,
+}
+
+// Replaces the material on glTF submeshes whose Name (or parent node Name) matches `mesh`.
+// `mesh` is a glob pattern, so "Banner*" or "*_team" match several submeshes at once.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MaterialOverride {
+    pub mesh: String,
+    pub material: TMaterial,
+}
+
+// StandardMaterials built from Inline definitions, keyed by the definition, so every object spawned
+// with the same inline material shares one asset
+#[derive(Resource, Default)]
+pub struct InlineMaterialCache(pub HashMap<InlineMaterialKey, Handle<StandardMaterial>>);
+
+// An Inline definition in hashable form: floats are compared by their bits and colors in linear space
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InlineMaterialKey {
+    base_color: [u32; 4],
+    metallic: u32,
+    roughness: u32,
+    emissive: [u32; 4],
+    textures: [Option<String>; 5],
+    // Variant index, plus the cutoff for Mask
+    alpha_mode: (u8, u32),
+    double_sided: bool,
+}
+
+fn color_bits(color: Color) -> [u32; 4] {
+    LinearRgba::from(color).to_f32_array().map(f32::to_bits)
+}
+
+fn alpha_mode_bits(mode: AlphaMode) -> (u8, u32) {
+    match mode {
+        AlphaMode::Opaque => (0, 0),
+        AlphaMode::Mask(cutoff) => (1, cutoff.to_bits()),
+        AlphaMode::Blend => (2, 0),
+        AlphaMode::Premultiplied => (3, 0),
+        AlphaMode::AlphaToCoverage => (4, 0),
+        AlphaMode::Add => (5, 0),
+        AlphaMode::Multiply => (6, 0),
+    }
+}
+
+fn default_inline_roughness() -> f32 { 0.5 }
+
+fn default_inline_emissive() -> Color { Color::BLACK }
+
+impl TMaterial {
+    // Build a StandardMaterial for an Inline definition. Cache-backed variants return None.
+    pub fn build_inline(&self, asset_server: &AssetServer) -> Option<StandardMaterial> {
+        let TMaterial::Inline { base_color, metallic, roughness, emissive, textures, alpha_mode, double_sided } = self else {
+            return None;
+        };
+        let load = |path: &Option<String>| path.as_ref().map(|p| asset_server.load(p.as_str()));
+        Some(StandardMaterial {
+            base_color: *base_color,
+            base_color_texture: load(&textures.base_color),
+            metallic: *metallic,
+            perceptual_roughness: *roughness,
+            metallic_roughness_texture: load(&textures.metallic_roughness),
+            normal_map_texture: load(&textures.normal),
+            occlusion_texture: load(&textures.occlusion),
+            emissive: LinearRgba::from(*emissive),
+            emissive_texture: load(&textures.emissive),
+            alpha_mode: *alpha_mode,
+            double_sided: *double_sided,
+            // Back faces are only culled for single-sided materials
+            cull_mode: if *double_sided { None } else { Some(bevy::render::render_resource::Face::Back) },
+            ..Default::default()
+        })
+    }
+
+    // Cache key for an Inline definition. Cache-backed variants return None.
+    pub fn inline_key(&self) -> Option<InlineMaterialKey> {
+        let TMaterial::Inline { base_color, metallic, roughness, emissive, textures, alpha_mode, double_sided } = self else {
+            return None;
+        };
+        Some(InlineMaterialKey {
+            base_color: color_bits(*base_color),
+            metallic: metallic.to_bits(),
+            roughness: roughness.to_bits(),
+            emissive: color_bits(*emissive),
+            textures: [
+                textures.base_color.clone(),
+                textures.normal.clone(),
+                textures.metallic_roughness.clone(),
+                textures.occlusion.clone(),
+                textures.emissive.clone(),
+            ],
+            alpha_mode: alpha_mode_bits(*alpha_mode),
+            double_sided: *double_sided,
+        })
+    }
+
+    // Resolve to a StandardMaterial handle: inline definitions are added to the asset store once per
+    // distinct definition, named variants are looked up in the MaterialCache (PathBlend/Tiled resolve
+    // to their base material).
+    pub fn resolve_standard(
+        &self,
+        material_cache: &MaterialCache,
+        materials: &mut Assets<StandardMaterial>,
+        inline_cache: &mut InlineMaterialCache,
+        asset_server: &AssetServer,
+    ) -> Option<Handle<StandardMaterial>> {
+        match self {
+            TMaterial::BasicMaterial { material_name }
+            | TMaterial::TiledMaterial { material_name, .. }
+            | TMaterial::PathBlend { material_name, .. } => material_cache.get(material_name).cloned(),
+            TMaterial::Inline { .. } => {
+                let key = self.inline_key()?;
+                if let Some(handle) = inline_cache.0.get(&key) {
+                    return Some(handle.clone());
+                }
+                let handle = materials.add(self.build_inline(asset_server)?);
+                inline_cache.0.insert(key, handle.clone());
+                Some(handle)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inline(alpha_mode: AlphaMode) -> TMaterial {
+        TMaterial::Inline {
+            base_color: Color::srgb(0.5, 0.2, 0.1),
+            metallic: 0.0,
+            roughness: 0.5,
+            emissive: Color::BLACK,
+            textures: InlineTextures::default(),
+            alpha_mode,
+            double_sided: false,
+        }
+    }
+
+    #[test]
+    fn identical_inline_definitions_share_a_key() {
+        assert_eq!(inline(AlphaMode::Opaque).inline_key(), inline(AlphaMode::Opaque).inline_key());
+        // The same color given in linear space is the same material
+        let linear = TMaterial::Inline {
+            base_color: Color::LinearRgba(LinearRgba::from(Color::srgb(0.5, 0.2, 0.1))),
+            metallic: 0.0,
+            roughness: 0.5,
+            emissive: Color::BLACK,
+            textures: InlineTextures::default(),
+            alpha_mode: AlphaMode::Opaque,
+            double_sided: false,
+        };
+        assert_eq!(linear.inline_key(), inline(AlphaMode::Opaque).inline_key());
+    }
+
+    #[test]
+    fn alpha_cutoff_is_part_of_the_key() {
+        assert_ne!(inline(AlphaMode::Mask(0.5)).inline_key(), inline(AlphaMode::Mask(0.25)).inline_key());
+        assert_ne!(inline(AlphaMode::Blend).inline_key(), inline(AlphaMode::Opaque).inline_key());
+        let named = TMaterial::BasicMaterial { material_name: "Grass".into() };
+        assert!(named.inline_key().is_none());
+    }
 }
@@ -0,0 +1,313 @@
+use std::collections::HashSet;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::{Collider, DefaultRapierContext, QueryFilter, RapierContext};
+use oxidized_navigation::query::find_path;
+use oxidized_navigation::{ActiveGenerationTasks, NavMesh, NavMeshAffector, NavMeshSettings};
+use crate::core::collider::ColliderPriority;
+use crate::core::connectivity_config::{ConnectivityConfig, ConnectivityRemedy};
+use crate::core::generation_pipeline::GenerationPipeline;
+use crate::core::tags::Tags;
+use crate::event_system::event_listeners::{CurrentPass, GenerationState, HighestPassIndex, ResolvedPathSpawns};
+use crate::event_system::generation_error::{GenerationError, GenerationErrorKind, PendingErrors};
+use crate::event_system::generation_phases::{GenerationPhase, PhaseAnchor, PhaseContext};
+use crate::event_system::generation_report::GenerationReport;
+use crate::event_system::generation_log::PATH;
+use crate::event_system::spawn_events::PathSpawnEvent;
+use crate::spawning::euler_transform::EulerTransform;
+use crate::spawning::nav_links::{find_path_with_links, LinkWalks, NavAreaCosts, NavLinks};
+use crate::spawning::path_solver::area_weighted_length;
+use crate::spawning::agent_navmesh::NavMeshAffectorParts;
+
+// Frames to wait after despawning blockers before trusting the navmesh again; the affected tiles are
+// only marked dirty and queued over the next frames
+const SETTLE_FRAMES: u32 = 3;
+// Gap kept between the underside of a DespawnBlockers corridor and the entities it joins
+const GROUND_CLEARANCE: f32 = 0.05;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Check,
+    Settling(u32),
+}
+
+// Custom phase after the last NavMeshBuilding of a run that checks every entity tagged with one of
+// the configured tags can reach the others and applies the configured remedy when some cannot. Added
+// by GeneratorPlugin when it has a `connectivity` config, or directly:
+// `app.add_generation_phase(ConnectivityPhase::new(config))`
+pub struct ConnectivityPhase {
+    config: ConnectivityConfig,
+    step: Step,
+    // Remedies applied since the check last passed or gave up
+    attempts: u32,
+}
+
+impl ConnectivityPhase {
+    pub fn new(config: ConnectivityConfig) -> Self {
+        ConnectivityPhase { config, step: Step::Check, attempts: 0 }
+    }
+}
+
+// Whether the navmesh built in the current pass is the last one of the run: none of the passes after
+// it that are known so far rebuilds the navmesh. Earlier builds are checked against a navmesh that
+// later passes still change, and passes that skip NavMeshBuilding would check a stale one.
+fn is_final_navmesh_build(world: &World) -> bool {
+    let pipeline = world.resource::<GenerationPipeline>();
+    let cur = world.resource::<CurrentPass>().0;
+    let highest = world.resource::<HighestPassIndex>().0;
+    pipeline.pass(cur).rebuild_navmesh && (cur as u16 + 1..=highest as u16).all(|p| !pipeline.pass(p as u8).rebuild_navmesh)
+}
+
+struct Tagged {
+    entity: Entity,
+    name: String,
+    position: Vec3,
+}
+
+impl GenerationPhase for ConnectivityPhase {
+    fn name(&self) -> &str {
+        "connectivity"
+    }
+
+    fn anchor(&self) -> PhaseAnchor {
+        PhaseAnchor::After(GenerationState::NavMeshBuilding)
+    }
+
+    fn on_enter(&mut self, _world: &mut World, _ctx: &PhaseContext) {
+        self.step = Step::Check;
+    }
+
+    fn is_complete(&mut self, world: &mut World, _ctx: &PhaseContext) -> bool {
+        if !is_final_navmesh_build(world) {
+            return true;
+        }
+        if let Step::Settling(frames) = self.step {
+            let busy = world.get_resource::<ActiveGenerationTasks>().is_some_and(|tasks| !tasks.is_empty());
+            if frames < SETTLE_FRAMES || busy {
+                self.step = Step::Settling(frames + 1);
+                return false;
+            }
+            self.step = Step::Check;
+        }
+
+        let tagged = tagged_entities(world, &self.config.tags);
+        let Some((connected, isolated)) = split_by_reachability(world, &tagged, self.config.search_radius) else {
+            warn!(target: PATH, "connectivity check without a navmesh; skipping");
+            self.attempts = 0;
+            return true;
+        };
+        if isolated.is_empty() {
+            debug!(target: PATH, entities = tagged.len(), "connectivity check passed");
+            world.resource_mut::<GenerationReport>().isolated.clear();
+            self.attempts = 0;
+            return true;
+        }
+
+        let names: Vec<String> = isolated.iter().map(|&i| tagged[i].name.clone()).collect();
+        debug!(target: PATH, isolated = ?names, attempts = self.attempts, "connectivity check found isolated entities");
+        if self.attempts < self.config.max_attempts {
+            // Each isolated entity is joined to the nearest entity it should reach
+            let links: Vec<(Vec3, Vec3)> = isolated.iter()
+                .filter_map(|&i| {
+                    let from = tagged[i].position;
+                    connected.iter()
+                        .map(|&j| tagged[j].position)
+                        .min_by(|a, b| a.distance_squared(from).total_cmp(&b.distance_squared(from)))
+                        .map(|to| (from, to))
+                })
+                .collect();
+            let protected: Vec<Entity> = tagged.iter().map(|t| t.entity).collect();
+            let applied = match &self.config.remedy {
+                ConnectivityRemedy::DespawnBlockers { corridor_width, probe_height, max_priority } => {
+                    let despawned = despawn_blockers(world, &links, &protected, *corridor_width, *probe_height, *max_priority);
+                    if despawned > 0 { self.step = Step::Settling(0); }
+                    despawned > 0
+                }
+                ConnectivityRemedy::CarvePath { reference, spread, count, placement } => {
+                    for &(from, to) in links.iter() {
+                        world.resource_mut::<ResolvedPathSpawns>().0.push(PathSpawnEvent {
+                            reference: reference.clone(),
+                            points: vec![from, to],
+                            tension: 0.0,
+                            spread: spread.clone(),
+                            count: *count,
+                            shaping: None,
+                            placement: placement.clone(),
+                            transform: EulerTransform::default(),
+                            parent: None,
+                        });
+                    }
+                    // The carved paths are generated by running this pass's Generating phase again, after
+                    // which the navmesh is rebuilt and checked again
+                    debug!(target: PATH, paths = links.len(), structure = reference.name(), "connectivity: carving paths");
+                    !links.is_empty()
+                }
+                ConnectivityRemedy::Fail => false,
+            };
+            if applied {
+                self.attempts += 1;
+                world.resource_mut::<GenerationReport>().connectivity_remedies += 1;
+                return matches!(self.config.remedy, ConnectivityRemedy::CarvePath { .. });
+            }
+        }
+
+        let mut error = GenerationError::new(
+            GenerationErrorKind::Unreachable,
+            format!("{} of {} tagged entities cannot reach the others: {}", names.len(), tagged.len(), names.join(", ")),
+        );
+        if matches!(self.config.remedy, ConnectivityRemedy::Fail) { error = error.fatal(); }
+        error.report(world.resource::<PendingErrors>());
+        world.resource_mut::<GenerationReport>().isolated = names;
+        self.attempts = 0;
+        true
+    }
+}
+
+fn tagged_entities(world: &mut World, tags: &[String]) -> Vec<Tagged> {
+    let mut query = world.query::<(Entity, &Tags, &GlobalTransform, Option<&Name>)>();
+    query.iter(world)
+        .filter(|(_, entity_tags, _, _)| tags.iter().any(|tag| entity_tags.contains(tag)))
+        .map(|(entity, _, global, name)| Tagged {
+            entity,
+            name: name.map(|n| n.as_str().to_string()).unwrap_or_else(|| format!("{:?}", entity)),
+            position: global.translation(),
+        })
+        .collect()
+}
+
+// Indices of the largest group of mutually reachable entities and of everything outside it, or None
+// without a navmesh
+fn split_by_reachability(world: &World, tagged: &[Tagged], search_radius: f32) -> Option<(Vec<usize>, Vec<usize>)> {
+    let tiles = world.get_resource::<NavMesh>()?.get();
+    let settings = world.get_resource::<NavMeshSettings>()?;
+    let links = world.resource::<NavLinks>();
+    let costs = world.resource::<NavAreaCosts>().multipliers();
+    let tiles = tiles.read().ok()?;
+    // One-way links make reachability directional, so both directions are searched then
+    let one_way = links.0.iter().any(|link| !link.bidirectional);
+    let mut link_walks = LinkWalks::default();
+    let mut walks = |a: Vec3, b: Vec3| {
+        find_path_with_links(
+            &links.0, a, b, &mut link_walks,
+            |x, y| find_path(&tiles, settings, x, y, Some(search_radius), costs).ok(),
+            |points| area_weighted_length(&tiles, settings, costs, points),
+        ).is_some()
+    };
+
+    let positions: Vec<Vec3> = tagged.iter().map(|t| t.position).collect();
+    let groups = group_by_reachability(&positions, |a, b| walks(a, b) && (!one_way || walks(b, a)));
+    let largest = groups.iter().enumerate().max_by_key(|(i, g)| (g.len(), std::cmp::Reverse(*i))).map(|(i, _)| i);
+    let mut connected = Vec::new();
+    let mut isolated = Vec::new();
+    for (i, group) in groups.into_iter().enumerate() {
+        if Some(i) == largest { connected = group; } else { isolated.extend(group); }
+    }
+    Some((connected, isolated))
+}
+
+// Indices of `positions` grouped so that every member of a group reaches every other. Reachability
+// both ways is transitive, so each position is only checked against the first member of the groups
+// found so far.
+fn group_by_reachability(positions: &[Vec3], mut reaches: impl FnMut(Vec3, Vec3) -> bool) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for (i, &position) in positions.iter().enumerate() {
+        match groups.iter_mut().find(|group| reaches(positions[group[0]], position)) {
+            Some(group) => group.push(i),
+            None => groups.push(vec![i]),
+        }
+    }
+    groups
+}
+
+// Whether `entity` is one of `protected` or sits under one of them
+fn is_protected(world: &World, entity: Entity, protected: &[Entity]) -> bool {
+    let mut current = Some(entity);
+    while let Some(e) = current {
+        if protected.contains(&e) { return true; }
+        current = world.get::<Parent>(e).map(|p| p.get());
+    }
+    false
+}
+
+// Despawn, per line, the navmesh affectors with the lowest ColliderPriority that the corridor along
+// it touches. Returns how many were despawned.
+fn despawn_blockers(
+    world: &mut World,
+    lines: &[(Vec3, Vec3)],
+    protected: &[Entity],
+    corridor_width: f32,
+    probe_height: f32,
+    max_priority: Option<i8>,
+) -> usize {
+    let mut context_query = world.query_filtered::<&RapierContext, With<DefaultRapierContext>>();
+    let Ok(context) = context_query.get_single(world) else {
+        warn!(target: PATH, "connectivity: no physics context to find blockers with");
+        return 0;
+    };
+    // The capsule's underside is kept above both entities, so the ground they stand on is not a blocker
+    let radius = (corridor_width * 0.5).max(0.01);
+    let up = Vec3::Y * probe_height.max(radius + GROUND_CLEARANCE);
+    let mut doomed: HashSet<Entity> = HashSet::new();
+    for &(from, to) in lines.iter() {
+        let corridor = Collider::capsule(from + up, to + up, radius);
+        let mut hits: Vec<(Entity, i8)> = Vec::new();
+        context.intersections_with_shape(Vec3::ZERO, Quat::IDENTITY, &corridor, QueryFilter::new().exclude_sensors(), |entity| {
+            if let (Some(priority), true) = (world.get::<ColliderPriority>(entity), world.get::<NavMeshAffector>(entity).is_some() || world.get::<NavMeshAffectorParts>(entity).is_some()) {
+                if max_priority.is_none_or(|max| priority.0 <= max) && !is_protected(world, entity, protected) {
+                    hits.push((entity, priority.0));
+                }
+            }
+            true
+        });
+        let Some(lowest) = hits.iter().map(|(_, p)| *p).min() else { continue; };
+        doomed.extend(hits.iter().filter(|(_, p)| *p == lowest).map(|(e, _)| *e));
+    }
+    for &entity in doomed.iter() {
+        let name = world.get::<Name>(entity).map(|n| n.as_str().to_string());
+        debug!(target: PATH, ?entity, name = name.as_deref(), "connectivity: despawning blocker");
+        if let Ok(ent) = world.get_entity_mut(entity) {
+            ent.despawn_recursive();
+        }
+    }
+    doomed.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::generation_pipeline::PassConfig;
+    use super::*;
+
+    #[test]
+    fn checks_only_after_the_last_navmesh_build() {
+        let decals = PassConfig { rebuild_navmesh: false, ..PassConfig::full("decals") };
+        let mut world = World::new();
+        world.insert_resource(GenerationPipeline::new(vec![PassConfig::full("terrain"), PassConfig::full("props"), decals]));
+        world.insert_resource(HighestPassIndex(2));
+        let final_build = |world: &mut World, pass: u8| {
+            world.insert_resource(CurrentPass(pass));
+            is_final_navmesh_build(world)
+        };
+        assert!(!final_build(&mut world, 0));
+        assert!(final_build(&mut world, 1));
+        // The decals pass leaves the navmesh as it was
+        assert!(!final_build(&mut world, 2));
+        // A pass discovered later that rebuilds the navmesh takes over
+        world.insert_resource(HighestPassIndex(3));
+        assert!(!final_build(&mut world, 1));
+        assert!(final_build(&mut world, 3));
+    }
+
+    #[test]
+    fn groups_against_one_member_each() {
+        // Two islands split at x = 10
+        let positions: Vec<Vec3> = [0.0, 12.0, 3.0, 15.0, 5.0].iter().map(|&x| Vec3::new(x, 0.0, 0.0)).collect();
+        let mut checks = 0;
+        let groups = group_by_reachability(&positions, |a, b| {
+            checks += 1;
+            (a.x < 10.0) == (b.x < 10.0)
+        });
+        assert_eq!(groups, vec![vec![0, 2, 4], vec![1, 3]]);
+        // Each position is checked against the groups before it until one takes it, never against every
+        // other position
+        assert_eq!(checks, 1 + 1 + 2 + 1);
+    }
+}
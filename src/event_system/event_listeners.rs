@@ -1,19 +1,34 @@
-#[allow(unused_variables)]
-
 use bevy::prelude::*;
 #[cfg(feature = "atmosphere")]
 use bevy_atmosphere::prelude::{AtmosphereModel, Nishita};
 use bevy::render::mesh::MeshAabb;
-use bevy_rapier3d::prelude::{ActiveCollisionTypes, ActiveEvents, CollisionEvent, Collider, ContactForceEvent, ContactForceEventThreshold, Damping, Dominance, LockedAxes, RigidBody, Sleeping};
+use bevy::asset::RecursiveDependencyLoadState;
+use bevy_rapier3d::prelude::{ActiveCollisionTypes, ActiveEvents, Collider, ContactForceEventThreshold, Damping, Dominance, LockedAxes, Sensor, Sleeping};
 use oxidized_navigation::NavMeshAffector;
 use crate::event_system::spawn_events::*;
-use crate::core::tmaterial::TMaterial;
+use crate::core::tmaterial::{InlineMaterialCache, MaterialOverride, TMaterial};
 use crate::serialization::caching::MaterialCache;
 use std::path::Path;
-use std::collections::HashSet;
-use crate::spawning::object_logic::{ObjectType, Pathfinder, PathState, Selectable};
+use std::collections::HashMap;
+use std::cell::RefCell;
+use crate::spawning::object_logic::{ObjectType, Ownership, Pathfinder, PathState, Selectable, TeamPalette};
 use crate::core::structure_key::StructureKey;
-use crate::core::collider::{ColliderBehaviour, ColliderPriority};
+use crate::core::generation_pipeline::{GenerationPipeline, PassConfig};
+use crate::event_system::generation_phases::{route_transition, CustomPhaseQueue, GenerationPhases};
+use crate::event_system::work_tickets::{complete_tickets, send_tracked, WorkTicket, WorkTickets};
+use crate::core::collider::{create_collider_from_geometry, ColliderGeometry, ColliderPriority, ColliderType, OverlapStrategy};
+use crate::event_system::overlap_resolution::{PendingOverlaps, SpawnSampler};
+use crate::event_system::generation_log::{MATERIAL, PATH, PHASE, SPAWN};
+use crate::event_system::generation_error::{GenerationError, GenerationErrorKind, PendingErrors};
+use crate::event_system::generation_budget::{defer_over_budget, FrameBudget};
+use crate::event_system::generation_report::{expansion_depth, ExpansionDepth, GenerationReport, ListenerTimer, ReportSamples};
+use crate::spawning::path_solver::{CostGridSolver, DefaultPathSolver, PathQuery, PathSolver};
+use crate::spawning::path_shaping::{shape_path, shape_walkable_path};
+use crate::core::navmesh_config::NavMeshAuthoring;
+use crate::spawning::agent_navmesh::{add_navmesh_affector, AgentNavMeshes};
+use crate::spawning::nav_links::{find_path_with_links, LinkWalks, NavAreaCosts, NavAreaVolume, NavLinkMarker, NavLinks};
+use crate::event_system::path_failure::{defer_path_request, fail_path_request, retry_path_request, time_out_path_requests, PathFailure, PathFailureReason};
+use crate::spawning::occupancy::{footprints_at, structure_footprint, world_transform, Footprint, LocalFootprint, OccupancyClaimed, OccupancyGrid};
 use crate::spawning::helpers::*;
 use crate::spawning::light_spawning::{spawn_point_light, spawn_spot_light};
 use crate::core::components::MainCamera;
@@ -22,20 +37,27 @@ use crate::core::structure_reference::StructureReference;
 use crate::core::components::MainDirectionalLight;
 use crate::event_system::spawnables::structure::spawn_structure_data;
 use crate::core::tags::Tags;
-use crate::core::components::{PathPolyline, PathPolylineList};
+use crate::core::components::PathPolylineList;
 use bevy::math::EulerRot;
 use crate::materials::path_blend::{GroundPathMaterial, PathBlendMaterial, PathBlendParams, make_path_blend_material};
 use bevy_pbr::StandardMaterial;
-
-// In non-debug builds, silence generation logging in this module by shadowing println!/info!
-// This keeps verbose generation output behind the `debug` feature flag without altering call sites.
-#[cfg(not(feature = "debug"))]
-macro_rules! println { ($($arg:tt)*) => {}; }
+use bevy::scene::SceneInstanceReady;
 
 // Buffer for PathSpawnEvent produced during PathResolve; these will be flushed in the next Generating
 #[derive(Resource, Default)]
 pub struct ResolvedPathSpawns(pub Vec<PathSpawnEvent>);
 
+// World-space polylines of the paths resolved this run, so cost-based solvers can reuse existing roads
+#[derive(Resource, Default)]
+pub struct ResolvedPolylines(pub Vec<Vec<Vec3>>);
+
+// Path requests buffered for PathResolve vs. those that finished resolving, for GenerationProgress
+#[derive(Resource, Default, Clone, Copy)]
+pub struct PathResolveCounters {
+    pub queued: u32,
+    pub resolved: u32,
+}
+
 // Apply all stored polylines from any PathPolylineList entity to any PathBlend material
 // whose entity shares at least one matching tag. Each list is applied independently,
 // and segments are built per-polyline without connecting across polylines.
@@ -61,18 +83,19 @@ pub fn apply_stored_polylines_to_tagged_pathblend(
                 mat.extension.params.set_segments_from_polylines(&list.0);
                 total_targets += 1;
                 total_polylines += list_poly_count;
-                eprintln!(
-                    "[PathBlend] Applied {} stored polylines to tagged target; total segments now {}",
-                    list_poly_count,
-                    mat.extension.params.flags.z
+                debug!(
+                    target: MATERIAL,
+                    polylines = list_poly_count,
+                    segments = mat.extension.params.flags.z,
+                    "applied stored polylines to tagged PathBlend target"
                 );
             } else {
-                eprintln!("[PathBlend] Target PathBlendMaterial handle not found while applying stored polylines");
+                warn!(target: MATERIAL, "PathBlendMaterial handle not found while applying stored polylines");
             }
         }
     }
     if total_targets > 0 {
-        eprintln!("[PathBlend] Applied stored polylines to {} target(s) ({} polylines)", total_targets, total_polylines);
+        debug!(target: MATERIAL, targets = total_targets, polylines = total_polylines, "applied stored polylines");
     }
 }
 
@@ -83,11 +106,11 @@ pub fn apply_world_path_points_to_material(
     target: Option<Res<GroundPathMaterial>>,
 ) {
     let Some(target) = target else {
-        eprintln!("[PathBlend] GroundPathMaterial resource not yet available; skipping PathWorldPointsEvent(s)");
+        trace!(target: MATERIAL, "GroundPathMaterial resource not yet available; skipping PathWorldPointsEvent(s)");
         return;
     };
     let Some(handle) = &target.0 else {
-        eprintln!("[PathBlend] GroundPathMaterial handle not set yet; skipping PathWorldPointsEvent(s)");
+        trace!(target: MATERIAL, "GroundPathMaterial handle not set yet; skipping PathWorldPointsEvent(s)");
         return;
     };
     if let Some(mat) = mats.get_mut(handle) {
@@ -96,35 +119,27 @@ pub fn apply_world_path_points_to_material(
         let mut total_points = 0usize;
         let mut events = 0usize;
         for ev in reader.read() {
-            eprintln!("[PathBlend] Applying PathWorldPointsEvent with {} points:", ev.points.len());
-            for (i, p) in ev.points.iter().enumerate() {
-                eprintln!("  [World][{}] {:?}", i, p);
-            }
+            trace!(target: MATERIAL, points = ?ev.points, "applying PathWorldPointsEvent");
             if ev.points.len() >= 2 { polylines.push(ev.points.clone()); }
             total_points += ev.points.len();
             events += 1;
         }
         if !polylines.is_empty() {
             mat.extension.params.set_segments_from_polylines(&polylines);
-            eprintln!(
-                "[PathBlend] Applied {} PathWorldPointsEvent(s) ({} polylines, {} points); total segments now {}",
+            debug!(
+                target: MATERIAL,
                 events,
-                polylines.len(),
-                total_points,
-                mat.extension.params.flags.z
+                polylines = polylines.len(),
+                points = total_points,
+                segments = mat.extension.params.flags.z,
+                "applied PathWorldPointsEvent(s)"
             );
         }
     } else {
-        eprintln!("[PathBlend] PathBlendMaterial handle not found in asset storage");
+        warn!(target: MATERIAL, "PathBlendMaterial handle not found in asset storage");
     }
 }
 
-// Bring in the Bevy log `info!` macro when debug feature is enabled,
-// otherwise define a no-op so the calls compile away in release.
-#[cfg(feature = "debug")]
-use bevy::log::info;
-#[cfg(not(feature = "debug"))]
-macro_rules! info { ($($arg:tt)*) => {}; }
 
 // When present (and true), the sun_position of the Atmosphere Nishita model is aligned each frame to the
 // authored MainDirectionalLight orientation. This resource is authored via .arch through the AtmosphereNishita key.
@@ -136,8 +151,12 @@ pub struct AtmosphereAlignToMainLight(pub bool);
 pub fn atmosphere_nishita_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<AtmosphereNishitaSpawnEvent>,
+    samples: Res<ReportSamples>,
 ) {
-    for ev in reader.read() {
+    let _timer = ListenerTimer::start("atmosphere_nishita_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (ev, id) in reader.read_with_id() {
+        handled.push(id);
         let mut n = Nishita::default();
         let sp = ev.sun_position;
         let len2 = sp.length_squared();
@@ -155,16 +174,19 @@ pub fn atmosphere_nishita_spawn_listener(
         // Also set (or clear) the alignment preference resource per authored value
         commands.insert_resource(AtmosphereAlignToMainLight(ev.align_to_main_light));
     }
+    complete_tickets(&mut commands, handled);
 }
 
 pub fn loop_param_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<LoopParamSpawnEvent>,
-    mut activity: ResMut<SpawnActivity>,
+    (samples, budget): (Res<ReportSamples>, Res<FrameBudget>),
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
+    let _timer = ListenerTimer::start("loop_param_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
+        if defer_over_budget(&mut commands, event, &budget) { continue; }
         // Container for grouping loop spawns
         let container = commands
             .spawn_empty()
@@ -213,55 +235,76 @@ pub fn loop_param_spawn_listener(
 
             let reference = event.reference.clone();
             commands.queue(move |world: &mut World| {
-                world.send_event(NestSpawnEvent { reference, transform: euler, parent: Some(container) });
+                send_tracked(world, NestSpawnEvent { reference, transform: euler, parent: Some(container), sampler: None, claimed: None });
             });
         }
     }
-    if processed { activity.idle_frames = 0; }
+    complete_tickets(&mut commands, handled);
 }
 
 // On entering Generating, flush any PathSpawnEvent captured during PathResolve
 pub fn flush_resolved_paths_on_enter_generating(
     mut resolved: ResMut<ResolvedPathSpawns>,
+    mut pending_nests: ResMut<PendingNests>,
     mut commands: Commands,
 ) {
-    if resolved.0.is_empty() { return; }
-    println!("[PathBuffer] Flushing {} buffered PathSpawnEvent(s) to Generating", resolved.0.len());
+    if resolved.0.is_empty() && pending_nests.0.is_empty() { return; }
+    debug!(target: PATH, count = resolved.0.len(), nests = pending_nests.0.len(), "flushing buffered PathSpawnEvent(s) to Generating");
     let to_send = std::mem::take(&mut resolved.0);
+    let nests = std::mem::take(&mut pending_nests.0);
     commands.queue(move |world: &mut World| {
         for ev in to_send.into_iter() {
-            println!("[PathBuffer] -> emit PathSpawnEvent: points={}, parent={:?}", ev.points.len(), ev.parent);
-            world.send_event(ev);
+            trace!(target: PATH, points = ev.points.len(), parent = ?ev.parent, "emit buffered PathSpawnEvent");
+            send_tracked(world, ev);
+        }
+        for ev in nests.into_iter() {
+            send_tracked(world, ev);
         }
     });
 }
 
+// Tagged transforms, parent transforms and tagged path holders the path listeners look up
+type PathLookups<'w, 's> = (
+    Query<'w, 's, (&'static GlobalTransform, &'static Tags)>,
+    Query<'w, 's, &'static GlobalTransform>,
+    Query<'w, 's, (Entity, &'static Tags)>,
+);
+// Present once the navmesh plugin is running
+type NavMeshAccess<'w> = (
+    Option<Res<'w, oxidized_navigation::NavMesh>>,
+    Option<Res<'w, oxidized_navigation::NavMeshSettings>>,
+    Option<Res<'w, oxidized_navigation::ActiveGenerationTasks>>,
+);
+// What a path search is run with: the solver for keys without a cost model, the run RNG, per-agent
+// navmeshes, off-mesh links and area costs
+type PathSolving<'w> = (Res<'w, DefaultPathSolver>, Res<'w, GenRng>, Res<'w, AgentNavMeshes>, Res<'w, NavLinks>, Res<'w, NavAreaCosts>);
+// Where resolved paths are recorded, the pass and progress counters they update, and where listener
+// timings and errors are reported
+type PathBookkeeping<'w> = (
+    ResMut<'w, ResolvedPathSpawns>,
+    ResMut<'w, PendingNests>,
+    ResMut<'w, ResolvedPolylines>,
+    ResMut<'w, HighestPassIndex>,
+    Option<Res<'w, CurrentPass>>,
+    ResMut<'w, PathResolveCounters>,
+    Res<'w, ReportSamples>,
+    Res<'w, PendingErrors>,
+);
+
 // Variant: path to ALL entities that have the given tag, emitting one PathSpawnEvent per target
 pub fn path_to_all_tags_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<PathToAllTagsSpawnEvent>,
-    tag_query: Query<(&GlobalTransform, &Tags)>,
-    nav_mesh: Option<Res<oxidized_navigation::NavMesh>>,
-    settings: Option<Res<oxidized_navigation::NavMeshSettings>>,
-    active_tasks: Option<Res<oxidized_navigation::ActiveGenerationTasks>>,
+    (tag_query, parent_query, store_query): PathLookups,
+    (nav_mesh, settings, active_tasks): NavMeshAccess,
+    (default_solver, gen_rng, agent_navmeshes, nav_links, area_costs): PathSolving,
     #[cfg(feature = "debug")] mut all_dbg: ResMut<AllPathsDebug>,
-    parent_query: Query<&GlobalTransform>,
-    mut activity: ResMut<SpawnActivity>,
-    store_query: Query<(Entity, &Tags)>,
-    mut resolved: ResMut<ResolvedPathSpawns>,
-    mut highest: ResMut<HighestPassIndex>,
-    cur_pass: Option<Res<CurrentPass>>,
+    (mut resolved, mut pending_nests, mut polylines, mut highest, cur_pass, mut path_counters, samples, errors): PathBookkeeping,
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        // If navmesh is still baking, defer deterministically
-        if let Some(tasks) = active_tasks.as_ref() {
-            if !tasks.is_empty() {
-                let ev = event.clone();
-                commands.queue(move |world: &mut World| { world.send_event(ev); });
-                continue;
-            }
-        }
+    let _timer = ListenerTimer::start("path_to_all_tags_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
         // Compute world-space base transform for this event
         let local_tf = Transform::from(event.transform.clone());
         let world_tf = match event.parent.and_then(|p| parent_query.get(p).ok()) {
@@ -270,6 +313,14 @@ pub fn path_to_all_tags_spawn_listener(
         };
         let base = world_tf.translation;
 
+        // If navmesh is still baking, wait for it; this does not spend a retry
+        if let Some(tasks) = active_tasks.as_ref() {
+            if !tasks.is_empty() || agent_navmeshes.is_building() {
+                defer_path_request(&mut commands, event);
+                continue;
+            }
+        }
+
         // Eagerly ensure a storage holder exists if store_as was provided, even before paths resolve
         if let Some(label) = event.store_as.as_ref() {
             let existing = store_query.iter().find(|(_, t)| t.contains(label)).map(|(e, _)| e);
@@ -281,7 +332,7 @@ pub fn path_to_all_tags_spawn_listener(
                     .insert(Name::new(format!("PathPolylineList: {}", label)))
                     .id();
                 if let Some(parent) = event.parent { commands.entity(e).set_parent(parent); }
-                eprintln!("[PathStore] Created holder 'PathPolylineList: {}' under parent {:?}", label, event.parent);
+                debug!(target: PATH, store_as = %label, parent = ?event.parent, "created PathPolylineList holder");
             }
         }
 
@@ -291,27 +342,66 @@ pub fn path_to_all_tags_spawn_listener(
             if tags.0.iter().any(|t| t == &event.tag) { targets.push(gt.translation()); }
         }
         if targets.is_empty() {
-            println!("[PathToAllTags] No entities found with tag '{}' yet; will retry next frame", event.tag);
-            let ev = event.clone();
-            commands.queue(move |world: &mut World| { world.send_event(ev); });
+            trace!(target: PATH, tag = %event.tag, retries = event.retries, "PathToAllTags: no entities with tag yet; retrying next frame");
+            if !retry_path_request(&mut commands, event, "PathToAllTags") {
+                let failure = PathFailure { reason: PathFailureReason::NoTaggedEntity, start: base + world_tf.rotation * event.start, end: None };
+                if fail_path_request(&mut commands, event, failure, &world_tf, &mut resolved, &mut pending_nests, &errors) {
+                    if let Some(cp) = cur_pass.as_ref() { if highest.0 < cp.0.saturating_add(1) { highest.0 = cp.0.saturating_add(1); } }
+                }
+            }
             continue;
         }
 
-        let (Some(nav_mesh), Some(settings)) = (nav_mesh.as_ref(), settings.as_ref()) else {
-            println!("[PathToAllTags] NavMesh/Settings not available yet; will retry next frame");
-            let ev = event.clone();
-            commands.queue(move |world: &mut World| { world.send_event(ev); });
+        // The agent profile's navmesh when the key names one, else the main navmesh
+        let navmesh = match event.agent.as_ref() {
+            Some(agent) => agent_navmeshes.get(agent),
+            None => nav_mesh.as_ref().zip(settings.as_ref()).map(|(m, s)| (m.get(), s.as_ref())),
+        };
+        let Some((tiles, settings)) = navmesh else {
+            trace!(target: PATH, retries = event.retries, agent = ?event.agent, "PathToAllTags: navmesh not available yet; retrying next frame");
+            if !retry_path_request(&mut commands, event, "PathToAllTags") {
+                // Give up on each target, so a StraightLine fallback draws one line per target
+                let mut buffered = false;
+                for end_pos in targets {
+                    let mut start_world = base + world_tf.rotation * event.start;
+                    start_world.y = end_pos.y + 0.05;
+                    let failure = PathFailure { reason: PathFailureReason::NavMeshUnavailable, start: start_world, end: Some(end_pos) };
+                    buffered |= fail_path_request(&mut commands, event, failure, &world_tf, &mut resolved, &mut pending_nests, &errors);
+                }
+                if buffered {
+                    if let Some(cp) = cur_pass.as_ref() { if highest.0 < cp.0.saturating_add(1) { highest.0 = cp.0.saturating_add(1); } }
+                }
+            }
             continue;
         };
 
         // For each target, build a path and enqueue a PathSpawnEvent
-        if let Ok(tiles) = nav_mesh.get().read() {
-            // Helper that captures tiles/settings without naming the private NavMeshTiles type
+        let mut new_polylines: Vec<Vec<Vec3>> = Vec::new();
+        if let Ok(tiles) = tiles.read() {
+            // Route through the key's cost model when it has one, else through the default solver
+            let tagged: Vec<(Vec3, &Tags)> = match event.cost {
+                Some(_) => tag_query.iter().map(|(gt, tags)| (gt.translation(), tags)).collect(),
+                None => Vec::new(),
+            };
+            let query = PathQuery {
+                tiles: &tiles,
+                settings,
+                tagged: &tagged,
+                roads: &polylines.0,
+                seed: gen_rng.seed(),
+                links: &nav_links.0,
+                area_costs: area_costs.multipliers(),
+            };
+            let grid_solver;
+            let solver: &dyn PathSolver = match event.cost.as_ref() {
+                Some(cost) => { grid_solver = CostGridSolver { cost: cost.clone() }; &grid_solver }
+                None => default_solver.0.as_ref(),
+            };
+            // Walks between link ends are shared by every try of this request
+            let link_walks = RefCell::new(LinkWalks::default());
             let try_between = |s: Vec3, e: Vec3| -> Option<Vec<Vec3>> {
-                match oxidized_navigation::query::find_path(&tiles, &settings, s, e, None, None) {
-                    Ok(points) if points.len() >= 2 => Some(points),
-                    _ => None,
-                }
+                let walks = &mut link_walks.borrow_mut();
+                find_path_with_links(query.links, s, e, walks, |a, b| solver.find_path(&query, a, b), |points| solver.path_cost(&query, points))
             };
             for end_pos_raw in targets {
                 // Rotate local start by world rotation; snap Y to end plane
@@ -342,12 +432,22 @@ pub fn path_to_all_tags_spawn_listener(
                                     if let Some(p) = try_between(cand, end_pos) { found = Some(p); break 'outer_bp; }
                                 }
                             }
-                            match found { Some(p) => p, None => continue } // skip this target this frame
+                            match found {
+                                Some(p) => p,
+                                None => {
+                                    // Targets are not retried one by one (the others already resolved), so fail right away
+                                    let failure = PathFailure { reason: PathFailureReason::NoPath, start: start_world, end: Some(end_pos) };
+                                    if fail_path_request(&mut commands, event, failure, &world_tf, &mut resolved, &mut pending_nests, &errors) {
+                                        if let Some(cp) = cur_pass.as_ref() { if highest.0 < cp.0.saturating_add(1) { highest.0 = cp.0.saturating_add(1); } }
+                                    }
+                                    continue;
+                                }
+                            }
                         }
                     };
                     path_points.clear();
                     path_points.push(start_world);
-                    path_points.extend(world_manual.into_iter());
+                    path_points.extend(world_manual);
                     if base_path.len() > 1 { path_points.extend_from_slice(&base_path[1..]); }
                 } else {
                     if let Some(points) = try_between(start_world, end_pos) {
@@ -364,16 +464,22 @@ pub fn path_to_all_tags_spawn_listener(
                                 if let Some(points) = try_between(cand, end_pos) { found = Some(points); break 'outer; }
                             }
                         }
-                        match found { Some(points) => { path_points = points; }, None => continue }
+                        match found {
+                            Some(points) => { path_points = points; },
+                            None => {
+                                let failure = PathFailure { reason: PathFailureReason::NoPath, start: start_world, end: Some(end_pos) };
+                                if fail_path_request(&mut commands, event, failure, &world_tf, &mut resolved, &mut pending_nests, &errors) {
+                                    if let Some(cp) = cur_pass.as_ref() { if highest.0 < cp.0.saturating_add(1) { highest.0 = cp.0.saturating_add(1); } }
+                                }
+                                continue;
+                            }
+                        }
                     }
                 }
 
                 // Log all computed world-space points before further processing/storage
         if !path_points.is_empty() {
-            println!("[PathToTag] World polyline ({} points):", path_points.len());
-            for (i, p) in path_points.iter().enumerate() {
-                println!("  [World][{}] {:?}", i, p);
-            }
+            trace!(target: PATH, points = ?path_points, "PathToAllTags: world polyline");
         }
         if path_points.len() < 2 { continue; }
 
@@ -443,8 +549,8 @@ pub fn path_to_all_tags_spawn_listener(
 
                                 let mut ok = true;
                                 for w in checkpoints.windows(2) {
-                                    match oxidized_navigation::query::find_path(&tiles, &settings, w[0], w[1], None, None) {
-                                        Ok(sub) if sub.len() >= 2 => { new_path.extend_from_slice(&sub[1..]); }
+                                    match try_between(w[0], w[1]) {
+                                        Some(sub) => { new_path.extend_from_slice(&sub[1..]); }
                                         _ => { ok = false; break; }
                                     }
                                 }
@@ -455,12 +561,19 @@ pub fn path_to_all_tags_spawn_listener(
                     }
                 }
 
+                // Optional shaping, kept on the navmesh
+                if let Some(shaping) = event.shaping.as_ref() {
+                    path_points = shape_walkable_path(&path_points, shaping, &query);
+                }
+
                 // Debug store
                 #[cfg(feature = "debug")] {
                     all_dbg.paths.push(path_points.clone());
                     if all_dbg.paths.len() > 256 { all_dbg.paths.remove(0); }
                 }
 
+                new_polylines.push(path_points.clone());
+
                 // Convert to local points for the container and enqueue PathSpawnEvent
                 let inv_world = world_tf.compute_matrix().inverse();
                 let local_points: Vec<Vec3> = path_points.iter().map(|p| inv_world.transform_point3(*p)).collect();
@@ -469,21 +582,21 @@ pub fn path_to_all_tags_spawn_listener(
                 let tension = event.tension;
                 let spread = event.spread.clone();
                 let count = event.count;
+                let placement = event.placement.clone();
                 let transform = event.transform.clone();
                 let parent = event.parent;
                 let points_len = local_points.len();
-                resolved.0.push(PathSpawnEvent { reference, points: local_points, tension, spread, count, transform, parent });
-                println!("[PathBuffer] Buffered PathSpawnEvent (to_all): points={}, parent={:?}", points_len, parent);
+                resolved.0.push(PathSpawnEvent { reference, points: local_points, tension, spread, count, shaping: None, placement, transform, parent });
+                debug!(target: PATH, points = points_len, parent = ?parent, "buffered PathSpawnEvent (to_all)");
                 if let Some(cp) = cur_pass.as_ref() { if highest.0 < cp.0.saturating_add(1) { highest.0 = cp.0.saturating_add(1); } }
             }
         }
+        polylines.0.extend(new_polylines);
 
-        processed = true;
+        path_counters.resolved += 1;
     }
-    if processed { activity.idle_frames = 0; }
+    complete_tickets(&mut commands, handled);
 }
-#[cfg(not(feature = "debug"))]
-macro_rules! info { ($($arg:tt)*) => {}; }
 
 // Marker to indicate we've already stripped colliders for a GenerationOnlyCollider subtree
 #[derive(Component, Default)]
@@ -505,42 +618,84 @@ pub fn in_pass_spawn_listener(
     mut reader: EventReader<InPassSpawnEvent>,
     mut highest: ResMut<HighestPassIndex>,
     mut pending: ResMut<PendingInPass>,
+    mut tickets: ResMut<WorkTickets>,
+    pipeline: Res<GenerationPipeline>,
+    (samples, errors): (Res<ReportSamples>, Res<PendingErrors>),
 ) {
-    for event in reader.read() {
+    let _timer = ListenerTimer::start("in_pass_spawn_listener", &samples);
+    for (event, id) in reader.read_with_id() {
+        // Parked items are tracked by PendingInPass from here on
+        tickets.complete(WorkTicket::of(id));
+        let mut event = event.clone();
+        // Named passes resolve to their position in the pipeline
+        if let Some(name) = event.pass.as_ref() {
+            match pipeline.index_of(name) {
+                Some(index) => event.index = index,
+                None => {
+                    GenerationError::new(GenerationErrorKind::UnknownPass, format!("Pass '{}' is not declared in GenerationPipeline; skipping", name))
+                        .with_structure(event.reference.name())
+                        .with_parent(event.parent)
+                        .report(&errors);
+                    continue;
+                }
+            }
+        }
         if event.index > highest.0 { highest.0 = event.index; }
-        println!(
-            "[InPass] queued index={} parent={:?} (highest now {})",
-            event.index, event.parent, highest.0
+        debug!(
+            target: SPAWN,
+            structure = event.reference.name(),
+            index = event.index,
+            pass = ?event.pass,
+            parent = ?event.parent,
+            highest = highest.0,
+            "InPass queued"
         );
-        pending.0.push(event.clone());
+        pending.0.push(event);
+    }
+}
+
+// Declared pipeline passes always run, even when no structure refers to them
+pub fn raise_highest_pass_to_pipeline(
+    pipeline: Res<GenerationPipeline>,
+    mut highest: ResMut<HighestPassIndex>,
+) {
+    if let Some(last) = pipeline.last_index() {
+        if highest.0 < last { highest.0 = last; }
     }
 }
 
+// Phase that follows `after` in the current pass, skipping phases the pass does not need
+fn next_phase(after: &GenerationState, pass: &PassConfig) -> GenerationState {
+    let order = [
+        (GenerationState::CollisionResolution, pass.collision_despawn),
+        (GenerationState::NavMeshBuilding, pass.rebuild_navmesh),
+        (GenerationState::PathResolve, pass.resolve_paths),
+    ];
+    order
+        .iter()
+        .skip_while(|(phase, _)| phase <= after)
+        .find(|(_, enabled)| *enabled)
+        .map(|(phase, _)| phase.clone())
+        .unwrap_or(GenerationState::Completed)
+}
+
+// Run condition: collision despawn only runs in passes that ask for it
+pub fn pass_runs_collision_despawn(pipeline: Res<GenerationPipeline>, cur: Res<CurrentPass>) -> bool {
+    pipeline.pass(cur.0).collision_despawn
+}
+
 // Drain and execute only the items for the current pass
 pub fn process_pending_inpass(
     mut commands: Commands,
     mut pending: ResMut<PendingInPass>,
     cur: Res<CurrentPass>,
-    mut activity: ResMut<SpawnActivity>,
+    errors: Res<PendingErrors>,
 ) {
     if pending.0.is_empty() { return; }
     let mut rest: Vec<InPassSpawnEvent> = Vec::new();
-    let mut any_spawned = false;
     for ev in pending.0.drain(..) {
         if ev.index == cur.0 {
-            any_spawned = true;
-            // Derive a label for logging
-            #[cfg(feature = "debug")]
-            {
-                let label = match &ev.reference {
-                    StructureReference::Raw { structure, .. } => structure.structure_name.clone(),
-                    StructureReference::Ref { structure, .. } => structure.clone(),
-                };
-                println!(
-                    "[InPass] spawning index={} structure='{}' parent={:?}",
-                    ev.index, label, ev.parent
-                );
-            }
+            debug!(target: SPAWN, structure = ev.reference.name(), index = ev.index, parent = ?ev.parent, "InPass spawning");
             match Structure::try_from(&ev.reference) {
                 Ok(structure) => {
                     let _ = spawn_structure_data(
@@ -551,7 +706,10 @@ pub fn process_pending_inpass(
                     );
                 }
                 Err(e) => {
-                    eprintln!("[InPass] Import error: {:?}", e);
+                    GenerationError::structure_error(&e)
+                        .with_structure(ev.reference.name())
+                        .with_parent(ev.parent)
+                        .report(&errors);
                 }
             }
         } else {
@@ -559,7 +717,6 @@ pub fn process_pending_inpass(
         }
     }
     pending.0 = rest;
-    if any_spawned { activity.idle_frames = 0; }
 }
 
 #[derive(Resource, Default)]
@@ -579,38 +736,57 @@ pub fn rand_dist_dir_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<RandDistDirSpawnEvent>,
     mut gen_rng: ResMut<GenRng>,
-    mut activity: ResMut<SpawnActivity>,
+    mut occupancy: ResMut<OccupancyGrid>,
+    hierarchy: Query<(&Transform, Option<&Parent>)>,
+    (samples, budget): (Res<ReportSamples>, Res<FrameBudget>),
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
-        // Sample angle in degrees and distance uniformly
-        let angle_deg = gen_rng.rng_mut().gen_range(event.angle_min_deg..=event.angle_max_deg);
-        let angle_rad = angle_deg.to_radians();
-        let dist = gen_rng.rng_mut().gen_range(event.dist_min..=event.dist_max);
-        let offset = Vec3::new(angle_rad.cos() * dist, event.y, angle_rad.sin() * dist);
-        println!(
-            "[RandDistDir] angle_deg={:.2}, dist={:.2}, offset={:?}",
-            angle_deg, dist, offset
-        );
-
-        // Apply offset relative to the provided base transform
-        let mut euler = event.transform.clone();
-        euler.translation = (
-            euler.translation.0 + offset.x,
-            euler.translation.1 + offset.y,
-            euler.translation.2 + offset.z,
-        );
+    let _timer = ListenerTimer::start("rand_dist_dir_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
+        if defer_over_budget(&mut commands, event, &budget) { continue; }
+        // Offset relative to the provided base transform; the sampler is kept so overlaps can resample
+        let sampler = SpawnSampler::DistDir {
+            dist_min: event.dist_min,
+            dist_max: event.dist_max,
+            angle_min_deg: event.angle_min_deg,
+            angle_max_deg: event.angle_max_deg,
+            y: event.y,
+            transform: event.transform.clone(),
+        };
+        let footprint = if occupancy.enabled { structure_footprint(&event.reference) } else { Vec::new() };
+        let euler = if footprint.is_empty() {
+            sampler.sample(&mut gen_rng)
+        } else {
+            // Draw new points until one lands on free ground
+            let parent_world = world_transform(event.parent, &hierarchy);
+            let attempts = occupancy.max_retries as usize + 1;
+            // Sampled lazily, so the RNG only advances for the points actually tried
+            let candidates = (0..attempts).map(|_| {
+                let euler = sampler.sample(&mut gen_rng);
+                let placed = footprints_at(&footprint, &(parent_world * Transform::from(euler.clone())));
+                (euler, placed)
+            });
+            match occupancy.place_first(candidates) {
+                Some(euler) => euler,
+                None => {
+                    debug!(target: SPAWN, structure = event.reference.name(), attempts, "RandDistDir: no free point; skipping");
+                    continue;
+                }
+            }
+        };
+        trace!(target: SPAWN, structure = event.reference.name(), translation = ?euler.translation, "RandDistDir placement");
 
         let reference = event.reference.clone();
         let parent = event.parent;
+        let claimed = (!footprint.is_empty()).then_some(OccupancyClaimed::default());
         commands.queue(move |world: &mut World| {
             // Create a container for applying the base transform, then nest the offset child under it
             // by reusing the Nest path: the child's local euler handles the offset.
-            world.send_event(NestSpawnEvent { reference, transform: euler, parent });
+            send_tracked(world, NestSpawnEvent { reference, transform: euler, parent, sampler: Some(sampler), claimed });
         });
     }
-    if processed { activity.idle_frames = 0; }
+    complete_tickets(&mut commands, handled);
 }
 
 #[cfg(feature = "debug")]
@@ -629,29 +805,16 @@ pub fn draw_all_paths_debug(mut gizmos: Gizmos, dbg: Res<AllPathsDebug>) {
 pub fn path_to_tag_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<PathToTagSpawnEvent>,
-    tag_query: Query<(&GlobalTransform, &Tags)>,
-    nav_mesh: Option<Res<oxidized_navigation::NavMesh>>,
-    settings: Option<Res<oxidized_navigation::NavMeshSettings>>,
-    active_tasks: Option<Res<oxidized_navigation::ActiveGenerationTasks>>,
+    (tag_query, parent_query, store_query): PathLookups,
+    (nav_mesh, settings, active_tasks): NavMeshAccess,
+    (default_solver, gen_rng, agent_navmeshes, nav_links, area_costs): PathSolving,
     #[cfg(feature = "debug")] mut all_dbg: ResMut<AllPathsDebug>,
-    parent_query: Query<&GlobalTransform>,
-    mut activity: ResMut<SpawnActivity>,
-    store_query: Query<(Entity, &Tags)>,
-    mut resolved: ResMut<ResolvedPathSpawns>,
-    mut highest: ResMut<HighestPassIndex>,
-    cur_pass: Option<Res<CurrentPass>>,
+    (mut resolved, mut pending_nests, mut polylines, mut highest, cur_pass, mut path_counters, samples, errors): PathBookkeeping,
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        // If navmesh is still baking, defer deterministically
-        if let Some(tasks) = active_tasks.as_ref() {
-            if !tasks.is_empty() {
-                let ev = event.clone();
-                commands.queue(move |world: &mut World| { world.send_event(ev); });
-                continue;
-            }
-        }
-        // Only mark processed when we actually succeed in producing a path
+    let _timer = ListenerTimer::start("path_to_tag_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
         // Compute world-space base transform for this event: parent GlobalTransform * local Transform
         let local_tf = Transform::from(event.transform.clone());
         let world_tf = match event.parent.and_then(|p| parent_query.get(p).ok()) {
@@ -660,6 +823,14 @@ pub fn path_to_tag_spawn_listener(
         };
         let base = world_tf.translation;
 
+        // If navmesh is still baking, wait for it; this does not spend a retry
+        if let Some(tasks) = active_tasks.as_ref() {
+            if !tasks.is_empty() || agent_navmeshes.is_building() {
+                defer_path_request(&mut commands, event);
+                continue;
+            }
+        }
+
         // Eagerly ensure a storage holder exists if store_as was provided, even before a path resolves
         if let Some(label) = event.store_as.as_ref() {
             let existing = store_query.iter().find(|(_, t)| t.contains(label)).map(|(e, _)| e);
@@ -691,33 +862,66 @@ pub fn path_to_tag_spawn_listener(
         }
 
         let Some((end_pos, _)) = best else {
-            println!("[PathToTag] No entity found with tag '{}' yet; will retry next frame", event.tag);
-            let ev = event.clone();
-            commands.queue(move |world: &mut World| { world.send_event(ev); });
+            trace!(target: PATH, tag = %event.tag, retries = event.retries, "PathToTag: no entity with tag yet; retrying next frame");
+            if !retry_path_request(&mut commands, event, "PathToTag") {
+                let failure = PathFailure { reason: PathFailureReason::NoTaggedEntity, start: base + world_tf.rotation * event.start, end: None };
+                if fail_path_request(&mut commands, event, failure, &world_tf, &mut resolved, &mut pending_nests, &errors) {
+                    if let Some(cp) = cur_pass.as_ref() { if highest.0 < cp.0.saturating_add(1) { highest.0 = cp.0.saturating_add(1); } }
+                }
+            }
             continue;
         };
 
-        let (Some(nav_mesh), Some(settings)) = (nav_mesh.as_ref(), settings.as_ref()) else {
-            println!("[PathToTag] NavMesh/Settings not available yet; will retry next frame");
-            let ev = event.clone();
-            commands.queue(move |world: &mut World| { world.send_event(ev); });
+        // The agent profile's navmesh when the key names one, else the main navmesh
+        let navmesh = match event.agent.as_ref() {
+            Some(agent) => agent_navmeshes.get(agent),
+            None => nav_mesh.as_ref().zip(settings.as_ref()).map(|(m, s)| (m.get(), s.as_ref())),
+        };
+        let Some((tiles, settings)) = navmesh else {
+            trace!(target: PATH, retries = event.retries, agent = ?event.agent, "PathToTag: navmesh not available yet; retrying next frame");
+            if !retry_path_request(&mut commands, event, "PathToTag") {
+                let mut start_world = base + world_tf.rotation * event.start;
+                start_world.y = end_pos.y + 0.05;
+                let failure = PathFailure { reason: PathFailureReason::NavMeshUnavailable, start: start_world, end: Some(end_pos) };
+                if fail_path_request(&mut commands, event, failure, &world_tf, &mut resolved, &mut pending_nests, &errors) {
+                    if let Some(cp) = cur_pass.as_ref() { if highest.0 < cp.0.saturating_add(1) { highest.0 = cp.0.saturating_add(1); } }
+                }
+            }
             continue;
         };
 
         // Compute path using oxidized_navigation (deterministic). If the initial start is not on the
         // navmesh, probe a few nearby offsets on the horizontal plane to find a valid start polygon.
         let mut path_points: Vec<Vec3> = Vec::new();
-        if let Ok(tiles) = nav_mesh.get().read() {
+        if let Ok(tiles) = tiles.read() {
             // Base start position rotated into world, then snapped near end's Y plane
             let mut start_world = base + world_tf.rotation * event.start;
             start_world.y = end_pos.y + 0.05;
 
-            // Helper to compute a path between arbitrary points
+            // Route through the key's cost model when it has one, else through the default solver
+            let tagged: Vec<(Vec3, &Tags)> = match event.cost {
+                Some(_) => tag_query.iter().map(|(gt, tags)| (gt.translation(), tags)).collect(),
+                None => Vec::new(),
+            };
+            let query = PathQuery {
+                tiles: &tiles,
+                settings,
+                tagged: &tagged,
+                roads: &polylines.0,
+                seed: gen_rng.seed(),
+                links: &nav_links.0,
+                area_costs: area_costs.multipliers(),
+            };
+            let grid_solver;
+            let solver: &dyn PathSolver = match event.cost.as_ref() {
+                Some(cost) => { grid_solver = CostGridSolver { cost: cost.clone() }; &grid_solver }
+                None => default_solver.0.as_ref(),
+            };
+            // Walks between link ends are shared by every try of this request
+            let link_walks = RefCell::new(LinkWalks::default());
             let try_between = |s: Vec3, e: Vec3| -> Option<Vec<Vec3>> {
-                match oxidized_navigation::query::find_path(&tiles, &settings, s, e, None, None) {
-                    Ok(points) if points.len() >= 2 => Some(points),
-                    _ => None,
-                }
+                let walks = &mut link_walks.borrow_mut();
+                find_path_with_links(query.links, s, e, walks, |a, b| solver.find_path(&query, a, b), |points| solver.path_cost(&query, points))
             };
 
             // Helper that also probes around the start point if direct path fails
@@ -767,8 +971,12 @@ pub fn path_to_tag_spawn_listener(
                             Some(p) => p,
                             None => {
                                 // Could not find a valid base path yet; requeue and try again next frame
-                                let ev = event.clone();
-                                commands.queue(move |world: &mut World| { world.send_event(ev); });
+                                if !retry_path_request(&mut commands, event, "PathToTag") {
+                                    let failure = PathFailure { reason: PathFailureReason::NoPath, start: start_world, end: Some(end_pos) };
+                                    if fail_path_request(&mut commands, event, failure, &world_tf, &mut resolved, &mut pending_nests, &errors) {
+                                        if let Some(cp) = cur_pass.as_ref() { if highest.0 < cp.0.saturating_add(1) { highest.0 = cp.0.saturating_add(1); } }
+                                    }
+                                }
                                 continue;
                             }
                         }
@@ -778,16 +986,13 @@ pub fn path_to_tag_spawn_listener(
                 // 4) Prepend: start_world + manual_points + base_path (skip base_path[0] which equals nav_start)
                 path_points.clear();
                 path_points.push(start_world);
-                path_points.extend(world_manual.into_iter());
+                path_points.extend(world_manual);
                 if base_path.len() > 1 { path_points.extend_from_slice(&base_path[1..]); }
             } else {
                 // No manual checkpoints: attempt direct path, then probe start if needed
                 if let Some(points) = try_between(start_world, end_pos) {
-                    println!(
-                        "[PathToTag] Computed path ({} pts) from {:?} to {:?}",
-                        points.len(), start_world, end_pos
-                    );
-                    println!("[PathToTag] Points: {:?}", points);
+                    debug!(target: PATH, points = points.len(), start = ?start_world, end = ?end_pos, "PathToTag: computed path");
+                    trace!(target: PATH, points = ?points, "PathToTag: path points");
                     path_points = points;
                 } else {
                     // Probe around start
@@ -799,19 +1004,23 @@ pub fn path_to_tag_spawn_listener(
                             let theta = (i as f32) * std::f32::consts::TAU / (steps as f32);
                             let cand = Vec3::new(start_world.x + r * theta.cos(), start_world.y, start_world.z + r * theta.sin());
                             if let Some(points) = try_between(cand, end_pos) {
-                                println!("[PathToTag] Probed start at r={:.2}, theta={:.2} -> valid", r, theta);
+                                trace!(target: PATH, r, theta, "PathToTag: probed start is valid");
                                 found = Some(points);
                                 break 'outer;
                             }
                         }
                     }
                     if let Some(points) = found {
-                        println!("[PathToTag] Probing succeeded; path points: {:?}", points);
+                        debug!(target: PATH, points = ?points, "PathToTag: probing succeeded");
                         path_points = points;
                     } else {
                         // Could not find a valid start polygon yet; requeue silently and try again
-                        let ev = event.clone();
-                        commands.queue(move |world: &mut World| { world.send_event(ev); });
+                        if !retry_path_request(&mut commands, event, "PathToTag") {
+                            let failure = PathFailure { reason: PathFailureReason::NoPath, start: start_world, end: Some(end_pos) };
+                            if fail_path_request(&mut commands, event, failure, &world_tf, &mut resolved, &mut pending_nests, &errors) {
+                                if let Some(cp) = cur_pass.as_ref() { if highest.0 < cp.0.saturating_add(1) { highest.0 = cp.0.saturating_add(1); } }
+                            }
+                        }
                         continue;
                     }
                 }
@@ -942,8 +1151,8 @@ pub fn path_to_tag_spawn_listener(
                             }
                             let mut ok = true;
                             for w in checkpoints.windows(2) {
-                                match oxidized_navigation::query::find_path(&tiles, &settings, w[0], w[1], None, None) {
-                                    Ok(sub) if sub.len() >= 2 => {
+                                match try_between(w[0], w[1]) {
+                                    Some(sub) => {
                                         // Avoid duplicating the junction point
                                         new_path.extend_from_slice(&sub[1..]);
                                     }
@@ -952,7 +1161,7 @@ pub fn path_to_tag_spawn_listener(
                             }
 
                             if ok && new_path.len() >= 2 {
-                                println!("[PathToTag][Wobble] Applied wobble (amp={:.2}) with {} checkpoints -> {} pts", amp, checkpoints.len(), new_path.len());
+                                trace!(target: PATH, amp, checkpoints = checkpoints.len(), points = new_path.len(), "PathToTag: applied wobble");
                                 path_points = new_path;
                                 applied = true;
                                 break;
@@ -961,11 +1170,17 @@ pub fn path_to_tag_spawn_listener(
                             }
                         }
                         if !applied {
-                            println!("[PathToTag][Wobble] Failed to apply wobble after retries; using base path");
+                            debug!(target: PATH, "PathToTag: failed to apply wobble after retries; using base path");
                         }
                         }
                     }
                 }
+
+            // Optional shaping, kept on the navmesh
+            if let Some(shaping) = event.shaping.as_ref() {
+                path_points = shape_walkable_path(&path_points, shaping, &query);
+                trace!(target: PATH, points = path_points.len(), "PathToTag: shaped path");
+            }
         }
 
         if path_points.len() < 2 { continue; }
@@ -975,6 +1190,7 @@ pub fn path_to_tag_spawn_listener(
             all_dbg.paths.push(path_points.clone());
             if all_dbg.paths.len() > 256 { all_dbg.paths.remove(0); }
         }
+        polylines.0.push(path_points.clone());
 
         // Convert world-space points to the container's local space using the full inverse transform
         // (accounts for rotation and scale, not just translation)
@@ -989,11 +1205,12 @@ pub fn path_to_tag_spawn_listener(
         let tension = event.tension;
         let spread = event.spread.clone();
         let count = event.count;
+        let placement = event.placement.clone();
         let transform = event.transform.clone();
         let parent = event.parent;
         let points_len = local_points.len();
-        println!("[PathBuffer] Buffered PathSpawnEvent: points={}, parent={:?}", points_len, parent);
-        resolved.0.push(PathSpawnEvent { reference, points: local_points, tension, spread, count, transform, parent });
+        debug!(target: PATH, points = points_len, parent = ?parent, "buffered PathSpawnEvent");
+        resolved.0.push(PathSpawnEvent { reference, points: local_points, tension, spread, count, shaping: None, placement, transform, parent });
         if let Some(cp) = cur_pass.as_ref() { if highest.0 < cp.0.saturating_add(1) { highest.0 = cp.0.saturating_add(1); } }
         // Also publish the world-space polyline so materials can visualize it
         let world_points = path_points.clone();
@@ -1014,7 +1231,7 @@ pub fn path_to_tag_spawn_listener(
                     .insert(Name::new(format!("PathPolylineList: {}", label)))
                     .id();
                 if let Some(parent) = event.parent { commands.entity(e).set_parent(parent); }
-                eprintln!("[PathStore] Created holder late 'PathPolylineList: {}' under parent {:?}", label, event.parent);
+                debug!(target: PATH, store_as = %label, parent = ?event.parent, "created PathPolylineList holder late");
                 e
             };
             // Append into the list using a queued world access to mutate the component safely
@@ -1024,112 +1241,95 @@ pub fn path_to_tag_spawn_listener(
                 let mut ent = world.entity_mut(holder);
                 if let Some(mut list) = ent.get_mut::<PathPolylineList>() {
                     list.0.push(to_add);
-                    eprintln!("[PathStore] Appended polyline to 'PathPolylineList: {}' (now {} polylines)", label_clone, list.0.len());
+                    debug!(target: PATH, store_as = %label_clone, polylines = list.0.len(), "appended polyline to PathPolylineList");
                 } else {
                     ent.insert(PathPolylineList(vec![to_add]));
-                    eprintln!("[PathStore] Inserted new PathPolylineList with 1 polyline into holder 'PathPolylineList: {}'", label_clone);
+                    debug!(target: PATH, store_as = %label_clone, "inserted new PathPolylineList into holder");
                 }
             });
         }
 
-        processed = true;
+        path_counters.resolved += 1;
     }
-    if processed { activity.idle_frames = 0; }
+    complete_tickets(&mut commands, handled);
 }
 
+// Roots and scenes whose colliders are still being prepared
+type PendingColliders<'w, 's> = (
+    Query<'w, 's, Entity, With<GenerationOnlyColliderPending>>,
+    Query<'w, 's, Entity, With<SelectiveReplacementPending>>,
+    Query<'w, 's, Entity, With<PendingMeshCollider>>,
+);
+
 // A single driver that manages all GenerationState transitions
 pub fn generation_state_driver(
-    state: Res<State<GenerationState>>,
-    mut next: ResMut<NextState<GenerationState>>,
-    // For CollisionResolution phase
-    mut timer: ResMut<CollisionResolutionTimer>,
+    (state, mut next): (Res<State<GenerationState>>, ResMut<NextState<GenerationState>>),
+    // For CollisionResolution phase, with the nudged/resampled objects still waiting to be checked for
+    // remaining overlap
+    (mut timer, overlaps): (ResMut<CollisionResolutionTimer>, Res<PendingOverlaps>),
     // For Generating readiness gating
-    gen_only_pending: Query<Entity, With<GenerationOnlyColliderPending>>,
-    selective_pending: Query<Entity, With<SelectiveReplacementPending>>,
-    mut stability: ResMut<SpawningStability>,
-    generating_frames: Res<GeneratingFrameCounter>,
-    activity: Res<SpawnActivity>,
-    mut arming: ResMut<GenerationAdvanceArming>,
+    (gen_only_pending, selective_pending, mesh_collider_pending): PendingColliders,
+    #[cfg(feature = "debug")] mut held_frames: Local<u32>,
     // For NavMeshBuilding completion check
-    active_tasks: Option<Res<oxidized_navigation::ActiveGenerationTasks>>,
-    // For pass-gated pending work
-    pending_inpass: Res<PendingInPass>,
-    cur_pass: Res<CurrentPass>,
+    (active_tasks, agent_navmeshes): (Option<Res<oxidized_navigation::ActiveGenerationTasks>>, Res<AgentNavMeshes>),
+    // For outstanding and pass-gated pending work
+    (tickets, pending_inpass, cur_pass, pipeline): (Res<WorkTickets>, Res<PendingInPass>, Res<CurrentPass>, Res<GenerationPipeline>),
+    // Custom phases are slotted in between the built-in transitions
+    (phases, mut phase_queue): (Res<GenerationPhases>, ResMut<CustomPhaseQueue>),
 ) {
+    // Phases after Generating are skipped when the current pass does not need them
+    let pass = pipeline.pass(cur_pass.0);
     match *state.get() {
         GenerationState::Generating => {
-            // Observe activity resets: idle_frames resets to 0 whenever a spawn listener processed events.
-            // If we ever observe a drop, mark that we've seen actual activity in this Generating phase.
-            if activity.idle_frames < arming.last_idle_frames {
-                arming.seen_activity = true;
-            }
-            arming.last_idle_frames = activity.idle_frames;
-            // Pending work check updates stability
-            // Also gate on any InPass items for the current pass that haven't been processed yet.
+            // Generating ends exactly when every dispatched spawn event has been handled and no
+            // deferred work remains, independent of frame timing.
             let any_inpass_current = pending_inpass.0.iter().any(|ev| ev.index == cur_pass.0);
-            let any_pending = !gen_only_pending.is_empty() || !selective_pending.is_empty() || any_inpass_current;
-            if any_pending {
-                stability.no_pending_stable_frames = 0;
-                arming.spawn_done_frames = 0;
-                if generating_frames.frames_in_generating % 30 == 0 {
-                    #[cfg(feature = "debug")]
-                    {
+            // Mesh-derived colliders must exist before collisions are resolved and the navmesh is built.
+            let any_pending = !gen_only_pending.is_empty()
+                || !selective_pending.is_empty()
+                || !mesh_collider_pending.is_empty()
+                || any_inpass_current;
+            let outstanding = tickets.outstanding();
+            if any_pending || outstanding > 0 {
+                #[cfg(feature = "debug")]
+                {
+                    *held_frames = held_frames.wrapping_add(1);
+                    if held_frames.is_multiple_of(30) {
                         let gen_cnt = gen_only_pending.iter().count();
                         let sel_cnt = selective_pending.iter().count();
                         let ip_cnt = pending_inpass.0.iter().filter(|ev| ev.index == cur_pass.0).count();
-                        println!(
-                            "[GenState][Generating] pending: gen_only={} selective={} inpass_cur_pass={} (holding)",
-                            gen_cnt, sel_cnt, ip_cnt
+                        debug!(
+                            target: PHASE,
+                            tickets = outstanding,
+                            gen_only = gen_cnt,
+                            selective = sel_cnt,
+                            inpass_cur_pass = ip_cnt,
+                            "Generating: holding for pending work"
                         );
                     }
                 }
                 return;
-            } else {
-                stability.no_pending_stable_frames = stability.no_pending_stable_frames.saturating_add(1);
             }
 
-            // Readiness thresholds: wait a bit longer and require multiple stable/idle frames
-            const MIN_GENERATING_FRAMES: u16 = 15;
-            const REQUIRED_STABLE_FRAMES: u8 = 3;
-            const REQUIRED_IDLE_FRAMES: u8 = 3;
-            // Require at least one observed activity reset before progressing out of Generating.
-            let ready = arming.seen_activity
-                && generating_frames.frames_in_generating >= MIN_GENERATING_FRAMES
-                && stability.no_pending_stable_frames >= REQUIRED_STABLE_FRAMES
-                && activity.idle_frames >= REQUIRED_IDLE_FRAMES;
-
-            if ready {
-                // Debounce before advancing
-                arming.spawn_done_frames = arming.spawn_done_frames.saturating_add(1);
-                const REQUIRED_FRAMES: u8 = 8;
-                if arming.spawn_done_frames >= REQUIRED_FRAMES {
-                    info!("[GenState] Generating -> CollisionResolution");
-                    next.set(GenerationState::CollisionResolution);
-                    arming.spawn_done_frames = 0;
-                }
-            } else {
-                arming.spawn_done_frames = 0;
-                if generating_frames.frames_in_generating % 30 == 0 {
-                    println!(
-                        "[GenState][Generating] waiting: frames={} stable_frames={} idle_frames={} (need >= {}, {}, {})",
-                        generating_frames.frames_in_generating,
-                        stability.no_pending_stable_frames,
-                        activity.idle_frames,
-                        MIN_GENERATING_FRAMES,
-                        REQUIRED_STABLE_FRAMES,
-                        REQUIRED_IDLE_FRAMES
-                    );
-                }
+            // The first pass waits for its initial spawn events (structure_spawn_listener counts in
+            // untracked ones too); later passes may legitimately be empty.
+            if tickets.issued_this_phase() == 0 && cur_pass.0 == 0 {
+                return;
             }
+
+            let to = next_phase(&GenerationState::Generating, &pass);
+            debug!(target: PHASE, to = ?to, pass = %pass.name, "Generating complete");
+            next.set(route_transition(&GenerationState::Generating, to, &phases, &mut phase_queue));
         }
         GenerationState::CollisionResolution => {
-            // Minimal wait (1 frame) just to flush any leftover contact events, then proceed.
+            // Minimal wait (1 frame) to flush leftover contact events, then proceed once moved objects have settled.
             const MIN_COLLISION_FRAMES: u16 = 1;
             const MAX_COLLISION_FRAMES: u16 = 60;  // safety cap
             timer.frames = timer.frames.saturating_add(1);
-            if timer.frames >= MIN_COLLISION_FRAMES || timer.frames > MAX_COLLISION_FRAMES {
-                println!("[GenState] CollisionResolution -> NavMeshBuilding");
-                next.set(GenerationState::NavMeshBuilding);
+            if (timer.frames >= MIN_COLLISION_FRAMES && overlaps.is_settled()) || timer.frames > MAX_COLLISION_FRAMES {
+                let to = next_phase(&GenerationState::CollisionResolution, &pass);
+                debug!(target: PHASE, to = ?to, frames = timer.frames, "CollisionResolution complete");
+                next.set(route_transition(&GenerationState::CollisionResolution, to, &phases, &mut phase_queue));
                 timer.frames = 0;
             }
         }
@@ -1138,20 +1338,24 @@ pub fn generation_state_driver(
             let done = match active_tasks {
                 Some(tasks) => tasks.is_empty(),
                 None => true,
-            };
+            } && !agent_navmeshes.is_building();
             if done {
-                println!("[GenState] NavMeshBuilding -> PathResolve");
-                next.set(GenerationState::PathResolve);
+                let to = next_phase(&GenerationState::NavMeshBuilding, &pass);
+                debug!(target: PHASE, to = ?to, "NavMeshBuilding complete");
+                next.set(route_transition(&GenerationState::NavMeshBuilding, to, &phases, &mut phase_queue));
             }
         }
         GenerationState::PathResolve => {
             // No-op here; advancement handled by advance_from_path_resolve
         }
+        GenerationState::CustomPhase => {
+            // Advancement handled by run_custom_phases
+        }
         GenerationState::Completed => {}
     }
 }
 
-// Timer used to ensure at least one frame in PathResolve state
+// Frames spent in PathResolve, for its timeout
 #[derive(Resource, Default)]
 pub struct PathResolveTimer { pub frames: u16 }
 
@@ -1160,61 +1364,45 @@ pub fn reset_path_resolve_phase(mut timer: ResMut<PathResolveTimer>) {
     timer.frames = 0;
 }
 
-// After one update in PathResolve, advance to Completed
+// Leave PathResolve once every PathToTag/PathToAllTags request has resolved or failed. Requests still
+// outstanding after PATH_RESOLVE_TIMEOUT_FRAMES are given up on with PathResolutionFailed.
 pub fn advance_from_path_resolve(
+    mut commands: Commands,
     state: Res<State<GenerationState>>,
-    mut timer: ResMut<PathResolveTimer>,
+    (mut timer, tickets): (ResMut<PathResolveTimer>, Res<WorkTickets>),
     mut next: ResMut<NextState<GenerationState>>,
-    resolved: Res<ResolvedPathSpawns>,
+    (resolved, pending_nests): (Res<ResolvedPathSpawns>, Res<PendingNests>),
+    phases: Res<GenerationPhases>,
+    mut phase_queue: ResMut<CustomPhaseQueue>,
 ) {
     if state.get() != &GenerationState::PathResolve { return; }
     timer.frames = timer.frames.saturating_add(1);
-    // Give path systems multiple frames to resolve (retries, asset readiness, etc.)
-    const PATH_RESOLVE_FRAMES: u16 = 60; // ~1s at 60 FPS
-    if timer.frames >= PATH_RESOLVE_FRAMES {
-        if !resolved.0.is_empty() {
-            println!("[GenState] PathResolve -> Generating (have {} buffered PathSpawnEvent)", resolved.0.len());
-            next.set(GenerationState::Generating);
-        } else {
-            println!("[GenState] PathResolve -> Completed");
-            next.set(GenerationState::Completed);
-        }
-        timer.frames = 0;
+    const PATH_RESOLVE_TIMEOUT_FRAMES: u16 = 600; // ~10s at 60 FPS
+    let outstanding = tickets.outstanding_of::<PathToTagSpawnEvent>() + tickets.outstanding_of::<PathToAllTagsSpawnEvent>();
+    if outstanding > 0 {
+        if timer.frames < PATH_RESOLVE_TIMEOUT_FRAMES { return; }
+        warn!(target: PATH, outstanding, frames = timer.frames, "PathResolve timed out; giving up on the remaining path requests");
+        commands.queue(|world: &mut World| {
+            time_out_path_requests::<PathToTagSpawnEvent>(world);
+            time_out_path_requests::<PathToAllTagsSpawnEvent>(world);
+        });
+        // Decide next frame, once the fallbacks of the timed-out requests have been buffered
+        return;
     }
-}
-
-// On entering Generating, reset stability counters/flags
-pub fn reset_generating_phase(
-    mut stability: ResMut<SpawningStability>,
-    mut gen_counter: ResMut<GeneratingFrameCounter>,
-    mut activity: ResMut<SpawnActivity>,
-    mut arming: ResMut<GenerationAdvanceArming>,
-) {
-    stability.no_pending_stable_frames = 0;
-    gen_counter.frames_in_generating = 0;
-    activity.idle_frames = 0;
-    arming.spawn_done_frames = 0;
-    arming.seen_activity = false;
-    // Initialize high so the first reset to 0 by any spawn listener is detected as a drop
-    arming.last_idle_frames = u8::MAX;
-}
-
-
-// While in Generating, tick the frame counter
-pub fn tick_generating_counter(
-    state: Option<Res<State<GenerationState>>>,
-    mut gen_counter: ResMut<GeneratingFrameCounter>,
-) {
-    if let Some(gs) = state {
-        if gs.get() == &GenerationState::Generating {
-            gen_counter.frames_in_generating = gen_counter.frames_in_generating.saturating_add(1);
-        }
+    // Fallbacks of failed requests may have buffered spawns for another Generating pass
+    if !resolved.0.is_empty() || !pending_nests.0.is_empty() {
+        debug!(target: PHASE, buffered = resolved.0.len(), nests = pending_nests.0.len(), "PathResolve complete; generating buffered path spawns");
+        next.set(GenerationState::Generating);
+    } else {
+        debug!(target: PHASE, "PathResolve complete");
+        next.set(route_transition(&GenerationState::PathResolve, GenerationState::Completed, &phases, &mut phase_queue));
     }
+    timer.frames = 0;
 }
 
-// Increment spawn activity idle counter each frame
-pub fn tick_spawn_activity(mut activity: ResMut<SpawnActivity>) {
-    activity.idle_frames = activity.idle_frames.saturating_add(1);
+// On entering Generating, start counting the tickets issued for this phase
+pub fn reset_generating_phase(mut tickets: ResMut<WorkTickets>) {
+    tickets.begin_phase();
 }
 
 // --- UI overlay for current GenerationState ---
@@ -1272,6 +1460,8 @@ pub enum GenerationState {
     NavMeshBuilding,
     // New intermediate state where we resolve paths (now that navmesh is ready)
     PathResolve,
+    // Downstream phases registered through GenerationPhases (see run_custom_phases)
+    CustomPhase,
     Completed,
 }
 
@@ -1282,17 +1472,6 @@ pub struct CollisionResolutionTimer {
     pub quiet_frames: u16,
 }
 
-// Stability counters to avoid premature transition out of Generating
-#[derive(Resource, Default)]
-pub struct SpawningStability {
-    pub no_pending_stable_frames: u8,
-}
-
-#[derive(Resource, Default)]
-pub struct GeneratingFrameCounter {
-    pub frames_in_generating: u16,
-}
-
 // Buffer for path events authored during Generating; these will be flushed in PathResolve
 #[derive(Resource, Default)]
 pub struct PendingPathEvents {
@@ -1308,17 +1487,16 @@ pub struct PendingNests(pub Vec<NestSpawnEvent>);
 // During Generating, capture path events and store them for PathResolve. Also eagerly create holders.
 pub fn buffer_path_events(
     mut commands: Commands,
-    mut r_to_tag: EventReader<PathToTagSpawnEvent>,
-    mut r_to_all: EventReader<PathToAllTagsSpawnEvent>,
-    mut r_plain: EventReader<PathSpawnEvent>,
+    (mut r_to_tag, mut r_to_all, mut r_plain): (EventReader<PathToTagSpawnEvent>, EventReader<PathToAllTagsSpawnEvent>, EventReader<PathSpawnEvent>),
     mut pending: ResMut<PendingPathEvents>,
     store_query: Query<(Entity, &Tags)>,
-    mut activity: ResMut<SpawnActivity>,
-    mut highest: ResMut<HighestPassIndex>,
-    cur_pass: Option<Res<CurrentPass>>,
+    (mut highest, cur_pass): (ResMut<HighestPassIndex>, Option<Res<CurrentPass>>),
+    mut tickets: ResMut<WorkTickets>,
+    mut path_counters: ResMut<PathResolveCounters>,
 ) {
-    let mut any = false;
-    for ev in r_to_tag.read() {
+    for (ev, id) in r_to_tag.read_with_id() {
+        // Buffered path work is resolved in PathResolve, not in this Generating phase
+        tickets.complete(WorkTicket::of(id));
         if let Some(label) = ev.store_as.as_ref() {
             let existing = store_query.iter().find(|(_, t)| t.contains(label)).map(|(e, _)| e);
             if existing.is_none() {
@@ -1329,13 +1507,14 @@ pub fn buffer_path_events(
                     .insert(Name::new(format!("PathPolylineList: {}", label)))
                     .id();
                 if let Some(parent) = ev.parent { commands.entity(e).set_parent(parent); }
-                eprintln!("[PathStore] (Buffer) Created holder 'PathPolylineList: {}' under parent {:?}", label, ev.parent);
+                debug!(target: PATH, store_as = %label, parent = ?ev.parent, "created PathPolylineList holder while buffering");
             }
         }
         pending.to_tag.push(ev.clone());
-        any = true;
+        path_counters.queued += 1;
     }
-    for ev in r_to_all.read() {
+    for (ev, id) in r_to_all.read_with_id() {
+        tickets.complete(WorkTicket::of(id));
         if let Some(label) = ev.store_as.as_ref() {
             let existing = store_query.iter().find(|(_, t)| t.contains(label)).map(|(e, _)| e);
             if existing.is_none() {
@@ -1346,19 +1525,18 @@ pub fn buffer_path_events(
                     .insert(Name::new(format!("PathPolylineList: {}", label)))
                     .id();
                 if let Some(parent) = ev.parent { commands.entity(e).set_parent(parent); }
-                eprintln!("[PathStore] (Buffer) Created holder 'PathPolylineList: {}' under parent {:?}", label, ev.parent);
+                debug!(target: PATH, store_as = %label, parent = ?ev.parent, "created PathPolylineList holder while buffering");
             }
         }
         pending.to_all.push(ev.clone());
+        path_counters.queued += 1;
         if let Some(cp) = cur_pass.as_ref() { if highest.0 < cp.0.saturating_add(1) { highest.0 = cp.0.saturating_add(1); } }
-        any = true;
     }
-    for ev in r_plain.read() {
+    for (ev, id) in r_plain.read_with_id() {
+        tickets.complete(WorkTicket::of(id));
         pending.plain.push(ev.clone());
         if let Some(cp) = cur_pass.as_ref() { if highest.0 < cp.0.saturating_add(1) { highest.0 = cp.0.saturating_add(1); } }
-        any = true;
     }
-    if any { activity.idle_frames = 0; }
 }
 
 // On entering PathResolve, flush buffered path events back into the event system
@@ -1367,13 +1545,18 @@ pub fn flush_path_events_on_enter(
     mut w_to_tag: EventWriter<PathToTagSpawnEvent>,
     mut w_to_all: EventWriter<PathToAllTagsSpawnEvent>,
     mut w_plain: EventWriter<PathSpawnEvent>,
+    mut tickets: ResMut<WorkTickets>,
 ) {
-    if !pending.to_tag.is_empty() { eprintln!("[PathStore] Flushing {} PathToTag events to PathResolve", pending.to_tag.len()); }
-    if !pending.to_all.is_empty() { eprintln!("[PathStore] Flushing {} PathToAllTags events to PathResolve", pending.to_all.len()); }
-    if !pending.plain.is_empty() { eprintln!("[PathStore] Flushing {} PathSpawn events to PathResolve", pending.plain.len()); }
-    for ev in pending.to_tag.drain(..) { w_to_tag.send(ev); }
-    for ev in pending.to_all.drain(..) { w_to_all.send(ev); }
-    for ev in pending.plain.drain(..) { w_plain.send(ev); }
+    debug!(
+        target: PATH,
+        to_tag = pending.to_tag.len(),
+        to_all = pending.to_all.len(),
+        plain = pending.plain.len(),
+        "flushing buffered path events to PathResolve"
+    );
+    for ev in pending.to_tag.drain(..) { tickets.issue(w_to_tag.send(ev)); }
+    for ev in pending.to_all.drain(..) { tickets.issue(w_to_all.send(ev)); }
+    for ev in pending.plain.drain(..) { tickets.issue(w_plain.send(ev)); }
 }
 
 // Threshold used to decide which colliders should influence the navmesh.
@@ -1386,28 +1569,15 @@ impl Default for NavMeshPriorityThreshold {
     fn default() -> Self { NavMeshPriorityThreshold(1) }
 }
 
-// Debounce arming for transition from Generating -> CollisionResolution
-#[derive(Resource, Default)]
-pub struct GenerationAdvanceArming {
-    pub spawn_done_frames: u8,
-    pub seen_activity: bool,
-    pub last_idle_frames: u8,
-}
-
 // Marker used to delay adding NavMeshAffector until we're ready to build the navmesh
 #[derive(Component, Default)]
 pub struct QueuedNavMeshAffector;
 
-// Tracks whether spawn-related systems have been active recently
-#[derive(Resource, Default)]
-pub struct SpawnActivity {
-    pub idle_frames: u8,
-}
 use rand::prelude::IteratorRandom;
 use crate::spawning::helpers::GenRng;
 use bevy::ecs::world::World;
 use crate::spawning::euler_transform::EulerTransform;
-use crate::spawning::transformation::{get_looped_position_list, generate_noise_spawn_points};
+use crate::spawning::transformation::{get_looped_position_list, generate_noise_spawn_points, generate_noise_path_points, jitter_path_points, path_placements, trim_polyline};
 use bevy_math::cubic_splines::CubicCardinalSpline;
 use rand::Rng;
 use crate::core::spread_data::SpreadData;
@@ -1437,71 +1607,70 @@ fn collect_entity_and_descendants(entity: Entity, children_query: &Query<&Childr
     }
 }
 
-// On entering NavMeshBuilding, convert all queued affectors to real NavMeshAffectors
-pub fn activate_navmesh_affectors(
+// On entering NavMeshBuilding, write the authored NavMeshConfig into NavMeshSettings. The grid is fixed
+// once tiles exist; agent changes after that re-flag the current affectors so their tiles rebuild.
+pub fn apply_navmesh_config(
     mut commands: Commands,
-    queued: Query<(Entity, Option<&ColliderPriority>), With<QueuedNavMeshAffector>>,
-    threshold: Option<Res<NavMeshPriorityThreshold>>,
+    authoring: Res<NavMeshAuthoring>,
+    nav_mesh: Option<Res<oxidized_navigation::NavMesh>>,
+    settings: Option<ResMut<oxidized_navigation::NavMeshSettings>>,
+    affectors: Query<Entity, With<NavMeshAffector>>,
 ) {
-    let thr = threshold.map(|t| t.0).unwrap_or(1);
-    for (e, pri_opt) in queued.iter() {
-        let include = match pri_opt { Some(ColliderPriority(p)) => *p >= thr, None => true };
-        if include {
-            commands.entity(e)
-                .insert(NavMeshAffector)
-                .remove::<QueuedNavMeshAffector>();
-        } else {
-            // Simply drop the queued flag so this collider does not affect the navmesh
-            commands.entity(e).remove::<QueuedNavMeshAffector>();
-        }
+    let Some(mut settings) = settings else { return; };
+    let config = authoring.effective();
+    let built = nav_mesh.is_some_and(|m| m.get().read().is_ok_and(|tiles| !tiles.tiles.is_empty()));
+
+    let mut target = settings.clone();
+    config.apply_grid(&mut target);
+    let grid_changed = target.cell_width != settings.cell_width
+        || target.cell_height != settings.cell_height
+        || target.tile_width != settings.tile_width;
+    if grid_changed && built {
+        warn!(target: PATH, "navmesh cell/tile size changes after the first navmesh build are ignored");
+        target.cell_width = settings.cell_width;
+        target.cell_height = settings.cell_height;
+        target.tile_width = settings.tile_width;
+    }
+    config.apply_agent(&mut target);
+    let agent_changed = target.walkable_radius != settings.walkable_radius
+        || target.walkable_height != settings.walkable_height
+        || target.step_height != settings.step_height
+        || target.max_traversable_slope_radians != settings.max_traversable_slope_radians;
+    if !grid_changed && !agent_changed { return; }
+
+    debug!(
+        target: PATH,
+        cell_width = target.cell_width,
+        walkable_radius = target.walkable_radius,
+        walkable_height = target.walkable_height,
+        step_height = target.step_height,
+        "applying navmesh config"
+    );
+    *settings = target;
+    if built && agent_changed {
+        for e in affectors.iter() { commands.entity(e).insert(NavMeshAffector); }
     }
 }
 
-// (SpawningComplete resource and monitor removed; readiness is computed inline in
-//  advance_to_collision_resolution using pending queries and stability counters.)
+type QueuedAffector = (Entity, Option<&'static Collider>, Option<&'static ColliderPriority>, Option<&'static ObjectType>, Option<&'static Tags>);
 
-// When generation/deferred tasks are done, move from Generating -> CollisionResolution
-pub fn advance_to_collision_resolution(
-    state: Res<State<GenerationState>>,
-    gen_only_pending: Query<Entity, With<GenerationOnlyColliderPending>>,
-    selective_pending: Query<Entity, With<SelectiveReplacementPending>>,
-    mut stability: ResMut<SpawningStability>,
-    generating_frames: Res<GeneratingFrameCounter>,
-    activity: Res<SpawnActivity>,
-    mut arming: ResMut<GenerationAdvanceArming>,
-    mut next: ResMut<NextState<GenerationState>>,
+// On entering NavMeshBuilding, convert all queued affectors to real NavMeshAffectors
+pub fn activate_navmesh_affectors(
+    mut commands: Commands,
+    queued: Query<QueuedAffector, With<QueuedNavMeshAffector>>,
+    threshold: Option<Res<NavMeshPriorityThreshold>>,
+    authoring: Res<NavMeshAuthoring>,
 ) {
-    if state.get() != &GenerationState::Generating { return; }
-
-    // Pending work check updates stability
-    let any_pending = !gen_only_pending.is_empty() || !selective_pending.is_empty();
-    if any_pending {
-        stability.no_pending_stable_frames = 0;
-        arming.spawn_done_frames = 0;
-        return;
-    } else {
-        stability.no_pending_stable_frames = stability.no_pending_stable_frames.saturating_add(1);
-    }
-
-    // Readiness thresholds
-    const MIN_GENERATING_FRAMES: u16 = 30;
-    const REQUIRED_STABLE_FRAMES: u8 = 20;
-    const REQUIRED_IDLE_FRAMES: u8 = 5;
-    let ready = generating_frames.frames_in_generating >= MIN_GENERATING_FRAMES
-        && stability.no_pending_stable_frames >= REQUIRED_STABLE_FRAMES
-        && activity.idle_frames >= REQUIRED_IDLE_FRAMES;
-
-    if ready {
-        // Debounce before advancing
-        arming.spawn_done_frames = arming.spawn_done_frames.saturating_add(1);
-        const REQUIRED_FRAMES: u8 = 5;
-        if arming.spawn_done_frames >= REQUIRED_FRAMES {
-            info!("[GenState] Generating -> CollisionResolution");
-            next.set(GenerationState::CollisionResolution);
-            arming.spawn_done_frames = 0;
+    let config = authoring.effective();
+    let thr = config.priority_threshold.or(threshold.map(|t| t.0)).unwrap_or(1);
+    for (e, collider, pri_opt, object_type, tags) in queued.iter() {
+        let include = match pri_opt { Some(ColliderPriority(p)) => *p >= thr, None => true }
+            && config.affectors.as_ref().is_none_or(|filter| filter.matches(object_type, tags));
+        // Excluded colliders simply drop the queued flag so they do not affect the navmesh
+        commands.entity(e).remove::<QueuedNavMeshAffector>();
+        if include {
+            add_navmesh_affector(&mut commands, e, collider);
         }
-    } else {
-        arming.spawn_done_frames = 0;
     }
 }
 
@@ -1515,7 +1684,7 @@ pub fn collision_resolution_waiter(
     // increment and move on after N frames
     timer.frames = timer.frames.saturating_add(1);
     if timer.frames > 60 {
-        info!("[GenState] CollisionResolution -> NavMeshBuilding");
+        debug!(target: PHASE, "CollisionResolution complete (timer)");
         next.set(GenerationState::NavMeshBuilding);
         // reset for potential reuse
         timer.frames = 0;
@@ -1535,7 +1704,7 @@ pub fn navmesh_build_monitor(
         None => true,
     };
     if done {
-        println!("[GenState] NavMeshBuilding -> Completed");
+        debug!(target: PHASE, "NavMeshBuilding complete");
         next.set(GenerationState::Completed);
     }
 }
@@ -1548,17 +1717,20 @@ pub fn advance_pass_or_finish(
 ) {
     if cur.0 < highest.0 {
         cur.0 = cur.0.saturating_add(1);
-        println!("[Pass] Advancing to pass {}", cur.0 + 1);
+        info!(target: PHASE, pass = cur.0, "advancing to next pass");
         next.set(GenerationState::Generating);
     } else {
-        println!("[Pass] Final pass reached: {}", cur.0 + 1);
+        info!(target: PHASE, pass = cur.0, "final pass reached");
     }
 }
 
+// Roots not yet queued for GenerationOnlyCollider stripping, nor already stripped
+type NotYetQueued = (Without<GenerationOnlyColliderPending>, Without<GenerationOnlyCollidersStripped>);
+
 // Enqueue pending processing for any root tagged GenerationOnlyCollider
 pub fn enqueue_generation_only_colliders(
     mut commands: Commands,
-    tagged: Query<(Entity, &Tags), (Without<GenerationOnlyColliderPending>, Without<GenerationOnlyCollidersStripped>)>,
+    tagged: Query<(Entity, &Tags), NotYetQueued>,
 ) {
     for (root, tags) in tagged.iter() {
         if tags.contains("GenerationOnlyCollider") {
@@ -1575,8 +1747,6 @@ pub fn strip_generation_only_colliders_progressor(
     collider_query: Query<&Collider>,
 ) {
     for (root, mut pending, name_opt) in pending_q.iter_mut() {
-        #[cfg(not(feature = "debug"))]
-        let _ = &name_opt;
         let mut to_visit = Vec::new();
         collect_entity_and_descendants(root, &children_query, &mut to_visit);
         let descendant_count = to_visit.len();
@@ -1593,8 +1763,6 @@ pub fn strip_generation_only_colliders_progressor(
 
         if pending.stable_frames >= 3 {
             let mut removed_count = 0usize;
-            #[cfg(not(feature = "debug"))]
-            let _ = &removed_count;
             for e in to_visit.iter().copied() {
                 if collider_query.get(e).is_ok() {
                     commands.entity(e).remove::<Collider>();
@@ -1602,9 +1770,12 @@ pub fn strip_generation_only_colliders_progressor(
                 }
             }
 
-            info!(
-                "[GenOnlyCollider] Stripped {} Collider components under entity {:?} ({:?})",
-                removed_count, root, name_opt.map(|n| n.as_str().to_string())
+            debug!(
+                target: SPAWN,
+                entity = ?root,
+                structure = name_opt.map(|n| n.as_str()),
+                removed = removed_count,
+                "stripped generation-only colliders"
             );
 
             commands.entity(root)
@@ -1614,26 +1785,44 @@ pub fn strip_generation_only_colliders_progressor(
     }
 }
 
+// Meshes and materials, including the PathBlend ones applied directly
+type MeshAssets<'w> = (
+    ResMut<'w, Assets<Mesh>>,
+    ResMut<'w, Assets<StandardMaterial>>,
+    ResMut<'w, InlineMaterialCache>,
+    ResMut<'w, Assets<PathBlendMaterial>>,
+    ResMut<'w, GroundPathMaterial>,
+);
+
+// The occupancy grid, the parent chains objects are placed through, and the containers whose
+// footprint a scatter spawner already claimed
+type OccupancyAccess<'w, 's> = (
+    ResMut<'w, OccupancyGrid>,
+    Query<'w, 's, (&'static Transform, Option<&'static Parent>)>,
+    Query<'w, 's, (), With<OccupancyClaimed>>,
+);
+
 pub fn mesh_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<MeshSpawnEvent>,
-    material_cache: Res<MaterialCache>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    // For direct PathBlend application
-    mut std_mats: ResMut<Assets<StandardMaterial>>,
-    mut ext_mats: ResMut<Assets<PathBlendMaterial>>,
-    mut ground_res: ResMut<GroundPathMaterial>,
-    asset_server: Res<AssetServer>,
-    mut activity: ResMut<SpawnActivity>,
+    (material_cache, asset_server): (Res<MaterialCache>, Res<AssetServer>),
+    (mut meshes, mut std_mats, mut inline_cache, mut ext_mats, mut ground_res): MeshAssets,
     tag_query: Query<&Tags>,
+    (mut occupancy, hierarchy, claimed): OccupancyAccess,
+    (samples, errors, budget): (Res<ReportSamples>, Res<PendingErrors>, Res<FrameBudget>),
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
+    let _timer = ListenerTimer::start("mesh_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
+        if defer_over_budget(&mut commands, event, &budget) { continue; }
         let (material_name, adjusted_mesh) = match &event.material {
             TMaterial::BasicMaterial { material_name } => {
                 (material_name.clone(), event.mesh.clone()) // No tiling factor adjustment needed
             }
+            TMaterial::Inline { .. } => {
+                ("Inline".to_string(), event.mesh.clone())
+            }
             TMaterial::TiledMaterial { material_name, tiling_factor } => {
                 let mut mesh = event.mesh.clone();
                 if let Some(bevy::render::mesh::VertexAttributeValues::Float32x2(uvs)) =
@@ -1662,11 +1851,25 @@ pub fn mesh_spawn_listener(
             }
         };
 
-        let bounding_box = adjusted_mesh.compute_aabb().unwrap();
-        let half_extents = bounding_box.half_extents;
-        let collider_size = Vec3::new(half_extents.x, half_extents.y, half_extents.z);
+        // Authored colliders come with the event; otherwise fit a cuboid to the mesh AABB
+        let collider = match &event.collider_type {
+            Some(collider_type) if collider_type.requires_mesh() => {
+                create_collider_from_geometry(collider_type, Some(&ColliderGeometry::from_mesh(&adjusted_mesh)), &errors)
+            }
+            Some(collider_type) => create_collider(collider_type, &errors),
+            None => {
+                let half_extents = adjusted_mesh.compute_aabb().unwrap().half_extents;
+                Some(Collider::cuboid(half_extents.x, half_extents.y, half_extents.z))
+            }
+        };
 
-        if let Some(material_handle) = material_cache.get(&material_name) {
+        // Inline materials are built from the event itself; everything else comes from the cache
+        let material_handle = match &event.material {
+            TMaterial::Inline { .. } => event.material.resolve_standard(&material_cache, &mut std_mats, &mut inline_cache, &asset_server),
+            _ => material_cache.get(&material_name).cloned(),
+        };
+
+        if let Some(material_handle) = material_handle {
             let mesh_handle = meshes.add(adjusted_mesh);
 
             // First, spawn the entity and get its ID
@@ -1677,17 +1880,19 @@ pub fn mesh_spawn_listener(
             match &event.material {
                 TMaterial::PathBlend { near_albedo_path, near_metallic_roughness_path, near_ao_path, .. } => {
                     // Build PathBlend material from the cached base StandardMaterial
-                    let base = std_mats.get(material_handle).cloned().unwrap_or_else(StandardMaterial::default);
+                    let base = std_mats.get(&material_handle).cloned().unwrap_or_else(StandardMaterial::default);
                     // Tuned defaults for high-visibility path blending
-                    let mut p = PathBlendParams::default();
-                    p.fade_radius = 0.2;
-                    p.min_blend = 0.0;
-                    p.max_blend = 1.0;
-                    p.near_base_color = Vec4::new(0.32, 0.24, 0.16, 1.0);
-                    p.near_metallic = 0.0;
-                    p.near_roughness = 0.95;
-                    p.thickness_scale = 1.0;
-                    p.base_width = 0.5;
+                    let mut p = PathBlendParams {
+                        fade_radius: 0.2,
+                        min_blend: 0.0,
+                        max_blend: 1.0,
+                        near_base_color: Vec4::new(0.32, 0.24, 0.16, 1.0),
+                        near_metallic: 0.0,
+                        near_roughness: 0.95,
+                        thickness_scale: 1.0,
+                        base_width: 0.5,
+                        ..default()
+                    };
                     p.set_falloff_mode(crate::materials::path_blend::falloff_mode::LINEAR);
                     p.set_invert(false);
                     // Initially no segments; they will be supplied via PathWorldPointsEvent
@@ -1702,10 +1907,6 @@ pub fn mesh_spawn_listener(
                         .insert(MeshMaterial3d(handle.clone()))
                         .insert(Transform::from(event.transform.clone()))
                         .insert(Name::new("Mesh"))
-                        .insert(Collider::cuboid(collider_size.x, collider_size.y, collider_size.z))
-                        .insert(ActiveEvents::COLLISION_EVENTS | ActiveEvents::CONTACT_FORCE_EVENTS)
-                        .insert(ActiveCollisionTypes::all())
-                        .insert(QueuedNavMeshAffector)
                         .insert(InheritedVisibility::default());
 
                     // Set the ground path material handle if not already set
@@ -1715,17 +1916,34 @@ pub fn mesh_spawn_listener(
                 _ => {
                     commands.entity(entity_id)
                         .insert(Mesh3d(mesh_handle))
-                        .insert(MeshMaterial3d((*material_handle).clone()))
+                        .insert(MeshMaterial3d(material_handle.clone()))
                         .insert(Transform::from(event.transform.clone()))
                         .insert(Name::new("Mesh"))
-                        .insert(Collider::cuboid(collider_size.x, collider_size.y, collider_size.z))
-                        .insert(ActiveEvents::COLLISION_EVENTS | ActiveEvents::CONTACT_FORCE_EVENTS)
-                        .insert(ActiveCollisionTypes::all())
-                        .insert(QueuedNavMeshAffector)
                         .insert(InheritedVisibility::default());
                 }
             }
 
+            if let Some(collider) = collider {
+                commands.entity(entity_id)
+                    .insert(collider)
+                    .insert(ActiveEvents::COLLISION_EVENTS | ActiveEvents::CONTACT_FORCE_EVENTS)
+                    .insert(ActiveCollisionTypes::all())
+                    .insert(QueuedNavMeshAffector);
+                if let Some(priority) = event.priority {
+                    commands.entity(entity_id)
+                        .insert(ColliderPriority(priority))
+                        .insert(OverlapStrategy(event.overlap.clone()));
+                    // Meshes of a structure a scatter spawner placed are already in the grid
+                    let unclaimed = !event.parent.is_some_and(|p| claimed.contains(p));
+                    let footprint = event.collider_type.as_ref()
+                        .and_then(|collider_type| LocalFootprint::from_collider_type(collider_type, priority, &Transform::IDENTITY));
+                    if let (true, true, Some(fp)) = (occupancy.enabled, unclaimed, footprint) {
+                        let world = world_transform(event.parent, &hierarchy) * Transform::from(event.transform.clone());
+                        occupancy.insert(&[Footprint::from_local(&fp, &world)]);
+                    }
+                }
+            }
+
             // Set parent if applicable, and propagate Tags from the parent so matching works
             if let Some(parent) = event.parent {
                 commands.entity(entity_id).set_parent(parent);
@@ -1734,21 +1952,26 @@ pub fn mesh_spawn_listener(
                 }
             }
         } else {
-            println!("Material not found: {}", material_name);
+            GenerationError::new(GenerationErrorKind::MissingMaterial, format!("Material not found: {}", material_name))
+                .with_parent(event.parent)
+                .report(&errors);
         }
     }
-    if processed { activity.idle_frames = 0; }
+    complete_tickets(&mut commands, handled);
 }
 
 pub fn scene_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<SceneSpawnEvent>,
     asset_server: Res<AssetServer>,
-    mut activity: ResMut<SpawnActivity>,
+    (mut occupancy, hierarchy, claimed): OccupancyAccess,
+    (samples, errors, budget): (Res<ReportSamples>, Res<PendingErrors>, Res<FrameBudget>),
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
+    let _timer = ListenerTimer::start("scene_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
+        if defer_over_budget(&mut commands, event, &budget) { continue; }
         let global_transform = Transform::from(event.transform.clone());
 
         let parent_entity = commands.spawn_empty()
@@ -1756,14 +1979,19 @@ pub fn scene_spawn_listener(
             .insert(InheritedVisibility::default())
             .id();
 
-        if let StructureKey::Object { path, collider, offset, ownership, selectable, object_type, visibility } = &event.data {
+        if let StructureKey::Object { path, collider, offset, ownership, selectable, object_type, visibility, material_overrides } = &event.data {
             let scene_handle: Handle<Scene> = asset_server.load(path);
 
             commands.entity(parent_entity).with_children(|parent| {
-                parent.spawn_empty()
+                let mut scene_root = parent.spawn_empty();
+                scene_root
                     .insert(InheritedVisibility::default())
                     .insert(SceneRoot(scene_handle))
                     .insert(Transform::from_translation(*offset));
+                // Overrides are applied by apply_scene_materials once the glTF hierarchy exists
+                if !material_overrides.is_empty() {
+                    scene_root.insert(PendingMaterialOverrides(material_overrides.clone()));
+                }
             });
 
             let filename = Path::new(path)
@@ -1780,18 +2008,15 @@ pub fn scene_spawn_listener(
             match mode {
                 crate::core::structure_key::VisibilityMode::Visible => {
                     commands.entity(parent_entity).insert(Visibility::Visible);
-                    #[cfg(feature = "debug")]
-                    println!("[Spawn][Visibility] {:?} -> Visible", parent_entity);
+                    trace!(target: SPAWN, entity = ?parent_entity, "visibility: Visible");
                 }
                 crate::core::structure_key::VisibilityMode::Hidden => {
                     commands.entity(parent_entity).insert(Visibility::Hidden);
-                    #[cfg(feature = "debug")]
-                    println!("[Spawn][Visibility] {:?} -> Hidden", parent_entity);
+                    trace!(target: SPAWN, entity = ?parent_entity, "visibility: Hidden");
                 }
                 crate::core::structure_key::VisibilityMode::Inherit => {
                     // Do not insert Visibility; allow inheritance from ancestors
-                    #[cfg(feature = "debug")]
-                    println!("[Spawn][Visibility] {:?} -> Inherit (no explicit Visibility)", parent_entity);
+                    trace!(target: SPAWN, entity = ?parent_entity, "visibility: Inherit (no explicit Visibility)");
                 }
             }
 
@@ -1799,12 +2024,29 @@ pub fn scene_spawn_listener(
                 commands.entity(parent_entity).insert(Selectable { is_selected: false });
             }
 
+            // Authored objects occupy the grid too, so scatter spawners avoid them. Objects of a
+            // structure a scatter spawner placed are already in it.
+            if occupancy.enabled && !event.parent.is_some_and(|p| claimed.contains(p)) {
+                if let Some(fp) = collider.as_ref().and_then(|info| LocalFootprint::from_collider(info, &Transform::IDENTITY)) {
+                    let world = world_transform(event.parent, &hierarchy) * global_transform;
+                    occupancy.insert(&[Footprint::from_local(&fp, &world)]);
+                }
+            }
+
             if let Some(internal_collider) = collider.clone() {
-                if let Some(collider) = create_collider(&internal_collider.collider_type) {
+                // Mesh-derived shapes are built by build_scene_mesh_colliders once the glTF hierarchy exists
+                let deferred = internal_collider.collider_type.requires_mesh();
+                let collider = if deferred { None } else { create_collider(&internal_collider.collider_type, &errors) };
+                if deferred || collider.is_some() {
                     let mut entity_commands = commands.entity(parent_entity);
-                    entity_commands.insert(collider)
+                    match collider {
+                        Some(collider) => { entity_commands.insert(collider); }
+                        None => { entity_commands.insert(PendingMeshCollider(internal_collider.collider_type.clone())); }
+                    }
+                    entity_commands
                         .insert(Dominance::group(internal_collider.priority))
                         .insert(ColliderPriority(internal_collider.priority))
+                        .insert(OverlapStrategy(internal_collider.overlap.clone()))
                         .insert(Damping { linear_damping: 10.0, angular_damping: 0.0 })
                         .insert(LockedAxes::ROTATION_LOCKED | LockedAxes::TRANSLATION_LOCKED_Y)
                         .insert(ActiveEvents::COLLISION_EVENTS | ActiveEvents::CONTACT_FORCE_EVENTS)
@@ -1821,6 +2063,7 @@ pub fn scene_spawn_listener(
                             let start_goal = global_transform.translation;
                             entity_commands.insert(Pathfinder {
                                 path: PathState::Ready(start_goal),
+                                ..default()
                             });
                             // Units should not affect the navmesh
                         }
@@ -1841,58 +2084,250 @@ pub fn scene_spawn_listener(
             commands.entity(parent_entity).set_parent(parent);
         }
     }
-    if processed { activity.idle_frames = 0; }
+    complete_tickets(&mut commands, handled);
+}
+
+// Mesh-derived collider waiting for the object's glTF scene to be instanced
+#[derive(Component, Clone, Debug)]
+pub struct PendingMeshCollider(pub ColliderType);
+
+// Transform of `entity` expressed in the local space of `ancestor`, composed from local Transforms
+// (GlobalTransform has not been propagated yet when a scene has just been instanced)
+fn transform_relative_to(
+    entity: Entity,
+    ancestor: Entity,
+    parent_query: &Query<&Parent>,
+    transform_query: &Query<&Transform>,
+) -> Transform {
+    let mut result = Transform::IDENTITY;
+    let mut current = entity;
+    while current != ancestor {
+        if let Ok(transform) = transform_query.get(current) {
+            result = *transform * result;
+        }
+        match parent_query.get(current) {
+            Ok(parent) => current = parent.get(),
+            Err(_) => break,
+        }
+    }
+    result
+}
+
+// Once an object's scene is ready, gather every submesh into the object container's space and build
+// the pending mesh-derived collider on the container (where scene_spawn_listener put the other physics components).
+pub fn build_scene_mesh_colliders(
+    trigger: Trigger<SceneInstanceReady>,
+    mut commands: Commands,
+    pending_query: Query<&PendingMeshCollider>,
+    (parent_query, children_query, transform_query): (Query<&Parent>, Query<&Children>, Query<&Transform>),
+    mesh_query: Query<&Mesh3d>,
+    (meshes, errors): (Res<Assets<Mesh>>, Res<PendingErrors>),
+) {
+    let scene_root = trigger.entity();
+    let Ok(container) = parent_query.get(scene_root).map(|p| p.get()) else { return; };
+    let Ok(PendingMeshCollider(collider_type)) = pending_query.get(container) else { return; };
+
+    let mut descendants = Vec::new();
+    collect_entity_and_descendants(scene_root, &children_query, &mut descendants);
+    let mut geometry = ColliderGeometry::default();
+    for e in descendants {
+        let Ok(mesh_handle) = mesh_query.get(e) else { continue; };
+        let Some(mesh) = meshes.get(&mesh_handle.0) else { continue; };
+        let transform = transform_relative_to(e, container, &parent_query, &transform_query);
+        geometry.append_mesh(mesh, &transform);
+    }
+
+    match create_collider_from_geometry(collider_type, Some(&geometry), &errors) {
+        Some(collider) => {
+            commands.entity(container).insert(collider);
+        }
+        None => GenerationError::new(
+            GenerationErrorKind::ColliderBuild,
+            format!("Could not build {:?} collider ({} triangles)", collider_type, geometry.indices.len()),
+        )
+        .with_parent(Some(container))
+        .report(&errors),
+    }
+    commands.entity(container).remove::<PendingMeshCollider>();
+}
+
+// A scene that fails to load is never instanced, so its pending mesh collider would hold Generating
+// forever. Report it and drop the marker instead.
+pub fn fail_unloaded_mesh_colliders(
+    mut commands: Commands,
+    pending_query: Query<(Entity, &PendingMeshCollider, &Children)>,
+    scene_query: Query<&SceneRoot>,
+    (asset_server, errors): (Res<AssetServer>, Res<PendingErrors>),
+) {
+    for (container, PendingMeshCollider(collider_type), children) in pending_query.iter() {
+        let Some(scene) = children.iter().find_map(|&child| scene_query.get(child).ok()) else { continue; };
+        let Some(RecursiveDependencyLoadState::Failed(error)) = asset_server.get_recursive_dependency_load_state(&scene.0) else { continue; };
+        GenerationError::new(
+            GenerationErrorKind::ColliderBuild,
+            format!("Could not build {:?} collider: scene failed to load ({})", collider_type, error),
+        )
+        .with_parent(Some(container))
+        .report(&errors);
+        commands.entity(container).remove::<PendingMeshCollider>();
+    }
+}
+
+// Material overrides waiting for a SceneRoot to finish instancing its glTF hierarchy
+#[derive(Component, Clone, Debug)]
+pub struct PendingMaterialOverrides(pub Vec<MaterialOverride>);
+
+// Team-tinted copies of source materials, keyed by (source material, team id), so every
+// `*_team` submesh of a team shares one material per source instead of cloning per instance.
+#[derive(Resource, Default)]
+pub struct TeamMaterialCache(pub HashMap<(AssetId<StandardMaterial>, u8), Handle<StandardMaterial>>);
+
+// A submesh matches when its own Name or its parent node's Name matches the glob pattern
+fn submesh_matches(pattern: &glob::Pattern, name: Option<&Name>, parent_name: Option<&Name>) -> bool {
+    name.map(|n| pattern.matches(n.as_str())).unwrap_or(false)
+        || parent_name.map(|n| pattern.matches(n.as_str())).unwrap_or(false)
+}
+
+// Once a scene is ready, rewrite materials on its glTF submeshes:
+//  1) authored PendingMaterialOverrides replace the material on matching submeshes;
+//  2) if the owning object belongs to a team, submeshes matching TeamPalette::mesh_pattern
+//     get a copy of their (possibly overridden) material recoloured to the team colour.
+pub fn apply_scene_materials(
+    trigger: Trigger<SceneInstanceReady>,
+    mut commands: Commands,
+    (pending_query, ownership_query, material_query): (Query<&PendingMaterialOverrides>, Query<&Ownership>, Query<&MeshMaterial3d<StandardMaterial>>),
+    (children_query, parent_query, name_query): (Query<&Children>, Query<&Parent>, Query<&Name>),
+    (material_cache, asset_server): (Res<MaterialCache>, Res<AssetServer>),
+    (mut std_mats, mut inline_cache): (ResMut<Assets<StandardMaterial>>, ResMut<InlineMaterialCache>),
+    (palette, mut team_cache, errors): (Option<Res<TeamPalette>>, ResMut<TeamMaterialCache>, Res<PendingErrors>),
+) {
+    let scene_root = trigger.entity();
+    let pending = pending_query.get(scene_root).ok();
+
+    // Ownership lives on the object container that parents the SceneRoot
+    let team = parent_query
+        .get(scene_root)
+        .ok()
+        .and_then(|p| ownership_query.get(p.get()).ok())
+        .and_then(|o| match o { Ownership::Team(id) => Some(*id), Ownership::Inherit => None });
+    let team_tint = match (team, palette.as_ref()) {
+        (Some(id), Some(palette)) => match glob::Pattern::new(&palette.mesh_pattern) {
+            Ok(pattern) => palette.color(id).map(|color| (id, color, pattern)),
+            Err(_) => {
+                GenerationError::new(GenerationErrorKind::InvalidPattern, format!("Invalid team colour mesh pattern '{}'", palette.mesh_pattern)).report(&errors);
+                None
+            }
+        },
+        _ => None,
+    };
+
+    if pending.is_none() && team_tint.is_none() { return; }
+
+    // Resolve each override once so matching submeshes share a single material handle
+    let mut resolved: Vec<(glob::Pattern, Handle<StandardMaterial>)> = Vec::new();
+    for ov in pending.map(|p| p.0.as_slice()).unwrap_or(&[]) {
+        let Ok(pattern) = glob::Pattern::new(&ov.mesh) else {
+            GenerationError::new(GenerationErrorKind::InvalidPattern, format!("Invalid material override mesh pattern '{}'", ov.mesh)).report(&errors);
+            continue;
+        };
+        match ov.material.resolve_standard(&material_cache, &mut std_mats, &mut inline_cache, &asset_server) {
+            Some(handle) => resolved.push((pattern, handle)),
+            None => GenerationError::new(GenerationErrorKind::MissingMaterial, format!("Material override not found for mesh pattern '{}'", ov.mesh)).report(&errors),
+        }
+    }
+
+    let mut descendants = Vec::new();
+    collect_entity_and_descendants(scene_root, &children_query, &mut descendants);
+    for e in descendants {
+        let Ok(current) = material_query.get(e) else { continue; };
+        let name = name_query.get(e).ok();
+        let parent_name = parent_query.get(e).ok().and_then(|p| name_query.get(p.get()).ok());
+
+        // Later overrides win when several patterns match the same submesh
+        let mut handle = resolved
+            .iter()
+            .rev()
+            .find(|(pattern, _)| submesh_matches(pattern, name, parent_name))
+            .map(|(_, h)| h.clone())
+            .unwrap_or_else(|| current.0.clone());
+
+        if let Some((team_id, color, pattern)) = team_tint.as_ref() {
+            if submesh_matches(pattern, name, parent_name) {
+                let key = (handle.id(), *team_id);
+                handle = match team_cache.0.get(&key) {
+                    Some(tinted) => tinted.clone(),
+                    None => {
+                        let mut tinted = std_mats.get(&handle).cloned().unwrap_or_default();
+                        tinted.base_color = *color;
+                        let tinted = std_mats.add(tinted);
+                        team_cache.0.insert(key, tinted.clone());
+                        tinted
+                    }
+                };
+            }
+        }
+
+        if handle != current.0 {
+            commands.entity(e).insert(MeshMaterial3d(handle));
+        }
+    }
+
+    if pending.is_some() {
+        commands.entity(scene_root).remove::<PendingMaterialOverrides>();
+    }
 }
 
 pub fn point_light_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<PointLightSpawnEvent>,
-    mut activity: ResMut<SpawnActivity>,
+    samples: Res<ReportSamples>,
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
+    let _timer = ListenerTimer::start("point_light_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
         let entity = spawn_point_light(
             &mut commands,
-            event.light.clone(),
+            event.light,
             Transform::from(event.transform.clone()),
         );
         if let Some(parent) = event.parent {
             commands.entity(entity).set_parent(parent);
         }
     }
-    if processed { activity.idle_frames = 0; }
+    complete_tickets(&mut commands, handled);
 }
 
 pub fn spot_light_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<SpotLightSpawnEvent>,
-    mut activity: ResMut<SpawnActivity>,
+    samples: Res<ReportSamples>,
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
+    let _timer = ListenerTimer::start("spot_light_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
         let entity = spawn_spot_light(
             &mut commands,
-            event.light.clone(),
+            event.light,
             Transform::from(event.transform.clone()),
         );
         if let Some(parent) = event.parent {
             commands.entity(entity).set_parent(parent);
         }
     }
-    if processed { activity.idle_frames = 0; }
+    complete_tickets(&mut commands, handled);
 }
 
 // Generic directional light spawner: always a regular light, parented under the provided container (if any)
 pub fn directional_light_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<DirectionalLightSpawnEvent>,
-    mut activity: ResMut<SpawnActivity>,
+    samples: Res<ReportSamples>,
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
+    let _timer = ListenerTimer::start("directional_light_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
         let entity = commands
             .spawn_empty()
             .insert(event.light.clone())
@@ -1904,7 +2339,7 @@ pub fn directional_light_spawn_listener(
             commands.entity(entity).set_parent(parent);
         }
     }
-    if processed { activity.idle_frames = 0; }
+    complete_tickets(&mut commands, handled);
 }
 
 // Dedicated spawner for the single main directional light (sun)
@@ -1912,11 +2347,12 @@ pub fn main_directional_light_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<MainDirectionalLightSpawnEvent>,
     existing: Query<Entity, With<MainDirectionalLight>>,
-    mut activity: ResMut<SpawnActivity>,
+    samples: Res<ReportSamples>,
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
+    let _timer = ListenerTimer::start("main_directional_light_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
         let target = existing.iter().next().unwrap_or_else(|| commands.spawn_empty().id());
         let mut ecmd = commands.entity(target);
         ecmd
@@ -1934,80 +2370,151 @@ pub fn main_directional_light_spawn_listener(
             commands.entity(target).set_parent(parent);
         }
     }
-    if processed { activity.idle_frames = 0; }
+    complete_tickets(&mut commands, handled);
 }
 
 pub fn ambient_light_spawn_listener(
     mut reader: EventReader<AmbientLightSpawnEvent>,
     mut ambient: ResMut<AmbientLight>,
-    mut activity: ResMut<SpawnActivity>,
+    mut tickets: ResMut<WorkTickets>,
+    samples: Res<ReportSamples>,
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
+    let _timer = ListenerTimer::start("ambient_light_spawn_listener", &samples);
+    for (event, id) in reader.read_with_id() {
+        tickets.complete(WorkTicket::of(id));
         *ambient = event.light.clone();
     }
-    if processed { activity.idle_frames = 0; }
 }
 
 pub fn distance_fog_spawn_listener(
     mut reader: EventReader<DistanceFogSpawnEvent>,
     mut query: Query<&mut DistanceFog, With<MainCamera>>,
-    mut activity: ResMut<SpawnActivity>,
+    mut tickets: ResMut<WorkTickets>,
+    samples: Res<ReportSamples>,
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
+    let _timer = ListenerTimer::start("distance_fog_spawn_listener", &samples);
+    for (event, id) in reader.read_with_id() {
+        tickets.complete(WorkTicket::of(id));
         for mut fog in &mut query {
             *fog = event.fog.clone();
         }
     }
-    if processed { activity.idle_frames = 0; }
+}
+
+// Navmesh overrides collect for the whole run and apply when the navmesh is next built
+pub fn navmesh_settings_spawn_listener(
+    mut reader: EventReader<NavMeshSettingsSpawnEvent>,
+    mut authoring: ResMut<NavMeshAuthoring>,
+    mut tickets: ResMut<WorkTickets>,
+    samples: Res<ReportSamples>,
+) {
+    let _timer = ListenerTimer::start("navmesh_settings_spawn_listener", &samples);
+    for (event, id) in reader.read_with_id() {
+        tickets.complete(WorkTicket::of(id));
+        authoring.overrides.push(event.config.clone());
+    }
+}
+
+pub fn nav_link_spawn_listener(
+    mut commands: Commands,
+    mut reader: EventReader<NavLinkSpawnEvent>,
+    samples: Res<ReportSamples>,
+) {
+    let _timer = ListenerTimer::start("nav_link_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
+        let entity = commands.spawn((
+            Transform::from(event.transform.clone()),
+            NavLinkMarker { from: event.from, to: event.to, bidirectional: event.bidirectional, cost: event.cost },
+            Name::new("NavLink"),
+        )).id();
+        if let Some(parent) = event.parent {
+            commands.entity(entity).set_parent(parent);
+        }
+    }
+    complete_tickets(&mut commands, handled);
+}
+
+// Area volumes are sensors so they shape the navmesh without blocking physics
+pub fn nav_area_spawn_listener(
+    mut commands: Commands,
+    mut reader: EventReader<NavAreaSpawnEvent>,
+    (samples, errors): (Res<ReportSamples>, Res<PendingErrors>),
+) {
+    let _timer = ListenerTimer::start("nav_area_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
+        let collider_type = event.shape.auto_collider_type();
+        let geometry = collider_type.requires_mesh().then(|| ColliderGeometry::from_mesh(&event.shape.to_mesh()));
+        let Some(collider) = create_collider_from_geometry(&collider_type, geometry.as_ref(), &errors) else {
+            warn!(target: PATH, shape = event.shape.variant_name(), "NavArea shape has no collider; skipping");
+            continue;
+        };
+        let entity = commands.spawn((
+            Transform::from(event.transform.clone()),
+            collider,
+            Sensor,
+            NavAreaVolume { area_type: event.area_type, cost: event.cost },
+            Name::new(format!("NavArea {}", event.shape.variant_name())),
+        )).id();
+        if let Some(parent) = event.parent {
+            commands.entity(entity).set_parent(parent);
+        }
+    }
+    complete_tickets(&mut commands, handled);
 }
 
 pub fn sound_effect_spawn_listener(
     mut reader: EventReader<SoundEffectSpawnEvent>,
     sfx: Res<AudioChannel<SoundEffects>>,
     asset_server: Res<AssetServer>,
-    mut activity: ResMut<SpawnActivity>,
+    mut tickets: ResMut<WorkTickets>,
+    samples: Res<ReportSamples>,
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
+    let _timer = ListenerTimer::start("sound_effect_spawn_listener", &samples);
+    for (event, id) in reader.read_with_id() {
+        tickets.complete(WorkTicket::of(id));
         let handle: Handle<AudioSource> = asset_server.load(event.file.as_str());
         // Play as a one-shot on the SFX channel
         sfx.play(handle);
     }
-    if processed { activity.idle_frames = 0; }
 }
 
 pub fn background_music_spawn_listener(
     mut reader: EventReader<BackgroundMusicSpawnEvent>,
     audio: Res<Audio>,
     asset_server: Res<AssetServer>,
-    mut activity: ResMut<SpawnActivity>,
+    mut tickets: ResMut<WorkTickets>,
+    samples: Res<ReportSamples>,
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
+    let _timer = ListenerTimer::start("background_music_spawn_listener", &samples);
+    for (event, id) in reader.read_with_id() {
+        tickets.complete(WorkTicket::of(id));
         let handle: Handle<AudioSource> = asset_server.load(event.file.as_str());
         // Stop any currently playing global track and start looping the new one
         audio.stop();
         audio.play(handle).looped();
     }
-    if processed { activity.idle_frames = 0; }
 }
 
 pub fn nest_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<NestSpawnEvent>,
-    mut activity: ResMut<SpawnActivity>,
+    mut report: ResMut<GenerationReport>,
+    hierarchy: Query<(Option<&ExpansionDepth>, Option<&Parent>)>,
+    (samples, errors, budget): (Res<ReportSamples>, Res<PendingErrors>, Res<FrameBudget>),
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
+    let _timer = ListenerTimer::start("nest_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
+        if defer_over_budget(&mut commands, event, &budget) { continue; }
         match Structure::try_from(&event.reference) {
             Ok(structure) => {
+                let depth = expansion_depth(event.parent, &hierarchy);
+                report.record_structure(&structure.structure_name, depth);
                 // Create a container entity for the nested structure
                 let container = commands
                     .spawn_empty()
@@ -2015,21 +2522,25 @@ pub fn nest_spawn_listener(
                     .insert(GlobalTransform::default())
                     .insert(InheritedVisibility::default())
                     .insert(Name::new(structure.structure_name.clone()))
+                    .insert(ExpansionDepth(depth))
                     .id();
+                let _span = debug_span!(target: SPAWN, "structure", structure = %structure.structure_name, entity = ?container, depth).entered();
 
                 if let Some(parent) = event.parent {
                     commands.entity(container).set_parent(parent);
                 }
+                if let Some(sampler) = event.sampler.clone() {
+                    commands.entity(container).insert(sampler);
+                }
+                if let Some(claimed) = event.claimed {
+                    commands.entity(container).insert(claimed);
+                }
 
                 // Attach Tags from the structure to the container (if any)
                 let tags = Tags(structure.tags.clone());
-                println!(
-                    "[Spawn] Nest: structure '{}' -> container {:?} | tags = {:?}",
-                    structure.structure_name, container, tags.0
-                );
-                if tags.len() != 0 {
+                debug!(target: SPAWN, tags = ?tags.0, "Nest spawned");
+                if !tags.is_empty() {
                     commands.entity(container).insert(tags);
-                    println!("[Spawn] Nest: tags inserted on {:?}", container);
                 }
 
                 let _ = spawn_structure_data(
@@ -2040,22 +2551,27 @@ pub fn nest_spawn_listener(
                 );
             }
             Err(e) => {
-                eprintln!("NestSpawnEvent import error: {:?}", e);
+                GenerationError::structure_error(&e)
+                    .with_structure(event.reference.name())
+                    .with_parent(event.parent)
+                    .report(&errors);
             }
         }
     }
-        if processed { activity.idle_frames = 0; }
+    complete_tickets(&mut commands, handled);
 }
 
 pub fn choose_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<ChooseSpawnEvent>,
     mut gen_rng: ResMut<GenRng>,
-    mut activity: ResMut<SpawnActivity>,
+    (samples, errors, budget): (Res<ReportSamples>, Res<PendingErrors>, Res<FrameBudget>),
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
+    let _timer = ListenerTimer::start("choose_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
+        if defer_over_budget(&mut commands, event, &budget) { continue; }
         match Structure::try_from(&event.list) {
             Ok(structure_list) => {
                 // Pick one
@@ -2069,11 +2585,14 @@ pub fn choose_spawn_listener(
                 );
             }
             Err(e) => {
-                eprintln!("ChooseSpawnEvent import error: {:?}", e);
+                GenerationError::structure_error(&e)
+                    .with_structure(event.list.name())
+                    .with_parent(event.parent)
+                    .report(&errors);
             }
         }
     }
-    if processed { activity.idle_frames = 0; }
+    complete_tickets(&mut commands, handled);
 }
 
 // Temporary no-op stubs to satisfy system registrations
@@ -2081,11 +2600,13 @@ pub fn choose_some_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<ChooseSomeSpawnEvent>,
     mut gen_rng: ResMut<GenRng>,
-    mut activity: ResMut<SpawnActivity>,
+    (samples, errors, budget): (Res<ReportSamples>, Res<PendingErrors>, Res<FrameBudget>),
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
+    let _timer = ListenerTimer::start("choose_some_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
+        if defer_over_budget(&mut commands, event, &budget) { continue; }
         match Structure::try_from(&event.list) {
             Ok(structure_list) => {
                 let sub_structure = structure_list.create_random_substructure(&event.count, gen_rng.rng_mut());
@@ -2098,67 +2619,81 @@ pub fn choose_some_spawn_listener(
                 );
             }
             Err(e) => {
-                eprintln!("ChooseSomeSpawnEvent import error: {:?}", e);
+                GenerationError::structure_error(&e)
+                    .with_structure(event.list.name())
+                    .with_parent(event.parent)
+                    .report(&errors);
             }
         }
     }
-    if processed { activity.idle_frames = 0; }
+    complete_tickets(&mut commands, handled);
 }
 
 pub fn rand_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<RandSpawnEvent>,
     mut gen_rng: ResMut<GenRng>,
-    mut activity: ResMut<SpawnActivity>,
+    (samples, budget): (Res<ReportSamples>, Res<FrameBudget>),
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
-        let jiggled = jiggle_transform(&mut gen_rng, event.rand.clone(), event.transform.clone());
-        info!(
-            "[RandJiggle] t=({:.3},{:.3},{:.3}) r=({:.1},{:.1},{:.1}) s=({:.2},{:.2},{:.2})",
-            jiggled.translation.0, jiggled.translation.1, jiggled.translation.2,
-            jiggled.rotation.0, jiggled.rotation.1, jiggled.rotation.2,
-            jiggled.scale.0, jiggled.scale.1, jiggled.scale.2
+    let _timer = ListenerTimer::start("rand_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
+        if defer_over_budget(&mut commands, event, &budget) { continue; }
+        let sampler = SpawnSampler::Rand { rand: event.rand.clone(), transform: event.transform.clone() };
+        let jiggled = sampler.sample(&mut gen_rng);
+        trace!(
+            target: SPAWN,
+            structure = event.reference.name(),
+            translation = ?jiggled.translation,
+            rotation = ?jiggled.rotation,
+            scale = ?jiggled.scale,
+            "Rand jiggle"
         );
         let reference = event.reference.clone();
         let parent = event.parent;
         commands.queue(move |world: &mut World| {
-            world.send_event(NestSpawnEvent { reference, transform: jiggled, parent });
+            send_tracked(world, NestSpawnEvent { reference, transform: jiggled, parent, sampler: Some(sampler), claimed: None });
         });
     }
-    if processed { activity.idle_frames = 0; }
+    complete_tickets(&mut commands, handled);
 }
 
 pub fn probability_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<ProbabilitySpawnEvent>,
     mut gen_rng: ResMut<GenRng>,
-    mut activity: ResMut<SpawnActivity>,
+    (samples, budget): (Res<ReportSamples>, Res<FrameBudget>),
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
+    let _timer = ListenerTimer::start("probability_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
+        if defer_over_budget(&mut commands, event, &budget) { continue; }
         if gen_rng.rng_mut().gen::<f32>() < event.probability {
             let reference = event.reference.clone();
             let transform = event.transform.clone();
             let parent = event.parent;
             commands.queue(move |world: &mut World| {
-                world.send_event(NestSpawnEvent { reference, transform, parent });
+                send_tracked(world, NestSpawnEvent { reference, transform, parent, sampler: None, claimed: None });
             });
         } // else skip spawn
     }
-    if processed { activity.idle_frames = 0; }
+    complete_tickets(&mut commands, handled);
 }
 
 pub fn loop_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<LoopSpawnEvent>,
-    mut activity: ResMut<SpawnActivity>,
+    mut occupancy: ResMut<OccupancyGrid>,
+    hierarchy: Query<(&Transform, Option<&Parent>)>,
+    (samples, budget): (Res<ReportSamples>, Res<FrameBudget>),
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
+    let _timer = ListenerTimer::start("loop_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
+        if defer_over_budget(&mut commands, event, &budget) { continue; }
         // Container for grouping loop spawns
         let container = commands
             .spawn_empty()
@@ -2171,6 +2706,9 @@ pub fn loop_spawn_listener(
             commands.entity(container).set_parent(parent);
         }
 
+        let footprint = if occupancy.enabled { structure_footprint(&event.reference) } else { Vec::new() };
+        let container_world = world_transform(event.parent, &hierarchy) * Transform::from(event.transform.clone());
+
         let positions = get_looped_position_list(
             Transform::from(event.transform.clone()).translation,
             event.shift_transform.clone(),
@@ -2197,7 +2735,7 @@ pub fn loop_spawn_listener(
             })
             .collect();
 
-        for (pos, offset) in positions.into_iter().zip(child_transforms.into_iter()) {
+        for (pos, offset) in positions.into_iter().zip(child_transforms) {
             let euler = EulerTransform {
                 translation: (
                     pos.x + offset.translation.0,
@@ -2207,24 +2745,32 @@ pub fn loop_spawn_listener(
                 rotation: offset.rotation,
                 scale: offset.scale,
             };
+            if !footprint.is_empty()
+                && !occupancy.try_claim(&footprints_at(&footprint, &(container_world * Transform::from(euler.clone()))))
+            {
+                continue;
+            }
 
             let reference = event.reference.clone();
+            let claimed = (!footprint.is_empty()).then_some(OccupancyClaimed::default());
             commands.queue(move |world: &mut World| {
-                world.send_event(NestSpawnEvent { reference, transform: euler, parent: Some(container) });
+                send_tracked(world, NestSpawnEvent { reference, transform: euler, parent: Some(container), sampler: None, claimed });
             });
         }
     }
-    if processed { activity.idle_frames = 0; }
+    complete_tickets(&mut commands, handled);
 }
 
 pub fn nesting_loop_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<NestingLoopSpawnEvent>,
-    mut activity: ResMut<SpawnActivity>,
+    (samples, budget): (Res<ReportSamples>, Res<FrameBudget>),
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
+    let _timer = ListenerTimer::start("nesting_loop_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
+        if defer_over_budget(&mut commands, event, &budget) { continue; }
         let base = Transform::from(event.transform.clone());
         let step = Transform::from(event.repeated_transform.clone());
 
@@ -2238,29 +2784,33 @@ pub fn nesting_loop_spawn_listener(
             let parent = event.parent;
             let euler = EulerTransform::from(current);
             commands.queue(move |world: &mut World| {
-                world.send_event(NestSpawnEvent { reference, transform: euler, parent });
+                send_tracked(world, NestSpawnEvent { reference, transform: euler, parent, sampler: None, claimed: None });
             });
         }
     }
-    if processed { activity.idle_frames = 0; }
+    complete_tickets(&mut commands, handled);
 }
 
 pub fn noise_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<NoiseSpawnEvent>,
     mut gen_rng: ResMut<GenRng>,
-    mut activity: ResMut<SpawnActivity>,
+    mut occupancy: ResMut<OccupancyGrid>,
+    hierarchy: Query<(&Transform, Option<&Parent>)>,
+    (samples, budget): (Res<ReportSamples>, Res<FrameBudget>),
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
+    let _timer = ListenerTimer::start("noise_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
+        if defer_over_budget(&mut commands, event, &budget) { continue; }
         // Container for grouping
         // Use a non-scaling container so child meshes are not scaled. Keep translation/rotation, zero out scale.
         let base = event.transform.clone();
         let container_tr = EulerTransform { scale: (1.0, 1.0, 1.0), ..base.clone() };
         let container = commands
             .spawn_empty()
-            .insert(Transform::from(container_tr))
+            .insert(Transform::from(container_tr.clone()))
             .insert(InheritedVisibility::default())
             .insert(Name::new("Noise Spawn"))
             .id();
@@ -2279,7 +2829,9 @@ pub fn noise_spawn_listener(
             resolution_modifier: event.resolution_modifier,
         };
 
-        let points = generate_noise_spawn_points(&temp_key, &mut gen_rng);
+        let points = generate_noise_spawn_points(&temp_key, &mut gen_rng, &samples);
+        let footprint = if occupancy.enabled { structure_footprint(&event.reference) } else { Vec::new() };
+        let container_world = world_transform(event.parent, &hierarchy) * Transform::from(container_tr);
 
         for (x, y, z) in points.into_iter() {
             // Apply desired radius scaling to local position so container can remain non-scaling.
@@ -2297,46 +2849,62 @@ pub fn noise_spawn_listener(
                 rotation: (0.0, 0.0, 0.0),
                 scale: (1.0, 1.0, 1.0),
             };
+            if !footprint.is_empty()
+                && !occupancy.try_claim(&footprints_at(&footprint, &(container_world * Transform::from_translation(local))))
+            {
+                continue;
+            }
 
             let reference = event.reference.clone();
+            let claimed = (!footprint.is_empty()).then_some(OccupancyClaimed::default());
             commands.queue(move |world: &mut World| {
-                world.send_event(NestSpawnEvent { reference, transform: euler, parent: Some(container) });
+                send_tracked(world, NestSpawnEvent { reference, transform: euler, parent: Some(container), sampler: None, claimed });
             });
         }
     }
-    if processed { activity.idle_frames = 0; }
+    complete_tickets(&mut commands, handled);
 }
 
 pub fn path_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<PathSpawnEvent>,
-    mut activity: ResMut<SpawnActivity>,
+    mut gen_rng: ResMut<GenRng>,
+    mut occupancy: ResMut<OccupancyGrid>,
+    hierarchy: Query<(&Transform, Option<&Parent>)>,
+    (samples, errors, budget): (Res<ReportSamples>, Res<PendingErrors>, Res<FrameBudget>),
 ) {
-    let mut processed = false;
-    for event in reader.read() {
-        processed = true;
-        // Container for grouping
-        let container = commands
-            .spawn_empty()
-            .insert(Transform::from(event.transform.clone()))
-            .insert(InheritedVisibility::default())
-            .insert(Name::new("Path Spawn"))
-            .id();
-
-        if let Some(parent) = event.parent {
-            commands.entity(container).set_parent(parent);
-        }
-
-        let curve = CubicCardinalSpline::new(event.tension, event.points.clone()).to_curve();
-        let positions: Vec<Vec3> = match event.spread {
-            SpreadData::Regular => {
-                curve.unwrap().iter_positions(event.count as usize).collect()
+    let _timer = ListenerTimer::start("path_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
+        if defer_over_budget(&mut commands, event, &budget) { continue; }
+
+        let mut points = match event.shaping.as_ref() {
+            Some(shaping) => shape_path(&event.points, shaping),
+            None => event.points.clone(),
+        };
+        if let Some(placement) = event.placement.as_ref() {
+            points = trim_polyline(&points, placement.start_inset, placement.end_inset);
+            if points.len() < 2 { continue; }
+        }
+        let curve_positions = |count: u32| -> Result<Vec<Vec3>, String> {
+            CubicCardinalSpline::new(event.tension, points.clone())
+                .to_curve()
+                .map(|curve| curve.iter_positions(count as usize).collect())
+                .map_err(|e| format!("Path spawn needs at least 2 points: {}", e))
+        };
+        let positions: Result<Vec<Vec3>, String> = match event.spread {
+            SpreadData::Regular => curve_positions(event.count),
+            SpreadData::Gaussian(standard_deviation) => {
+                curve_positions(event.count).and_then(|positions| jitter_path_points(positions, standard_deviation, &mut gen_rng))
             }
             SpreadData::Constant(spacing) => {
-                // Even spacing along the ORIGINAL polyline (event.points), inclusive of endpoints
-                let pts = &event.points;
-                if pts.len() < 2 {
-                    pts.clone()
+                // Even spacing along the (shaped) polyline, inclusive of endpoints
+                let pts = &points;
+                if spacing <= 0.0 {
+                    Err(format!("Constant spread needs a positive spacing (got {})", spacing))
+                } else if pts.len() < 2 {
+                    Ok(pts.clone())
                 } else {
                     let mut seg_lengths: Vec<f32> = Vec::with_capacity(pts.len() - 1);
                     let mut cum: Vec<f32> = Vec::with_capacity(pts.len());
@@ -2347,7 +2915,7 @@ pub fn path_spawn_listener(
                         cum.push(cum.last().copied().unwrap_or(0.0) + l);
                     }
                     let total_len = *cum.last().unwrap_or(&0.0);
-                    let step = spacing.max(0.001);
+                    let step = spacing;
 
                     // Helper to sample along the polyline at arclength s
                     let sample_at = |s: f32| -> Vec3 {
@@ -2375,13 +2943,40 @@ pub fn path_spawn_listener(
                     if remainder + 1.0e-4 >= step {
                         out.push(*pts.last().unwrap());
                     }
-                    out
+                    Ok(out)
                 }
             }
-            _ => {
-                panic!("This spread type not supported yet!");
+            SpreadData::Noise { ref fbm_data, sample_size, exclusivity_radius, resolution_modifier } => {
+                generate_noise_path_points(&points, fbm_data, sample_size, exclusivity_radius, resolution_modifier, event.count, (&mut gen_rng, &samples))
             }
         };
+        // Bad spread parameters fail this path only, through a GenerationErrorEvent
+        let positions = match positions {
+            Ok(positions) => positions,
+            Err(reason) => {
+                GenerationError::new(GenerationErrorKind::InvalidSpread, reason)
+                    .with_structure(event.reference.name())
+                    .with_parent(event.parent)
+                    .report(&errors);
+                continue;
+            }
+        };
+
+        // Container for grouping
+        let container = commands
+            .spawn_empty()
+            .insert(Transform::from(event.transform.clone()))
+            .insert(InheritedVisibility::default())
+            .insert(Name::new("Path Spawn"))
+            .id();
+
+        if let Some(parent) = event.parent {
+            commands.entity(container).set_parent(parent);
+        }
+
+        let footprint = if occupancy.enabled { structure_footprint(&event.reference) } else { Vec::new() };
+        let container_world = world_transform(event.parent, &hierarchy) * Transform::from(event.transform.clone());
+
         // Compute simple tangents using neighboring points (forward differences at ends)
         let n = positions.len();
         let mut tangents: Vec<Vec3> = Vec::with_capacity(n);
@@ -2396,30 +2991,44 @@ pub fn path_spawn_listener(
             tangents.push(t);
         }
 
-        for (point, tan) in positions.into_iter().zip(tangents.into_iter()) {
-            // Yaw so +Z faces along the tangent
-            let yaw_rad = tan.x.atan2(tan.z);
-            let yaw_deg = yaw_rad.to_degrees();
-            let euler = EulerTransform {
-                translation: (point.x, point.y, point.z),
-                rotation: (0.0, yaw_deg, 0.0),
-                scale: (1.0, 1.0, 1.0),
-            };
+        for (i, (path_point, tan)) in positions.into_iter().zip(tangents).enumerate() {
+            // Offset to the placement's sides along the tangent; without a placement this is the point itself
+            for (point, facing) in path_placements(path_point, tan, i, event.placement.as_ref()) {
+                // Yaw so +Z faces the requested direction
+                let yaw_rad = facing.x.atan2(facing.z);
+                let yaw_deg = yaw_rad.to_degrees();
+                let euler = EulerTransform {
+                    translation: (point.x, point.y, point.z),
+                    rotation: (0.0, yaw_deg, 0.0),
+                    scale: (1.0, 1.0, 1.0),
+                };
+                if !footprint.is_empty()
+                    && !occupancy.try_claim(&footprints_at(&footprint, &(container_world * Transform::from(euler.clone()))))
+                {
+                    continue;
+                }
 
-            let reference = event.reference.clone();
-            commands.queue(move |world: &mut World| {
-                world.send_event(NestSpawnEvent { reference, transform: euler, parent: Some(container) });
-            });
+                let reference = event.reference.clone();
+                let claimed = (!footprint.is_empty()).then_some(OccupancyClaimed::default());
+                commands.queue(move |world: &mut World| {
+                    send_tracked(world, NestSpawnEvent { reference, transform: euler, parent: Some(container), sampler: None, claimed });
+                });
+            }
         }
     }
-    if processed { activity.idle_frames = 0; }
+    complete_tickets(&mut commands, handled);
 }
 
 pub fn reflection_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<ReflectionSpawnEvent>,
+    (samples, errors, budget): (Res<ReportSamples>, Res<PendingErrors>, Res<FrameBudget>),
 ) {
-    for event in reader.read() {
+    let _timer = ListenerTimer::start("reflection_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
+        if defer_over_budget(&mut commands, event, &budget) { continue; }
         if event.reflect_child {
             // Reflect children individually: spawn original children and their reflected counterparts
             match Structure::try_from(&event.reference) {
@@ -2438,13 +3047,15 @@ pub fn reflection_spawn_listener(
 
                     // Attach structure tags to container if any
                     let tags = Tags(structure.tags.clone());
-                    println!(
-                        "[Spawn] Reflection(child): '{}' -> container {:?} | tags = {:?}",
-                        structure.structure_name, container, tags.0
+                    debug!(
+                        target: SPAWN,
+                        structure = %structure.structure_name,
+                        entity = ?container,
+                        tags = ?tags.0,
+                        "Reflection (child) spawned"
                     );
-                    if tags.len() != 0 {
+                    if !tags.is_empty() {
                         commands.entity(container).insert(tags);
-                        println!("[Spawn] Reflection(child): tags inserted on {:?}", container);
                     }
 
                     // Build a composite structure with original and reflected children
@@ -2483,7 +3094,10 @@ pub fn reflection_spawn_listener(
                     );
                 }
                 Err(e) => {
-                    eprintln!("ReflectionSpawnEvent import error: {:?}", e);
+                    GenerationError::structure_error(&e)
+                        .with_structure(event.reference.name())
+                        .with_parent(event.parent)
+                        .report(&errors);
                 }
             }
             continue;
@@ -2510,28 +3124,37 @@ pub fn reflection_spawn_listener(
         let reference_a = event.reference.clone();
         let euler_a = original.clone();
         commands.queue(move |world: &mut World| {
-            world.send_event(NestSpawnEvent { reference: reference_a, transform: euler_a, parent: Some(container) });
+            send_tracked(world, NestSpawnEvent { reference: reference_a, transform: euler_a, parent: Some(container), sampler: None, claimed: None });
         });
 
         let reference_b = event.reference.clone();
         let euler_b = reflected.clone();
         commands.queue(move |world: &mut World| {
-            world.send_event(NestSpawnEvent { reference: reference_b, transform: euler_b, parent: Some(container) });
+            send_tracked(world, NestSpawnEvent { reference: reference_b, transform: euler_b, parent: Some(container), sampler: None, claimed: None });
         });
     }
+    complete_tickets(&mut commands, handled);
 }
 
 pub fn selective_replacement_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<SelectiveReplacementSpawnEvent>,
     // The actual replacement is deferred and handled by selective_replacement_progressor
+    (samples, errors, budget): (Res<ReportSamples>, Res<PendingErrors>, Res<FrameBudget>),
 ) {
-    for event in reader.read() {
+    let _timer = ListenerTimer::start("selective_replacement_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        handled.push(id);
+        if defer_over_budget(&mut commands, event, &budget) { continue; }
         // 1) Spawn the initial structure under the provided parent/transform
         let initial_structure = match Structure::try_from(&event.initial_reference) {
             Ok(s) => s,
             Err(e) => {
-                eprintln!("SelectiveReplacement initial import error: {:?}", e);
+                GenerationError::structure_error(&e)
+                    .with_structure(event.initial_reference.name())
+                    .with_parent(event.parent)
+                    .report(&errors);
                 continue;
             }
         };
@@ -2547,20 +3170,13 @@ pub fn selective_replacement_spawn_listener(
             commands.entity(container).set_parent(parent);
         }
 
-        println!(
-            "[SelectiveReplacement] Start: initial '{}' -> container {:?}",
-            initial_structure.structure_name, container
-        );
+        let _span = debug_span!(target: SPAWN, "structure", structure = %initial_structure.structure_name, entity = ?container).entered();
 
         // Attach Tags on the container if the structure has them
         let container_tags = Tags(initial_structure.tags.clone());
-        println!(
-            "[SelectiveReplacement] Container tags = {:?}",
-            container_tags.0
-        );
-        if container_tags.len() != 0 {
+        debug!(target: SPAWN, tags = ?container_tags.0, "SelectiveReplacement started");
+        if !container_tags.is_empty() {
             commands.entity(container).insert(container_tags);
-            println!("[SelectiveReplacement] Tags inserted on container {:?}", container);
         }
 
         let _ = spawn_structure_data(
@@ -2569,10 +3185,7 @@ pub fn selective_replacement_spawn_listener(
             Transform::IDENTITY,
             Some(container),
         );
-        println!(
-            "[SelectiveReplacement] Finished enqueueing initial '{}' children under {:?}",
-            initial_structure.structure_name, container
-        );
+        trace!(target: SPAWN, "SelectiveReplacement enqueued initial children");
 
         // 2) Defer the replacement: attach a pending component to the container.
         commands.entity(container).insert(SelectiveReplacementPending {
@@ -2584,6 +3197,7 @@ pub fn selective_replacement_spawn_listener(
             stable_frames: 0,
         });
     }
+    complete_tickets(&mut commands, handled);
 }
 
 // Runs each frame to check if the subtree under containers with SelectiveReplacementPending has stabilized.
@@ -2594,7 +3208,7 @@ pub fn selective_replacement_progressor(
     transform_query: Query<&Transform>,
     tag_query: Query<(Entity, &Tags, Option<&Name>)>,
     any_entity_query: Query<Entity>,
-    mut gen_rng: ResMut<GenRng>,
+    (mut gen_rng, errors): (ResMut<GenRng>, Res<PendingErrors>),
 ) {
     for (container, mut pending) in pending_query.iter_mut() {
         // Count all descendants (regardless of tags). This stabilizes only when the subtree finished expanding.
@@ -2608,9 +3222,12 @@ pub fn selective_replacement_progressor(
         if total_descendants == 0 {
             // Nothing spawned yet under this container — keep waiting.
             if pending.last_descendant_count != 0 {
-                println!(
-                    "[SelectiveReplacement][Deferred] descendants changed: {} -> {} (waiting)",
-                    pending.last_descendant_count, total_descendants
+                trace!(
+                    target: SPAWN,
+                    entity = ?container,
+                    from = pending.last_descendant_count,
+                    to = total_descendants,
+                    "SelectiveReplacement: descendants changed; waiting"
                 );
                 pending.last_descendant_count = 0;
             }
@@ -2619,9 +3236,12 @@ pub fn selective_replacement_progressor(
         }
 
         if total_descendants != pending.last_descendant_count {
-            println!(
-                "[SelectiveReplacement][Deferred] descendants changed: {} -> {} (waiting)",
-                pending.last_descendant_count, total_descendants
+            trace!(
+                target: SPAWN,
+                entity = ?container,
+                from = pending.last_descendant_count,
+                to = total_descendants,
+                "SelectiveReplacement: descendants changed; waiting"
             );
             pending.last_descendant_count = total_descendants;
             pending.stable_frames = 0;
@@ -2642,22 +3262,28 @@ pub fn selective_replacement_progressor(
         // If there are still zero candidates, do not proceed. Keep waiting.
         if count == 0 {
             if pending.last_candidate_count != 0 {
-                println!(
-                    "[SelectiveReplacement][Deferred] candidates changed: {} -> {} (waiting)",
-                    pending.last_candidate_count, count
+                trace!(
+                    target: SPAWN,
+                    entity = ?container,
+                    from = pending.last_candidate_count,
+                    to = count,
+                    "SelectiveReplacement: candidates changed; waiting"
                 );
                 pending.last_candidate_count = 0;
             } else {
-                println!("[SelectiveReplacement][Deferred] still 0 candidates; waiting");
+                trace!(target: SPAWN, entity = ?container, "SelectiveReplacement: still 0 candidates; waiting");
             }
             pending.stable_frames = 0;
             continue;
         }
 
         if count != pending.last_candidate_count {
-            println!(
-                "[SelectiveReplacement][Deferred] candidates changed: {} -> {} (waiting)",
-                pending.last_candidate_count, count
+            trace!(
+                target: SPAWN,
+                entity = ?container,
+                from = pending.last_candidate_count,
+                to = count,
+                "SelectiveReplacement: candidates changed; waiting"
             );
             pending.last_candidate_count = count;
             pending.stable_frames = 0;
@@ -2672,16 +3298,22 @@ pub fn selective_replacement_progressor(
         }
 
         // Perform replacement now
-        println!(
-            "[SelectiveReplacement][Deferred] stabilized with {} candidates. Proceeding to replace {}",
-            count, pending.replace_count
+        debug!(
+            target: SPAWN,
+            entity = ?container,
+            candidates = count,
+            replace_count = pending.replace_count,
+            "SelectiveReplacement stabilized; replacing"
         );
 
         // Resolve the replacement structure
         let replacement_structure = match Structure::try_from(&pending.replacement_reference) {
             Ok(s) => s,
             Err(e) => {
-                eprintln!("SelectiveReplacement replacement import error: {:?}", e);
+                GenerationError::structure_error(&e)
+                    .with_structure(pending.replacement_reference.name())
+                    .with_parent(Some(container))
+                    .report(&errors);
                 // Remove the pending to avoid infinite retry
                 commands.entity(container).remove::<SelectiveReplacementPending>();
                 continue;
@@ -2692,18 +3324,10 @@ pub fn selective_replacement_progressor(
         let chosen: Vec<(Entity, Option<String>)> = candidates
             .into_iter()
             .choose_multiple(gen_rng.rng_mut(), pending.replace_count);
-        println!(
-            "[SelectiveReplacement] Chosen {} entities to replace (replace_count = {})",
-            chosen.len(), pending.replace_count
-        );
+        debug!(target: SPAWN, entity = ?container, chosen = chosen.len(), "SelectiveReplacement chose targets");
 
         for (target, name_opt) in chosen {
-            #[cfg(not(feature = "debug"))]
-            let _ = &name_opt;
-            println!(
-                "[SelectiveReplacement] Replacing entity {:?} name {:?}",
-                target, name_opt
-            );
+            debug!(target: SPAWN, entity = ?target, name = name_opt.as_deref(), "SelectiveReplacement replacing");
             // Get parent of the target and its transform
             let parent_of_target = parent_query.get(target).ok().map(|p| p.get());
             let Ok(target_transform) = transform_query.get(target) else { continue; };
@@ -2714,7 +3338,7 @@ pub fn selective_replacement_progressor(
             // Spawn replacement container
             let repl_container = commands
                 .spawn_empty()
-                .insert(Transform::from(*target_transform))
+                .insert(*target_transform)
                 .insert(InheritedVisibility::default())
                 .insert(Name::new(replacement_structure.structure_name.clone()))
                 .id();
@@ -2725,13 +3349,15 @@ pub fn selective_replacement_progressor(
 
             // Attach tags from replacement structure
             let repl_tags = Tags(replacement_structure.tags.clone());
-            println!(
-                "[SelectiveReplacement] Replacement '{}' -> container {:?} | tags = {:?}",
-                replacement_structure.structure_name, repl_container, repl_tags.0
+            debug!(
+                target: SPAWN,
+                structure = %replacement_structure.structure_name,
+                entity = ?repl_container,
+                tags = ?repl_tags.0,
+                "SelectiveReplacement replacement spawned"
             );
-            if repl_tags.len() != 0 {
+            if !repl_tags.is_empty() {
                 commands.entity(repl_container).insert(repl_tags);
-                println!("[SelectiveReplacement] Tags inserted on replacement container {:?}", repl_container);
             }
 
             let _ = spawn_structure_data(
@@ -2758,71 +3384,3 @@ fn is_descendant(ancestor: Entity, child: Entity, parent_query: &Query<&Parent>)
     false
 }
 
-// Despawn the lower-priority entity whenever two colliders with ColliderPriority make contact.
-pub fn collider_priority_despawn_system(
-    mut commands: Commands,
-    mut contact_events: EventReader<ContactForceEvent>,
-    mut collision_events: EventReader<CollisionEvent>,
-    priorities: Query<&ColliderPriority>,
-    name_query: Query<&Name>,
-) {
-    // Track entities we already queued for despawn this frame to avoid double-despawn warnings
-    let mut already_despawned: HashSet<Entity> = HashSet::new();
-    // Process contact force events (if any)
-    for ev in contact_events.read() {
-        let a = ev.collider1;
-        let b = ev.collider2;
-        if let (Ok(pa), Ok(pb)) = (priorities.get(a), priorities.get(b)) {
-            let name_a = name_query.get(a).ok().map(|n| n.as_str().to_string());
-            let name_b = name_query.get(b).ok().map(|n| n.as_str().to_string());
-            #[cfg(not(feature = "debug"))]
-            { let _ = &name_a; let _ = &name_b; }
-            info!(
-                "[PriorityDespawn] ContactForce: {:?}({:?})[{}] <-> {:?}({:?})[{}]",
-                a, name_a, pa.0, b, name_b, pb.0
-            );
-            if pa.0 == pb.0 { continue; }
-            let loser = if pa.0 < pb.0 { a } else { b };
-            let loser_name = name_query.get(loser).ok().map(|n| n.as_str().to_string());
-            #[cfg(not(feature = "debug"))]
-            { let _ = &loser_name; }
-            if already_despawned.insert(loser) {
-                info!("[PriorityDespawn] Despawning lower priority entity: {:?} ({:?})", loser, loser_name);
-                commands.queue(move |world: &mut World| {
-                    if let Ok(ent) = world.get_entity_mut(loser) {
-                        ent.despawn_recursive();
-                    }
-                });
-            }
-        }
-    }
-
-    // Also process basic collision start events (more reliable for kinematic bodies)
-    for ev in collision_events.read() {
-        if let CollisionEvent::Started(a, b, _) = ev {
-            if let (Ok(pa), Ok(pb)) = (priorities.get(*a), priorities.get(*b)) {
-                let name_a = name_query.get(*a).ok().map(|n| n.as_str().to_string());
-                let name_b = name_query.get(*b).ok().map(|n| n.as_str().to_string());
-                #[cfg(not(feature = "debug"))]
-                { let _ = &name_a; let _ = &name_b; }
-                info!(
-                    "[PriorityDespawn] CollisionStarted: {:?}({:?})[{}] <-> {:?}({:?})[{}]",
-                    *a, name_a, pa.0, *b, name_b, pb.0
-                );
-                if pa.0 == pb.0 { continue; }
-                let loser = if pa.0 < pb.0 { *a } else { *b };
-                let loser_name = name_query.get(loser).ok().map(|n| n.as_str().to_string());
-                #[cfg(not(feature = "debug"))]
-                { let _ = &loser_name; }
-                if already_despawned.insert(loser) {
-                    info!("[PriorityDespawn] Despawning lower priority entity: {:?} ({:?})", loser, loser_name);
-                    commands.queue(move |world: &mut World| {
-                        if let Ok(ent) = world.get_entity_mut(loser) {
-                            ent.despawn_recursive();
-                        }
-                    });
-                }
-            }
-        }
-    }
-}
@@ -1,8 +1,22 @@
 use bevy::prelude::*;
 use crate::event_system::spawn_events::*;
+use crate::core::tmaterial::InlineMaterialCache;
 use crate::event_system::event_listeners::*;
 use crate::event_system::spawnables::structure::structure_spawn_listener;
-use crate::event_system::event_listeners::collider_priority_despawn_system;
+use crate::event_system::work_tickets::{expire_dropped_tickets, WorkTickets};
+use crate::event_system::generation_progress::*;
+use crate::event_system::overlap_resolution::{clear_overlap_report, resolve_collider_overlaps, OverlapReport, PendingOverlaps};
+use crate::event_system::generation_report::{collect_generation_samples, finish_generation_report, record_phase_times, reset_generation_report, GenerationReport, PhaseClock, ReportSamples};
+use crate::spawning::occupancy::{reset_occupancy_grid, OccupancyGrid};
+use crate::event_system::generation_log::sync_log_context;
+use crate::event_system::generation_budget::{reset_frame_budget, FrameBudget, GenerationBudget};
+use crate::event_system::generation_error::{send_generation_errors, GenerationErrorEvent, GenerationStrictness, PendingErrors};
+use crate::event_system::path_failure::PathResolutionFailed;
+use crate::spawning::path_solver::DefaultPathSolver;
+use crate::core::navmesh_config::NavMeshAuthoring;
+use crate::spawning::agent_navmesh::{poll_agent_navmesh_builds, start_agent_navmesh_builds, AgentNavMeshes};
+use crate::spawning::nav_links::{register_nav_connections, NavAreaCosts, NavLinks};
+use crate::event_system::generation_phases::{reset_custom_phases, run_custom_phases, CustomPhaseQueue, GenerationPhases};
 
 pub struct EventSystemPlugin;
 
@@ -10,11 +24,11 @@ impl Plugin for EventSystemPlugin {
     fn build(&self, app: &mut App) {
         // Initialize generation states/resources
         app.init_resource::<CollisionResolutionTimer>();
-        app.init_resource::<SpawningStability>();
-        app.init_resource::<GeneratingFrameCounter>();
-        app.init_resource::<SpawnActivity>();
-        app.init_resource::<GenerationAdvanceArming>();
         app.init_resource::<NavMeshPriorityThreshold>();
+        app.init_resource::<NavMeshAuthoring>();
+        app.init_resource::<AgentNavMeshes>();
+        app.init_resource::<NavLinks>();
+        app.init_resource::<NavAreaCosts>();
         app.init_resource::<PathResolveTimer>();
         #[cfg(feature = "debug")]
         {
@@ -24,7 +38,25 @@ impl Plugin for EventSystemPlugin {
         app.init_resource::<HighestPassIndex>();
         app.init_resource::<PendingInPass>();
         app.init_resource::<PendingPathEvents>();
+        app.init_resource::<PendingNests>();
+        app.init_resource::<ResolvedPolylines>();
+        app.init_resource::<DefaultPathSolver>();
         app.init_resource::<ResolvedPathSpawns>();
+        app.init_resource::<WorkTickets>();
+        app.init_resource::<PathResolveCounters>();
+        app.init_resource::<GenerationProgress>();
+        app.init_resource::<GenerationPhases>();
+        app.init_resource::<CustomPhaseQueue>();
+        app.init_resource::<OverlapReport>();
+        app.init_resource::<PendingOverlaps>();
+        app.init_resource::<OccupancyGrid>();
+        app.init_resource::<GenerationReport>();
+        app.init_resource::<PhaseClock>();
+        app.init_resource::<ReportSamples>();
+        app.init_resource::<GenerationStrictness>();
+        app.init_resource::<PendingErrors>();
+        app.init_resource::<GenerationBudget>();
+        app.init_resource::<FrameBudget>();
         app.init_state::<GenerationState>();
         // Registering all events
         app.add_event::<MeshSpawnEvent>()
@@ -35,6 +67,9 @@ impl Plugin for EventSystemPlugin {
             .add_event::<DirectionalLightSpawnEvent>()
             .add_event::<AmbientLightSpawnEvent>()
             .add_event::<DistanceFogSpawnEvent>()
+            .add_event::<NavMeshSettingsSpawnEvent>()
+            .add_event::<NavLinkSpawnEvent>()
+            .add_event::<NavAreaSpawnEvent>()
             .add_event::<AtmosphereNishitaSpawnEvent>()
             .add_event::<MainDirectionalLightSpawnEvent>()
             .add_event::<SoundEffectSpawnEvent>()
@@ -56,6 +91,13 @@ impl Plugin for EventSystemPlugin {
             .add_event::<ReflectionSpawnEvent>()
             .add_event::<SelectiveReplacementSpawnEvent>();
         app.add_event::<InPassSpawnEvent>();
+        app.add_event::<GenerationStarted>()
+            .add_event::<PhaseChanged>()
+            .add_event::<GenerationFinished>()
+            .add_event::<CancelGeneration>()
+            .add_event::<GenerationCancelled>();
+        app.add_event::<GenerationErrorEvent>();
+        app.add_event::<PathResolutionFailed>();
 
         // Registering non-path event handling systems (only needed during Generating)
         app.add_systems(Update, (
@@ -68,6 +110,9 @@ impl Plugin for EventSystemPlugin {
             main_directional_light_spawn_listener,
             ambient_light_spawn_listener,
             distance_fog_spawn_listener,
+            navmesh_settings_spawn_listener,
+            nav_link_spawn_listener,
+            nav_area_spawn_listener,
             sound_effect_spawn_listener,
             background_music_spawn_listener,
             // Materialize path-driven spawns during Generating (not PathResolve)
@@ -75,6 +120,17 @@ impl Plugin for EventSystemPlugin {
             nest_spawn_listener,
         ).run_if(in_state(GenerationState::Generating)));
 
+        // Swap authored/team materials onto glTF submeshes once their scene has been instanced
+        app.init_resource::<TeamMaterialCache>();
+        app.init_resource::<InlineMaterialCache>();
+        app.add_observer(apply_scene_materials);
+        // Build mesh-derived colliders (convex hull, trimesh, decomposition) from the instanced glTF
+        app.add_observer(build_scene_mesh_colliders);
+        // ...or report them when the scene fails to load, so Generating is not held forever
+        app.add_systems(Update, fail_unloaded_mesh_colliders
+            .run_if(in_state(GenerationState::Generating))
+            .before(generation_state_driver));
+
         // Atmosphere events are only processed when the 'atmosphere' feature is enabled
         #[cfg(feature = "atmosphere")]
         {
@@ -83,6 +139,15 @@ impl Plugin for EventSystemPlugin {
 
         // UI overlay update (always on)
         app.add_systems(Update, update_generation_state_overlay);
+        // Public progress snapshot and phase events for loading screens/telemetry
+        app.add_systems(Update, (emit_phase_changes, update_generation_progress));
+        // Statistics/profiling report, finalised once GenerationFinished is sent
+        app.add_systems(Update, (
+            reset_generation_report,
+            record_phase_times,
+            collect_generation_samples,
+            finish_generation_report,
+        ).chain());
         // Draw accumulated path debug gizmos when enabled
         #[cfg(feature = "debug")]
         {
@@ -91,8 +156,6 @@ impl Plugin for EventSystemPlugin {
 
         // Generation-time systems only (excluding pathfinding, which is buffered)
         app.add_systems(Update, (
-            // Tick spawn activity every frame; individual listeners will reset this when they process events
-            tick_spawn_activity,
             buffer_path_events,
             choose_spawn_listener,
             choose_some_spawn_listener,
@@ -107,10 +170,14 @@ impl Plugin for EventSystemPlugin {
             noise_spawn_listener,
             reflection_spawn_listener,
             selective_replacement_spawn_listener,
-            collider_priority_despawn_system,
-            // Tick generating frame counter while in Generating state
-            tick_generating_counter,
         ).run_if(in_state(GenerationState::Generating)));
+        // Overlap resolution only in passes that ask for it; CollisionResolution waits for moved objects to settle
+        app.add_systems(Update, resolve_collider_overlaps
+            .run_if(in_state(GenerationState::Generating).or(in_state(GenerationState::CollisionResolution)))
+            .run_if(pass_runs_collision_despawn));
+        app.add_systems(Update, clear_overlap_report.run_if(on_event::<GenerationStarted>).before(resolve_collider_overlaps));
+        // Footprints only live for one generation run
+        app.add_systems(OnEnter(GenerationState::Generating), reset_occupancy_grid.run_if(on_event::<GenerationStarted>).after(start_generation_progress));
 
         // Path resolution window: run all pathfinding and material application here
         app.add_systems(OnEnter(GenerationState::PathResolve), (reset_path_resolve_phase, flush_path_events_on_enter));
@@ -123,7 +190,16 @@ impl Plugin for EventSystemPlugin {
         app.add_systems(Update, advance_from_path_resolve);
 
         // Single state driver for all GenerationState transitions (always scheduled)
-        app.add_systems(Update, generation_state_driver);
+        // Ordered after process_pending_inpass so the structures it dispatches are already ticketed
+        app.add_systems(Update, generation_state_driver.after(process_pending_inpass));
+        // Downstream phases registered with add_generation_phase run here while in CustomPhase
+        app.add_systems(Update, run_custom_phases.run_if(in_state(GenerationState::CustomPhase)));
+        // Release tickets for spawn events that expired without reaching a listener
+        app.add_systems(Last, expire_dropped_tickets);
+        // Cancelling drops whatever is still queued, so it runs after everything this frame was sent
+        app.add_systems(Last, cancel_generation.run_if(on_event::<CancelGeneration>).after(expire_dropped_tickets).before(send_generation_errors));
+        // Errors raised anywhere during the frame are published as GenerationErrorEvent (and may abort the run)
+        app.add_systems(Last, send_generation_errors.after(expire_dropped_tickets));
 
         // Deferred processors to run after children spawned in Update have been realized (only during Generating)
         app.add_systems(PostUpdate, (
@@ -132,13 +208,22 @@ impl Plugin for EventSystemPlugin {
             strip_generation_only_colliders_progressor,
         ).run_if(in_state(GenerationState::Generating)));
 
-        // On entering navmesh build phase, activate queued affectors
-        app.add_systems(OnEnter(GenerationState::NavMeshBuilding), activate_navmesh_affectors);
+        // On entering navmesh build phase, apply the authored settings, register links and area volumes,
+        // activate queued affectors and start the agent profile navmeshes from them
+        app.add_systems(OnEnter(GenerationState::NavMeshBuilding), (
+            apply_navmesh_config,
+            register_nav_connections,
+            activate_navmesh_affectors,
+            start_agent_navmesh_builds,
+        ).chain());
+        app.add_systems(Update, poll_agent_navmesh_builds);
         // On entering Generating, reset counters and flush any resolved PathSpawnEvent from PathResolve
-        app.add_systems(OnEnter(GenerationState::Generating), (reset_generating_phase, flush_resolved_paths_on_enter_generating));
-        // On entering Completed, advance pass if more passes exist
-        app.add_systems(OnEnter(GenerationState::Completed), advance_pass_or_finish);
+        app.add_systems(OnEnter(GenerationState::Generating), (start_generation_progress, sync_log_context, reset_generating_phase, reset_custom_phases, raise_highest_pass_to_pipeline, flush_resolved_paths_on_enter_generating));
+        // On entering Completed, advance pass if more passes exist (closing the run first if this was the last one)
+        app.add_systems(OnEnter(GenerationState::Completed), (finish_generation_progress, advance_pass_or_finish).chain());
         app.add_systems(Startup, spawn_generation_state_overlay);
+        // Per-frame spawn budget for the spawn listeners
+        app.add_systems(First, reset_frame_budget);
 
     }
 }
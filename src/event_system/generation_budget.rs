@@ -0,0 +1,106 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+use bevy::prelude::*;
+use crate::event_system::generation_log::SPAWN;
+use crate::event_system::work_tickets::send_tracked;
+
+// Per-frame limit on the spawn events the spawn listeners handle. Events over the limit are re-sent
+// and handled on a later frame, so a large structure is spread over several frames instead of
+// flooding the queues in one. None leaves that limit off (the default).
+// max_spawns_per_frame is counted per spawn listener: the listeners run in parallel, so a shared
+// count would defer whichever events happened to come last across threads. With a count of its own,
+// the events a listener defers depend only on its own queue, and a seed reproduces a run under the
+// same budget. The time limit makes the frame an event lands in depend on machine speed; use
+// max_spawns_per_frame alone when runs must be reproducible from their seed.
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct GenerationBudget {
+    pub max_spawns_per_frame: Option<u32>,
+    // Wall time since the start of the frame after which further events are deferred
+    pub max_ms_per_frame: Option<f32>,
+}
+
+#[derive(Default)]
+struct FrameSpend {
+    limits: GenerationBudget,
+    // Events handled this frame, per spawn event type (one listener reads each type)
+    spawns: HashMap<TypeId, u32>,
+    deferred: u32,
+    started: Option<Instant>,
+}
+
+// Running totals for the current frame, shared by all spawn listeners through `Res<FrameBudget>`
+#[derive(Resource, Default)]
+pub struct FrameBudget(Mutex<FrameSpend>);
+
+// Start a new frame's budget from the current GenerationBudget
+pub fn reset_frame_budget(budget: Res<GenerationBudget>, frame_budget: Res<FrameBudget>) {
+    if let Ok(mut frame) = frame_budget.0.lock() {
+        if frame.deferred > 0 {
+            let spawns: u32 = frame.spawns.values().sum();
+            debug!(target: SPAWN, deferred = frame.deferred, spawns, "spawn budget exhausted; deferred events");
+        }
+        *frame = FrameSpend { limits: *budget, started: Some(Instant::now()), ..Default::default() };
+    }
+}
+
+// Take one event of type `kind` from this frame's budget. The first event of each type in a frame is
+// always allowed so generation keeps moving whatever the limits are.
+fn take_budget(budget: &FrameBudget, kind: TypeId) -> bool {
+    let Ok(mut frame) = budget.0.lock() else { return true; };
+    let spawns = frame.spawns.get(&kind).copied().unwrap_or(0);
+    if spawns > 0 {
+        let over_count = frame.limits.max_spawns_per_frame.is_some_and(|max| spawns >= max);
+        let over_time = match (frame.limits.max_ms_per_frame, frame.started) {
+            (Some(max_ms), Some(started)) => started.elapsed().as_secs_f32() * 1000.0 >= max_ms,
+            _ => false,
+        };
+        if over_count || over_time {
+            frame.deferred += 1;
+            return false;
+        }
+    }
+    *frame.spawns.entry(kind).or_default() += 1;
+    true
+}
+
+// Called by spawn listeners for each event they read: when the frame's budget is spent the event is
+// re-sent (with a new work ticket) and true is returned so the listener skips it for now.
+pub fn defer_over_budget<E: Event + Clone>(commands: &mut Commands, event: &E, budget: &FrameBudget) -> bool {
+    if take_budget(budget, TypeId::of::<E>()) {
+        return false;
+    }
+    let ev = event.clone();
+    commands.queue(move |world: &mut World| { send_tracked(world, ev); });
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct First;
+    struct Second;
+
+    fn frame_with(max: u32) -> FrameBudget {
+        let budget = FrameBudget::default();
+        budget.0.lock().unwrap().limits = GenerationBudget { max_spawns_per_frame: Some(max), max_ms_per_frame: None };
+        budget
+    }
+
+    #[test]
+    fn spawn_count_is_kept_per_listener() {
+        // However the two listeners interleave, each handles exactly its own share
+        let budget = frame_with(2);
+        let first = TypeId::of::<First>();
+        let second = TypeId::of::<Second>();
+        assert!(take_budget(&budget, first));
+        assert!(take_budget(&budget, first));
+        assert!(!take_budget(&budget, first));
+        assert!(take_budget(&budget, second));
+        assert!(take_budget(&budget, second));
+        assert!(!take_budget(&budget, second));
+        assert_eq!(budget.0.lock().unwrap().deferred, 2);
+    }
+}
@@ -0,0 +1,237 @@
+use std::sync::Mutex;
+use bevy::prelude::*;
+use bevy::utils::tracing::Span;
+use crate::core::structure_error::StructureError;
+use crate::event_system::event_listeners::{CurrentPass, GenerationState, HighestPassIndex, PendingInPass, PendingNests, ResolvedPathSpawns};
+use crate::event_system::generation_log::{LogContext, ERROR, IMPORT, MATERIAL, PATH, PHASE, SPAWN};
+use crate::event_system::generation_progress::GenerationProgress;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GenerationErrorKind {
+    // A referenced .arch file is missing or does not parse
+    ImportFailed,
+    CycleDetected,
+    // Ownership::Inherit on a top-level reference
+    InvalidOwnership,
+    // InPass names a pass that is not declared in the GenerationPipeline
+    UnknownPass,
+    MissingMaterial,
+    // A material override or team colour mesh pattern is not a valid glob
+    InvalidPattern,
+    ColliderBuild,
+    // Duplicate or cyclic custom generation phases
+    PhaseRegistration,
+    // SpreadData parameters a path cannot be spawned with (e.g. a non-positive spacing)
+    InvalidSpread,
+    // A PathToTag/PathToAllTags request ran out of retries with nothing to fall back to
+    PathUnresolved,
+    // Entities the connectivity check found cut off from the others
+    Unreachable,
+    Other,
+}
+
+impl GenerationErrorKind {
+    // Tracing target the error is logged under
+    pub fn target(&self) -> &'static str {
+        match self {
+            GenerationErrorKind::ImportFailed
+            | GenerationErrorKind::CycleDetected
+            | GenerationErrorKind::InvalidOwnership => IMPORT,
+            GenerationErrorKind::MissingMaterial | GenerationErrorKind::InvalidPattern => MATERIAL,
+            GenerationErrorKind::PhaseRegistration => PHASE,
+            GenerationErrorKind::PathUnresolved | GenerationErrorKind::Unreachable => PATH,
+            GenerationErrorKind::UnknownPass
+            | GenerationErrorKind::InvalidSpread
+            | GenerationErrorKind::ColliderBuild
+            | GenerationErrorKind::Other => SPAWN,
+        }
+    }
+}
+
+impl From<&StructureError> for GenerationErrorKind {
+    fn from(error: &StructureError) -> Self {
+        match error {
+            StructureError::CycleDetected(_) => GenerationErrorKind::CycleDetected,
+            StructureError::ImportFailed(_) => GenerationErrorKind::ImportFailed,
+            StructureError::InheritOwnershipAtTopLevel(_) => GenerationErrorKind::InvalidOwnership,
+            StructureError::Other(_) => GenerationErrorKind::Other,
+        }
+    }
+}
+
+// Sent once per failure (a failed import, an unknown pass, a missing material, ...). Games can surface
+// these in-editor; GenerationStrictness decides whether they are logged and whether they abort the run.
+#[derive(Event, Debug, Clone)]
+pub struct GenerationErrorEvent {
+    pub kind: GenerationErrorKind,
+    // Structure being spawned when the error occurred, when known
+    pub structure: Option<String>,
+    // Names of the entities the failing content was spawned under, from the root down
+    pub parent_chain: Vec<String>,
+    // The underlying error message
+    pub source: String,
+    // Nearest entity of the parent chain, for selecting it in an editor
+    pub entity: Option<Entity>,
+    // 0-based pass the error occurred in
+    pub pass: u8,
+}
+
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GenerationStrictness {
+    // Send the events without logging them
+    Ignore,
+    // Log every error as a warning and keep generating
+    #[default]
+    Warn,
+    // Log the error and end the run right away (GenerationStats::aborted), e.g. to fail CI on broken content
+    Abort,
+}
+
+// Builder for a failure reported from a listener or helper:
+// `GenerationError::structure_error(&e).with_structure(name).with_parent(parent).report(&errors)`
+#[derive(Debug)]
+pub struct GenerationError {
+    kind: GenerationErrorKind,
+    structure: Option<String>,
+    parent: Option<Entity>,
+    source: String,
+    pass: u8,
+    fatal: bool,
+}
+
+impl GenerationError {
+    pub fn new(kind: GenerationErrorKind, source: impl ToString) -> Self {
+        GenerationError { kind, structure: None, parent: None, source: source.to_string(), pass: 0, fatal: false }
+    }
+
+    pub fn structure_error(error: &StructureError) -> Self {
+        Self::new(GenerationErrorKind::from(error), error)
+    }
+
+    pub fn with_structure(mut self, structure: impl Into<String>) -> Self {
+        self.structure = Some(structure.into());
+        self
+    }
+
+    // Entity the failing content was spawned under; its ancestors make up the parent chain
+    pub fn with_parent(mut self, parent: Option<Entity>) -> Self {
+        self.parent = parent;
+        self
+    }
+
+    // End the run as GenerationStrictness::Abort would, whatever the configured strictness
+    pub fn fatal(mut self) -> Self {
+        self.fatal = true;
+        self
+    }
+
+    // Queue the error; it is logged and sent as a GenerationErrorEvent at the end of the frame
+    pub fn report(mut self, errors: &PendingErrors) {
+        self.pass = errors.1.pass();
+        if let Ok(mut pending) = errors.0.lock() {
+            pending.push((self, Span::current()));
+        }
+    }
+}
+
+// Errors raised during the frame, with the span they were raised in, sent once per frame by
+// send_generation_errors. Reporting only needs `Res<PendingErrors>`, so listeners and the helpers
+// they pass it to can raise errors without an EventWriter of their own.
+#[derive(Resource, Default)]
+pub struct PendingErrors(Mutex<Vec<(GenerationError, Span)>>, LogContext);
+
+impl PendingErrors {
+    // Pass the queued errors are attributed to
+    pub fn context(&self) -> &LogContext {
+        &self.1
+    }
+
+    // Errors reported this frame and not yet sent
+    pub fn len(&self) -> usize {
+        self.0.lock().map(|pending| pending.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn parent_chain(entity: Option<Entity>, hierarchy: &Query<(Option<&Name>, Option<&Parent>)>) -> Vec<String> {
+    let mut chain = Vec::new();
+    let mut current = entity;
+    while let Some(e) = current {
+        let Ok((name, parent)) = hierarchy.get(e) else { break; };
+        chain.push(name.map(|n| n.as_str().to_string()).unwrap_or_else(|| format!("{:?}", e)));
+        current = parent.map(|p| p.get());
+    }
+    chain.reverse();
+    chain
+}
+
+pub fn send_generation_errors(
+    mut writer: EventWriter<GenerationErrorEvent>,
+    (strictness, pending): (Res<GenerationStrictness>, Res<PendingErrors>),
+    hierarchy: Query<(Option<&Name>, Option<&Parent>)>,
+    mut progress: ResMut<GenerationProgress>,
+    (cur, mut highest): (Res<CurrentPass>, ResMut<HighestPassIndex>),
+    (mut pending_inpass, mut resolved, mut pending_nests): (ResMut<PendingInPass>, ResMut<ResolvedPathSpawns>, ResMut<PendingNests>),
+    (state, mut next): (Res<State<GenerationState>>, ResMut<NextState<GenerationState>>),
+) {
+    let errors = {
+        let Ok(mut pending) = pending.0.lock() else { return; };
+        std::mem::take(&mut *pending)
+    };
+    if errors.is_empty() { return; }
+
+    let mut abort = *strictness == GenerationStrictness::Abort;
+    for (error, span) in errors {
+        let strictness = if error.fatal { GenerationStrictness::Abort } else { *strictness };
+        abort |= error.fatal;
+        let event = GenerationErrorEvent {
+            kind: error.kind,
+            parent_chain: parent_chain(error.parent, &hierarchy),
+            structure: error.structure,
+            source: error.source,
+            entity: error.parent,
+            pass: error.pass,
+        };
+        span.in_scope(|| match strictness {
+            GenerationStrictness::Ignore => {}
+            GenerationStrictness::Warn => warn!(
+                target: ERROR,
+                kind = ?event.kind,
+                subsystem = event.kind.target(),
+                structure = event.structure.as_deref(),
+                parent_chain = %event.parent_chain.join("/"),
+                pass = event.pass,
+                "{}",
+                event.source
+            ),
+            GenerationStrictness::Abort => error!(
+                target: ERROR,
+                kind = ?event.kind,
+                subsystem = event.kind.target(),
+                structure = event.structure.as_deref(),
+                parent_chain = %event.parent_chain.join("/"),
+                pass = event.pass,
+                "{}",
+                event.source
+            ),
+        });
+        if progress.running { progress.errors += 1; }
+        writer.send(event);
+    }
+
+    // Abort: drop the remaining passes and close the run through Completed
+    if abort && progress.running && !progress.aborted {
+        error!(target: ERROR, errors = progress.errors, "aborting generation");
+        progress.aborted = true;
+        highest.0 = cur.0;
+        pending_inpass.0.clear();
+        resolved.0.clear();
+        pending_nests.0.clear();
+        if state.get() != &GenerationState::Completed {
+            next.set(GenerationState::Completed);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use bevy::prelude::*;
+use crate::event_system::event_listeners::CurrentPass;
+use crate::event_system::generation_error::PendingErrors;
+use crate::event_system::generation_report::ReportSamples;
+use crate::spawning::helpers::GenRng;
+
+// Tracing targets used by generation logging, so output can be filtered per subsystem, e.g.
+// `RUST_LOG=proc_gen::path=debug,proc_gen::spawn=trace` (or LogPlugin::filter).
+pub const SPAWN: &str = "proc_gen::spawn";
+pub const PHASE: &str = "proc_gen::phase";
+pub const IMPORT: &str = "proc_gen::import";
+pub const PATH: &str = "proc_gen::path";
+pub const MATERIAL: &str = "proc_gen::material";
+pub const OVERLAP: &str = "proc_gen::overlap";
+pub const ERROR: &str = "proc_gen::error";
+
+// Pass and seed of the current run, attached to listener spans and reported errors. Each of
+// ReportSamples and PendingErrors holds one, so listeners read it from resources they already take
+// and every App keeps its own.
+#[derive(Default)]
+pub struct LogContext {
+    pass: AtomicU8,
+    seed: AtomicU64,
+}
+
+impl LogContext {
+    pub fn pass(&self) -> u8 {
+        self.pass.load(Ordering::Relaxed)
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed.load(Ordering::Relaxed)
+    }
+
+    fn set(&self, pass: u8, seed: u64) {
+        self.pass.store(pass, Ordering::Relaxed);
+        self.seed.store(seed, Ordering::Relaxed);
+    }
+}
+
+// Refresh the span context whenever a pass (or a new run) enters Generating
+pub fn sync_log_context(cur: Res<CurrentPass>, gen_rng: Res<GenRng>, samples: Res<ReportSamples>, errors: Res<PendingErrors>) {
+    samples.context().set(cur.0, gen_rng.seed());
+    errors.context().set(cur.0, gen_rng.seed());
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+    use super::*;
+
+    fn world_at(pass: u8, seed: u64) -> World {
+        let mut world = World::new();
+        world.insert_resource(CurrentPass(pass));
+        world.insert_resource(GenRng::new(seed));
+        world.init_resource::<ReportSamples>();
+        world.init_resource::<PendingErrors>();
+        world.run_system_once(sync_log_context).unwrap();
+        world
+    }
+
+    #[test]
+    fn log_context_is_kept_per_world() {
+        let first = world_at(1, 7);
+        let second = world_at(3, 42);
+        let samples = first.resource::<ReportSamples>().context();
+        assert_eq!((samples.pass(), samples.seed()), (1, 7));
+        assert_eq!(first.resource::<PendingErrors>().context().pass(), 1);
+        let samples = second.resource::<ReportSamples>().context();
+        assert_eq!((samples.pass(), samples.seed()), (3, 42));
+        assert_eq!(second.resource::<PendingErrors>().context().pass(), 3);
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::VecDeque;
+use bevy::prelude::*;
+use crate::core::generation_pipeline::GenerationPipeline;
+use crate::event_system::event_listeners::{CurrentPass, GenerationState, PendingNests, ResolvedPathSpawns};
+use crate::event_system::generation_log::PHASE;
+use crate::event_system::generation_error::{GenerationError, GenerationErrorKind, PendingErrors};
+
+// Marks the entity that procedurally generated content is parented under. Custom phases receive
+// every such entity through PhaseContext::roots.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct GenerationRoot;
+
+// Where a custom phase sits relative to the built-in phases. Phases run once per cycle even when the
+// current pass skips the built-in phase they are anchored to. Nothing runs before Generating, so
+// `Before(GenerationState::Generating)` is rejected at registration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PhaseAnchor {
+    After(GenerationState),
+    Before(GenerationState),
+}
+
+impl PhaseAnchor {
+    // Normalise to the built-in phase this custom phase runs in front of
+    fn precedes(&self) -> GenerationState {
+        match self {
+            PhaseAnchor::After(GenerationState::Generating) => GenerationState::CollisionResolution,
+            PhaseAnchor::After(GenerationState::CollisionResolution) => GenerationState::NavMeshBuilding,
+            PhaseAnchor::After(GenerationState::NavMeshBuilding) => GenerationState::PathResolve,
+            PhaseAnchor::Before(state) => state.clone(),
+            PhaseAnchor::After(_) => GenerationState::Completed,
+        }
+    }
+}
+
+// What a custom phase gets to work with
+#[derive(Debug, Clone)]
+pub struct PhaseContext {
+    // 0-based pass index and its pipeline name
+    pub pass: u8,
+    pub pass_name: String,
+    // Entities marked with GenerationRoot
+    pub roots: Vec<Entity>,
+}
+
+// A downstream generation phase (lighting bake, cover-point extraction, minimap render, ...).
+// Register with `app.add_generation_phase(..)`. While it runs the state is GenerationState::CustomPhase.
+pub trait GenerationPhase: Send + Sync + 'static {
+    fn name(&self) -> &str;
+
+    // Defaults to the end of the cycle, after PathResolve
+    fn anchor(&self) -> PhaseAnchor {
+        PhaseAnchor::Before(GenerationState::Completed)
+    }
+
+    // Names of custom phases with the same anchor that must run before/after this one
+    fn after_phases(&self) -> Vec<String> {
+        Vec::new()
+    }
+    fn before_phases(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    // Called once when the phase starts
+    fn on_enter(&mut self, _world: &mut World, _ctx: &PhaseContext) {}
+
+    // Polled once per frame (starting the frame of on_enter) until it returns true
+    fn is_complete(&mut self, world: &mut World, ctx: &PhaseContext) -> bool;
+}
+
+// Registered custom phases in execution order
+#[derive(Resource, Default)]
+pub struct GenerationPhases {
+    phases: Vec<Box<dyn GenerationPhase>>,
+    order: Vec<usize>,
+}
+
+impl GenerationPhases {
+    pub fn register(&mut self, phase: impl GenerationPhase, errors: &PendingErrors) {
+        if self.phases.iter().any(|p| p.name() == phase.name()) {
+            GenerationError::new(GenerationErrorKind::PhaseRegistration, format!("Generation phase '{}' is already registered; ignoring", phase.name())).report(errors);
+            return;
+        }
+        if phase.anchor() == PhaseAnchor::Before(GenerationState::Generating) {
+            GenerationError::new(GenerationErrorKind::PhaseRegistration, format!("Generation phase '{}' is anchored before Generating, which has no slot; use After(Generating) instead. Ignoring", phase.name())).report(errors);
+            return;
+        }
+        self.phases.push(Box::new(phase));
+        self.order = self.sorted(errors);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.phases.is_empty()
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.order.iter().map(|&i| self.phases[i].name()).collect()
+    }
+
+    fn name_of(&self, index: usize) -> Option<&str> {
+        self.phases.get(index).map(|p| p.name())
+    }
+
+    // Phases to run when the driver moves from `from` to `to`, in order
+    fn between(&self, from: &GenerationState, to: &GenerationState) -> VecDeque<usize> {
+        self.order
+            .iter()
+            .copied()
+            .filter(|&i| {
+                let precedes = self.phases[i].anchor().precedes();
+                &precedes > from && &precedes <= to
+            })
+            .collect()
+    }
+
+    // Group by anchor, then order within each anchor by the before/after names (registration order
+    // breaks ties). Cycles are reported and the remaining phases keep registration order.
+    fn sorted(&self, errors: &PendingErrors) -> Vec<usize> {
+        let mut anchors: Vec<GenerationState> = self.phases.iter().map(|p| p.anchor().precedes()).collect();
+        anchors.sort();
+        anchors.dedup();
+
+        let mut order = Vec::with_capacity(self.phases.len());
+        for anchor in anchors {
+            let group: Vec<usize> = (0..self.phases.len())
+                .filter(|&i| self.phases[i].anchor().precedes() == anchor)
+                .collect();
+            // edges[a] contains b when a must run before b
+            let mut edges: Vec<Vec<usize>> = vec![Vec::new(); self.phases.len()];
+            let position = |name: &str| group.iter().copied().find(|&j| self.phases[j].name() == name);
+            for &i in &group {
+                for name in self.phases[i].after_phases() {
+                    if let Some(j) = position(&name) { edges[j].push(i); }
+                }
+                for name in self.phases[i].before_phases() {
+                    if let Some(j) = position(&name) { edges[i].push(j); }
+                }
+            }
+            let mut remaining = group.clone();
+            while !remaining.is_empty() {
+                let ready = remaining
+                    .iter()
+                    .position(|&i| !remaining.iter().any(|&j| edges[j].contains(&i)));
+                match ready {
+                    Some(k) => order.push(remaining.remove(k)),
+                    None => {
+                        let names: Vec<&str> = remaining.iter().map(|&i| self.phases[i].name()).collect();
+                        GenerationError::new(GenerationErrorKind::PhaseRegistration, format!("Generation phase ordering cycle between {:?}; using registration order", names))
+                            .report(errors);
+                        order.append(&mut remaining);
+                    }
+                }
+            }
+        }
+        order
+    }
+}
+
+pub trait AddGenerationPhase {
+    fn add_generation_phase(&mut self, phase: impl GenerationPhase) -> &mut Self;
+}
+
+impl AddGenerationPhase for App {
+    fn add_generation_phase(&mut self, phase: impl GenerationPhase) -> &mut Self {
+        // May be called before EventSystemPlugin is added; registration errors go out with the first frame's
+        self.init_resource::<PendingErrors>();
+        self.init_resource::<GenerationPhases>();
+        self.world_mut().resource_scope(|world, mut phases: Mut<GenerationPhases>| {
+            phases.register(phase, world.resource::<PendingErrors>());
+        });
+        self
+    }
+}
+
+// Custom phases queued for the current transition and the built-in phase to resume afterwards
+#[derive(Resource, Default)]
+pub struct CustomPhaseQueue {
+    queue: VecDeque<usize>,
+    started: bool,
+    resume: Option<GenerationState>,
+}
+
+impl CustomPhaseQueue {
+    pub fn is_running(&self) -> bool {
+        self.resume.is_some()
+    }
+}
+
+// A new run (or a reset) abandons whatever custom phases were still queued
+pub fn reset_custom_phases(mut queue: ResMut<CustomPhaseQueue>) {
+    *queue = CustomPhaseQueue::default();
+}
+
+// Name of the custom phase currently running, if any
+pub fn active_custom_phase(phases: &GenerationPhases, queue: &CustomPhaseQueue) -> Option<String> {
+    queue.queue.front().and_then(|&i| phases.name_of(i)).map(str::to_string)
+}
+
+// Used by the built-in transitions instead of setting `to` directly: when custom phases sit between
+// `from` and `to` they run first (as CustomPhase) and `to` is entered once they have all completed.
+pub fn route_transition(
+    from: &GenerationState,
+    to: GenerationState,
+    phases: &GenerationPhases,
+    queue: &mut CustomPhaseQueue,
+) -> GenerationState {
+    let pending = phases.between(from, &to);
+    if pending.is_empty() {
+        return to;
+    }
+    queue.queue = pending;
+    queue.started = false;
+    queue.resume = Some(to);
+    GenerationState::CustomPhase
+}
+
+fn phase_context(world: &mut World) -> PhaseContext {
+    let pass = world.resource::<CurrentPass>().0;
+    let pass_name = world.resource::<GenerationPipeline>().pass(pass).name;
+    let roots = world
+        .query_filtered::<Entity, With<GenerationRoot>>()
+        .iter(world)
+        .collect();
+    PhaseContext { pass, pass_name, roots }
+}
+
+// Exclusive driver for GenerationState::CustomPhase: starts and polls the queued phases in order,
+// then resumes the built-in transition that was interrupted.
+pub fn run_custom_phases(world: &mut World) {
+    if world.resource::<State<GenerationState>>().get() != &GenerationState::CustomPhase {
+        return;
+    }
+    let ctx = phase_context(world);
+    world.resource_scope(|world, mut phases: Mut<GenerationPhases>| {
+        world.resource_scope(|world, mut queue: Mut<CustomPhaseQueue>| {
+            while let Some(&index) = queue.queue.front() {
+                let phase = &mut phases.phases[index];
+                if !queue.started {
+                    info!(target: PHASE, phase = phase.name(), pass = %ctx.pass_name, "custom phase started");
+                    phase.on_enter(world, &ctx);
+                    queue.started = true;
+                }
+                if !phase.is_complete(world, &ctx) {
+                    return;
+                }
+                info!(target: PHASE, phase = phase.name(), "custom phase complete");
+                queue.queue.pop_front();
+                queue.started = false;
+            }
+            let mut resume = queue.resume.take().unwrap_or(GenerationState::Completed);
+            // Path spawns and nests a phase buffered are generated before the cycle ends, as PathResolve
+            // does for its fallbacks
+            let buffered = world.get_resource::<ResolvedPathSpawns>().is_some_and(|r| !r.0.is_empty())
+                || world.get_resource::<PendingNests>().is_some_and(|n| !n.0.is_empty());
+            if resume == GenerationState::Completed && buffered {
+                resume = GenerationState::Generating;
+            }
+            debug!(target: PHASE, resume = ?resume, "custom phases complete");
+            world.resource_mut::<NextState<GenerationState>>().set(resume);
+        });
+    });
+}
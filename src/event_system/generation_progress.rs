@@ -0,0 +1,232 @@
+use std::time::Duration;
+use bevy::asset::RecursiveDependencyLoadState;
+use bevy::prelude::*;
+use bevy::state::state::StateTransitionEvent;
+use crate::core::navmesh_config::NavMeshAuthoring;
+use crate::event_system::event_listeners::{CurrentPass, GenerationState, HighestPassIndex, PathResolveCounters, PendingInPass, PendingNests, PendingPathEvents, ResolvedPathSpawns, ResolvedPolylines};
+use crate::core::generation_pipeline::GenerationPipeline;
+use crate::event_system::generation_phases::{active_custom_phase, CustomPhaseQueue, GenerationPhases, GenerationRoot};
+use crate::event_system::generation_report::ExpansionDepth;
+use crate::event_system::overlap_resolution::PendingOverlaps;
+use crate::event_system::work_tickets::{drop_outstanding_work, WorkTickets};
+use crate::spawning::occupancy::OccupancyGrid;
+use crate::event_system::generation_log::PHASE;
+use crate::spawning::helpers::GenRng;
+
+// Public snapshot of generation progress, refreshed every frame. Meant for loading screens and
+// telemetry, so games do not need to read the individual internal resources.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct GenerationProgress {
+    pub running: bool,
+    pub phase: GenerationState,
+    // 1-based current pass and total passes known so far (more may be discovered while generating)
+    pub current_pass: u8,
+    pub total_passes: u8,
+    // Name of the current pass from the GenerationPipeline ("passN" for undeclared passes)
+    pub pass_name: String,
+    // Name of the downstream phase running while phase is CustomPhase
+    pub custom_phase: Option<String>,
+    pub tickets_outstanding: usize,
+    pub tickets_completed: u32,
+    // Object scenes that have been requested but are not fully loaded yet
+    pub assets_loading: usize,
+    pub navmesh_tiles_remaining: usize,
+    pub paths_resolved: u32,
+    pub paths_queued: u32,
+    // GenerationErrorEvents sent during this run, and whether GenerationStrictness::Abort ended it
+    pub errors: u32,
+    pub aborted: bool,
+    pub elapsed: Duration,
+    started_at: Duration,
+}
+
+// Summary attached to GenerationFinished
+#[derive(Debug, Clone, Default)]
+pub struct GenerationStats {
+    pub passes: u8,
+    pub spawn_events: u32,
+    pub paths_resolved: u32,
+    pub paths_queued: u32,
+    pub errors: u32,
+    pub aborted: bool,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct GenerationStarted;
+
+#[derive(Event, Debug, Clone)]
+pub struct PhaseChanged {
+    pub from: GenerationState,
+    pub to: GenerationState,
+    // 1-based pass the new phase belongs to
+    pub pass: u8,
+}
+
+// Stop the run in flight: queued spawn/path work is dropped, the content generated so far is
+// despawned and the state machine goes back to idle (Completed, with nothing left to run).
+// Output means the children of GenerationRoot entities (the roots themselves are kept) and
+// top-level structure containers.
+#[derive(Event, Debug, Clone, Default)]
+pub struct CancelGeneration;
+
+// Sent instead of GenerationFinished when a run was cancelled
+#[derive(Event, Debug, Clone)]
+pub struct GenerationCancelled {
+    // 1-based pass the run was in
+    pub pass: u8,
+    pub elapsed: Duration,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct GenerationFinished {
+    pub duration: Duration,
+    pub stats: GenerationStats,
+}
+
+// On entering Generating outside of a run (app start, or a regeneration after Completed), start a new run
+pub fn start_generation_progress(
+    mut progress: ResMut<GenerationProgress>,
+    mut tickets: ResMut<WorkTickets>,
+    mut path_counters: ResMut<PathResolveCounters>,
+    (mut polylines, mut navmesh): (ResMut<ResolvedPolylines>, ResMut<NavMeshAuthoring>),
+    time: Res<Time<Real>>,
+    mut started: EventWriter<GenerationStarted>,
+) {
+    if progress.running { return; }
+    tickets.reset_completed();
+    *path_counters = PathResolveCounters::default();
+    polylines.0.clear();
+    // StructureKey::NavMeshSettings overrides only last for the run that added them
+    navmesh.overrides.clear();
+    *progress = GenerationProgress {
+        running: true,
+        started_at: time.elapsed(),
+        ..Default::default()
+    };
+    started.send(GenerationStarted);
+}
+
+// On entering Completed for the final pass, close the run. Must run before advance_pass_or_finish
+// moves CurrentPass on.
+pub fn finish_generation_progress(
+    mut progress: ResMut<GenerationProgress>,
+    tickets: Res<WorkTickets>,
+    path_counters: Res<PathResolveCounters>,
+    cur: Res<CurrentPass>,
+    highest: Res<HighestPassIndex>,
+    time: Res<Time<Real>>,
+    mut finished: EventWriter<GenerationFinished>,
+) {
+    if !progress.running || cur.0 < highest.0 { return; }
+    let duration = time.elapsed().saturating_sub(progress.started_at);
+    progress.running = false;
+    progress.phase = GenerationState::Completed;
+    progress.elapsed = duration;
+    finished.send(GenerationFinished {
+        duration,
+        stats: GenerationStats {
+            passes: highest.0.saturating_add(1),
+            spawn_events: tickets.completed(),
+            paths_resolved: path_counters.resolved,
+            paths_queued: path_counters.queued,
+            errors: progress.errors,
+            aborted: progress.aborted,
+        },
+    });
+}
+
+// Forward GenerationState transitions as PhaseChanged events
+pub fn emit_phase_changes(
+    mut transitions: EventReader<StateTransitionEvent<GenerationState>>,
+    (cur, gen_rng): (Res<CurrentPass>, Res<GenRng>),
+    mut changed: EventWriter<PhaseChanged>,
+) {
+    for transition in transitions.read() {
+        if let (Some(from), Some(to)) = (transition.exited.clone(), transition.entered.clone()) {
+            if from != to {
+                info!(target: PHASE, from = ?from, to = ?to, pass = cur.0, seed = gen_rng.seed(), "phase transition");
+                changed.send(PhaseChanged { from, to, pass: cur.0.saturating_add(1) });
+            }
+        }
+    }
+}
+
+pub fn update_generation_progress(
+    mut progress: ResMut<GenerationProgress>,
+    (state, cur, highest, pipeline): (Res<State<GenerationState>>, Res<CurrentPass>, Res<HighestPassIndex>, Res<GenerationPipeline>),
+    (phases, phase_queue): (Res<GenerationPhases>, Res<CustomPhaseQueue>),
+    (tickets, path_counters): (Res<WorkTickets>, Res<PathResolveCounters>),
+    active_tasks: Option<Res<oxidized_navigation::ActiveGenerationTasks>>,
+    (scenes, asset_server): (Query<&SceneRoot>, Res<AssetServer>),
+    time: Res<Time<Real>>,
+) {
+    if !progress.running { return; }
+    progress.phase = state.get().clone();
+    progress.current_pass = cur.0.saturating_add(1);
+    progress.total_passes = highest.0.saturating_add(1);
+    progress.pass_name = pipeline.pass(cur.0).name;
+    progress.custom_phase = active_custom_phase(&phases, &phase_queue);
+    progress.tickets_outstanding = tickets.outstanding();
+    progress.tickets_completed = tickets.completed();
+    progress.assets_loading = scenes
+        .iter()
+        .filter(|scene| matches!(
+            asset_server.get_recursive_dependency_load_state(&scene.0),
+            Some(RecursiveDependencyLoadState::NotLoaded | RecursiveDependencyLoadState::Loading)
+        ))
+        .count();
+    progress.navmesh_tiles_remaining = active_tasks.map(|t| t.len()).unwrap_or(0);
+    progress.paths_resolved = path_counters.resolved;
+    progress.paths_queued = path_counters.queued;
+    progress.elapsed = time.elapsed().saturating_sub(progress.started_at);
+}
+
+// Runs when CancelGeneration is sent
+pub fn cancel_generation(world: &mut World) {
+    let elapsed = {
+        let progress = world.resource::<GenerationProgress>();
+        world.resource::<Time<Real>>().elapsed().saturating_sub(progress.started_at)
+    };
+    let pass = world.resource::<CurrentPass>().0;
+    info!(target: PHASE, pass, "cancelling generation");
+
+    // Queued work: spawn events in flight and the deferred InPass/path buffers
+    drop_outstanding_work(world);
+    world.resource_mut::<PendingInPass>().0.clear();
+    *world.resource_mut::<PendingPathEvents>() = PendingPathEvents::default();
+    world.resource_mut::<ResolvedPathSpawns>().0.clear();
+    world.resource_mut::<PendingNests>().0.clear();
+    world.resource_mut::<ResolvedPolylines>().0.clear();
+    world.resource_mut::<PendingOverlaps>().0.clear();
+    world.resource_mut::<OccupancyGrid>().clear();
+    *world.resource_mut::<CustomPhaseQueue>() = CustomPhaseQueue::default();
+    *world.resource_mut::<PathResolveCounters>() = PathResolveCounters::default();
+
+    // Partial output
+    let mut roots = world.query_filtered::<Entity, With<GenerationRoot>>();
+    let roots: Vec<Entity> = roots.iter(world).collect();
+    for root in roots {
+        if let Ok(mut root) = world.get_entity_mut(root) {
+            root.despawn_descendants();
+        }
+    }
+    let mut top_level = world.query_filtered::<Entity, (With<ExpansionDepth>, Without<Parent>)>();
+    let top_level: Vec<Entity> = top_level.iter(world).collect();
+    for entity in top_level {
+        if let Ok(entity) = world.get_entity_mut(entity) {
+            entity.despawn_recursive();
+        }
+    }
+
+    // Back to idle: entering Completed with pass 0 of 0 neither advances nor reports a finished run
+    world.resource_mut::<CurrentPass>().0 = 0;
+    world.resource_mut::<HighestPassIndex>().0 = 0;
+    let was_running = std::mem::take(&mut world.resource_mut::<GenerationProgress>().running);
+    if world.resource::<State<GenerationState>>().get() != &GenerationState::Completed {
+        world.resource_mut::<NextState<GenerationState>>().set(GenerationState::Completed);
+    }
+    if was_running {
+        world.send_event(GenerationCancelled { pass: pass.saturating_add(1), elapsed });
+    }
+}
+
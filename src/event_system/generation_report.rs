@@ -0,0 +1,258 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use bevy::prelude::*;
+use bevy::state::state::StateTransitionEvent;
+use bevy::utils::tracing::span::EnteredSpan;
+use serde::Serialize;
+use crate::event_system::event_listeners::{GenerationState, PathResolveCounters};
+use crate::event_system::generation_log::{LogContext, SPAWN};
+use crate::event_system::generation_progress::{GenerationFinished, GenerationStarted};
+use crate::event_system::overlap_resolution::OverlapReport;
+use crate::spawning::occupancy::OccupancyGrid;
+
+#[derive(Default)]
+struct SampleData {
+    listeners: HashMap<&'static str, ListenerStats>,
+    exclusivity: ExclusivityStats,
+    path_retries: HashMap<&'static str, u32>,
+}
+
+// Samples recorded by the listeners and the helpers they call, drained into the GenerationReport once
+// per frame by collect_generation_samples. Listeners only need `Res<ReportSamples>`, so recording
+// does not serialise them.
+#[derive(Resource, Default)]
+pub struct ReportSamples(Mutex<SampleData>, LogContext);
+
+impl ReportSamples {
+    fn record_listener(&self, name: &'static str, ms: f64) {
+        if let Ok(mut samples) = self.0.lock() {
+            let stats = samples.listeners.entry(name).or_default();
+            stats.runs += 1;
+            stats.total_ms += ms;
+            stats.max_ms = stats.max_ms.max(ms);
+        }
+    }
+
+    pub fn record_exclusivity(&self, candidates: usize, accepted: usize) {
+        if let Ok(mut samples) = self.0.lock() {
+            samples.exclusivity.calls += 1;
+            samples.exclusivity.candidates += candidates as u64;
+            samples.exclusivity.accepted += accepted as u64;
+        }
+    }
+
+    pub fn record_path_retry(&self, listener: &'static str) {
+        if let Ok(mut samples) = self.0.lock() {
+            *samples.path_retries.entry(listener).or_default() += 1;
+        }
+    }
+
+    // Pass and seed the listener spans are tagged with
+    pub fn context(&self) -> &LogContext {
+        &self.1
+    }
+
+    fn take(&self) -> SampleData {
+        self.0.lock().map(|mut samples| std::mem::take(&mut *samples)).unwrap_or_default()
+    }
+}
+
+// Nesting level of a structure container, used to report how deep expansion went
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ExpansionDepth(pub u32);
+
+// Depth for a structure spawned under `parent`: one below the nearest ancestor structure
+pub fn expansion_depth(parent: Option<Entity>, hierarchy: &Query<(Option<&ExpansionDepth>, Option<&Parent>)>) -> u32 {
+    let mut current = parent;
+    while let Some(entity) = current {
+        let Ok((depth, parent)) = hierarchy.get(entity) else { break; };
+        if let Some(depth) = depth {
+            return depth.0 + 1;
+        }
+        current = parent.map(|p| p.get());
+    }
+    1
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct StructureStats {
+    pub instances: u32,
+    // Deepest nesting level this structure was expanded at (top-level structures are depth 1)
+    pub max_depth: u32,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct ListenerStats {
+    // Frames the listener ran in and total time spent in it
+    pub runs: u32,
+    pub total_ms: f64,
+    pub max_ms: f64,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct ExclusivityStats {
+    // filter_by_exclusivity calls, the noise samples they were given and the points they kept
+    pub calls: u32,
+    pub candidates: u64,
+    pub accepted: u64,
+}
+
+// Statistics of the last (or current) generation run. Serializable so it can be written out after
+// Completed, e.g. `report.write_to("gen_report.ron")`.
+#[derive(Resource, Serialize, Debug, Clone, Default)]
+pub struct GenerationReport {
+    // Whether the run has finished; the report keeps filling in while false
+    pub complete: bool,
+    pub total_ms: f64,
+    pub structures: BTreeMap<String, StructureStats>,
+    pub max_expansion_depth: u32,
+    pub listeners: BTreeMap<String, ListenerStats>,
+    // Time spent in each phase, summed over all passes
+    pub phases_ms: BTreeMap<String, f64>,
+    pub navmesh_build_ms: f64,
+    pub exclusivity: ExclusivityStats,
+    // "Try again next frame" requeues per path listener
+    pub path_retries: BTreeMap<String, u32>,
+    pub paths_queued: u32,
+    pub paths_resolved: u32,
+    // Entities removed or moved by overlap resolution, and placements rejected by the occupancy grid
+    pub priority_despawns: u32,
+    pub overlap_moves: u32,
+    pub occupancy_rejections: u32,
+    // Remedies the connectivity check applied, and the entities it still found cut off (by name)
+    pub connectivity_remedies: u32,
+    pub isolated: Vec<String>,
+    // GenerationErrorEvents sent during the run
+    pub errors: u32,
+    pub aborted: bool,
+}
+
+impl GenerationReport {
+    pub fn record_structure(&mut self, name: &str, depth: u32) {
+        let stats = self.structures.entry(name.to_string()).or_default();
+        stats.instances += 1;
+        stats.max_depth = stats.max_depth.max(depth);
+        self.max_expansion_depth = self.max_expansion_depth.max(depth);
+    }
+
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    // Writes JSON for a .json path and RON otherwise
+    pub fn write_to(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let text = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => self.to_json().map_err(|e| e.to_string())?,
+            _ => self.to_ron().map_err(|e| e.to_string())?,
+        };
+        std::fs::write(path, text).map_err(|e| format!("Failed to write report to {}: {}", path.display(), e))
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+// Times a listener from creation until drop: `let _timer = ListenerTimer::start("mesh_spawn_listener", &samples);`
+// While alive it also keeps a `listener` tracing span entered, carrying the pass and seed.
+pub struct ListenerTimer<'a> {
+    name: &'static str,
+    started: Instant,
+    samples: &'a ReportSamples,
+    _span: EnteredSpan,
+}
+
+impl<'a> ListenerTimer<'a> {
+    pub fn start(name: &'static str, samples: &'a ReportSamples) -> Self {
+        let span = info_span!(target: SPAWN, "listener", name, pass = samples.context().pass(), seed = samples.context().seed());
+        ListenerTimer { name, started: Instant::now(), samples, _span: span.entered() }
+    }
+}
+
+impl Drop for ListenerTimer<'_> {
+    fn drop(&mut self) {
+        self.samples.record_listener(self.name, millis(self.started.elapsed()));
+    }
+}
+
+// Start a fresh report with each run, dropping samples recorded in between runs
+pub fn reset_generation_report(
+    mut report: ResMut<GenerationReport>,
+    mut started: EventReader<GenerationStarted>,
+    mut phase_clock: ResMut<PhaseClock>,
+    time: Res<Time<Real>>,
+    samples: Res<ReportSamples>,
+) {
+    if started.read().count() == 0 { return; }
+    *report = GenerationReport::default();
+    phase_clock.entered = Some(time.elapsed());
+    samples.take();
+}
+
+pub fn collect_generation_samples(mut report: ResMut<GenerationReport>, samples: Res<ReportSamples>) {
+    if report.complete { return; }
+    let samples = samples.take();
+    for (name, stats) in samples.listeners {
+        let total = report.listeners.entry(name.to_string()).or_default();
+        total.runs += stats.runs;
+        total.total_ms += stats.total_ms;
+        total.max_ms = total.max_ms.max(stats.max_ms);
+    }
+    report.exclusivity.calls += samples.exclusivity.calls;
+    report.exclusivity.candidates += samples.exclusivity.candidates;
+    report.exclusivity.accepted += samples.exclusivity.accepted;
+    for (name, count) in samples.path_retries {
+        *report.path_retries.entry(name.to_string()).or_default() += count;
+    }
+}
+
+// When the current phase was entered
+#[derive(Resource, Default)]
+pub struct PhaseClock {
+    entered: Option<Duration>,
+}
+
+pub fn record_phase_times(
+    mut transitions: EventReader<StateTransitionEvent<GenerationState>>,
+    mut report: ResMut<GenerationReport>,
+    mut phase_clock: ResMut<PhaseClock>,
+    time: Res<Time<Real>>,
+) {
+    for transition in transitions.read() {
+        let now = time.elapsed();
+        if let (Some(from), Some(entered)) = (transition.exited.clone(), phase_clock.entered) {
+            let ms = millis(now.saturating_sub(entered));
+            *report.phases_ms.entry(format!("{:?}", from)).or_default() += ms;
+            if from == GenerationState::NavMeshBuilding {
+                report.navmesh_build_ms += ms;
+            }
+        }
+        phase_clock.entered = Some(now);
+    }
+}
+
+pub fn finish_generation_report(
+    mut finished: EventReader<GenerationFinished>,
+    mut report: ResMut<GenerationReport>,
+    overlaps: Res<OverlapReport>,
+    occupancy: Res<OccupancyGrid>,
+    path_counters: Res<PathResolveCounters>,
+) {
+    let Some(event) = finished.read().last() else { return; };
+    report.total_ms = millis(event.duration);
+    report.paths_queued = path_counters.queued;
+    report.paths_resolved = path_counters.resolved;
+    report.priority_despawns = overlaps.removed().count() as u32;
+    report.overlap_moves = overlaps.moved().count() as u32;
+    report.occupancy_rejections = occupancy.rejected;
+    report.errors = event.stats.errors;
+    report.aborted = event.stats.aborted;
+    report.complete = true;
+}
@@ -1,5 +1,15 @@
 pub mod event_listeners;
 pub mod spawn_events;
+pub mod work_tickets;
+pub mod generation_progress;
+pub mod generation_phases;
+pub mod generation_report;
+pub mod generation_log;
+pub mod generation_error;
+pub mod generation_budget;
+pub mod path_failure;
+pub mod overlap_resolution;
+pub mod connectivity_check;
 pub mod spawn_macro;
 pub mod event_system_plugin;
 pub mod spawnables;
@@ -0,0 +1,293 @@
+use std::collections::HashSet;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::{CollisionEvent, ContactForceEvent, DefaultRapierContext, RapierContext};
+use rand::Rng;
+use crate::core::collider::{ColliderPriority, OverlapResolution, OverlapStrategy};
+use crate::core::rand_data::RandData;
+use crate::event_system::generation_log::OVERLAP;
+use crate::spawning::euler_transform::EulerTransform;
+use crate::spawning::helpers::{jiggle_transform, GenRng};
+
+
+// How many times a Resample object may ask its spawner for a new point before it is removed
+const RESAMPLE_ATTEMPTS: u8 = 4;
+// Extra distance added to every nudge so the pair ends up separated rather than touching
+const NUDGE_MARGIN: f32 = 0.01;
+
+// The random placement an object was spawned with, kept on the container that Rand/RandDistDir
+// produce so Resample can draw a new point from the same distribution.
+#[derive(Component, Debug, Clone)]
+pub enum SpawnSampler {
+    Rand {
+        rand: RandData,
+        transform: EulerTransform,
+    },
+    DistDir {
+        dist_min: f32,
+        dist_max: f32,
+        angle_min_deg: f32,
+        angle_max_deg: f32,
+        y: f32,
+        transform: EulerTransform,
+    },
+}
+
+impl SpawnSampler {
+    pub fn sample(&self, gen_rng: &mut ResMut<GenRng>) -> EulerTransform {
+        match self {
+            SpawnSampler::Rand { rand, transform } => jiggle_transform(gen_rng, rand.clone(), transform.clone()),
+            SpawnSampler::DistDir { dist_min, dist_max, angle_min_deg, angle_max_deg, y, transform } => {
+                // Sample angle in degrees and distance uniformly, then offset the base transform
+                let angle_rad = gen_rng.rng_mut().gen_range(*angle_min_deg..=*angle_max_deg).to_radians();
+                let dist = gen_rng.rng_mut().gen_range(*dist_min..=*dist_max);
+                let mut euler = transform.clone();
+                euler.translation = (
+                    euler.translation.0 + angle_rad.cos() * dist,
+                    euler.translation.1 + y,
+                    euler.translation.2 + angle_rad.sin() * dist,
+                );
+                euler
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OverlapAction {
+    Removed,
+    // Translations are in the moved entity's parent space
+    Moved { from: Vec3, to: Vec3 },
+    // `container` is the spawner container that received the new point
+    Resampled { container: Entity, from: Vec3, to: Vec3 },
+    Kept,
+}
+
+#[derive(Debug, Clone)]
+pub struct OverlapRecord {
+    // The lower-priority object and the one it overlapped
+    pub entity: Entity,
+    pub name: Option<String>,
+    pub against: Entity,
+    pub action: OverlapAction,
+}
+
+// Every overlap resolved during the current generation run, in the order they were handled
+#[derive(Resource, Default, Debug)]
+pub struct OverlapReport {
+    pub records: Vec<OverlapRecord>,
+}
+
+impl OverlapReport {
+    pub fn removed(&self) -> impl Iterator<Item = &OverlapRecord> {
+        self.records.iter().filter(|r| r.action == OverlapAction::Removed)
+    }
+
+    pub fn moved(&self) -> impl Iterator<Item = &OverlapRecord> {
+        self.records
+            .iter()
+            .filter(|r| matches!(r.action, OverlapAction::Moved { .. } | OverlapAction::Resampled { .. }))
+    }
+}
+
+// Nudges/resamples already spent by an object (or by the spawner container that was resampled)
+#[derive(Component, Default, Debug)]
+pub struct OverlapAttempts {
+    pub attempts: u8,
+    pub distance: f32,
+}
+
+// Pairs that were moved apart and are checked again next frame: rapier does not report a new
+// contact when a moved object still overlaps the same collider.
+#[derive(Resource, Default)]
+pub struct PendingOverlaps(pub Vec<(Entity, Entity)>);
+
+impl PendingOverlaps {
+    pub fn is_settled(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+pub fn clear_overlap_report(mut report: ResMut<OverlapReport>, mut pending: ResMut<PendingOverlaps>) {
+    report.records.clear();
+    pending.0.clear();
+}
+
+// Resolve contacts between colliders with ColliderPriority: the lower-priority side is handled with
+// its OverlapStrategy (Despawn when it has none). Equal priorities are left alone.
+pub fn resolve_collider_overlaps(
+    mut commands: Commands,
+    (mut contact_events, mut collision_events): (EventReader<ContactForceEvent>, EventReader<CollisionEvent>),
+    (priorities, strategies, name_query): (Query<&ColliderPriority>, Query<&OverlapStrategy>, Query<&Name>),
+    rapier: Query<&RapierContext, With<DefaultRapierContext>>,
+    (mut transforms, global_transforms, parents): (Query<&mut Transform>, Query<&GlobalTransform>, Query<&Parent>),
+    (samplers, mut attempts): (Query<&SpawnSampler>, Query<&mut OverlapAttempts>),
+    (mut gen_rng, mut report, mut pending): (ResMut<GenRng>, ResMut<OverlapReport>, ResMut<PendingOverlaps>),
+) {
+    let context = rapier.get_single().ok();
+
+    // Contact force events, collision start events (more reliable for kinematic bodies), and pairs
+    // moved last frame that may still be penetrating
+    let mut pairs: Vec<(Entity, Entity)> = contact_events.read().map(|ev| (ev.collider1, ev.collider2)).collect();
+    for ev in collision_events.read() {
+        if let CollisionEvent::Started(a, b, _) = ev {
+            pairs.push((*a, *b));
+        }
+    }
+    for (loser, winner) in pending.0.drain(..) {
+        if context.and_then(|c| penetration(c, loser, winner)).is_some() {
+            pairs.push((loser, winner));
+        }
+    }
+
+    // Each object is resolved at most once per frame
+    let mut handled: HashSet<Entity> = HashSet::new();
+    for (a, b) in pairs {
+        let (Ok(pa), Ok(pb)) = (priorities.get(a), priorities.get(b)) else { continue; };
+        trace!(target: OVERLAP, a = ?a, a_priority = pa.0, b = ?b, b_priority = pb.0, "contact");
+        if pa.0 == pb.0 { continue; }
+        let (loser, winner) = if pa.0 < pb.0 { (a, b) } else { (b, a) };
+        if !handled.insert(loser) { continue; }
+
+        let strategy = strategies.get(loser).map(|s| s.0.clone()).unwrap_or_default();
+        let name = name_query.get(loser).ok().map(|n| n.as_str().to_string());
+        let action = match strategy {
+            OverlapResolution::Despawn => OverlapAction::Removed,
+            OverlapResolution::Keep => OverlapAction::Kept,
+            OverlapResolution::Nudge { max_distance, max_attempts } => {
+                // Only actual penetration is pushed out; touching contacts (or contacts that cannot be
+                // measured without a physics context) are left as they are
+                match context.and_then(|c| penetration(c, loser, winner)) {
+                    None => OverlapAction::Kept,
+                    Some(push) => {
+                        let spent = attempts.get(loser).ok();
+                        let distance = push.length();
+                        match transforms.get_mut(loser) {
+                            Ok(mut transform) if nudge_within_budget(spent, distance, max_distance, max_attempts) => {
+                                let local = world_to_parent_space(loser, push, &parents, &global_transforms);
+                                let from = transform.translation;
+                                transform.translation += local;
+                                let (spent_attempts, spent_distance) = spent.map_or((0, 0.0), |a| (a.attempts, a.distance));
+                                commands.entity(loser).insert(OverlapAttempts { attempts: spent_attempts + 1, distance: spent_distance + distance });
+                                pending.0.push((loser, winner));
+                                OverlapAction::Moved { from, to: transform.translation }
+                            }
+                            // Over budget, or nothing to move
+                            _ => OverlapAction::Removed,
+                        }
+                    }
+                }
+            }
+            OverlapResolution::Resample => {
+                match find_sampler(loser, &samplers, &parents) {
+                    Some(container) => {
+                        let spent = attempts.get(container).map(|a| a.attempts).unwrap_or(0);
+                        let transform = transforms.get_mut(container).ok();
+                        if let (Some(mut transform), true) = (transform, spent < RESAMPLE_ATTEMPTS) {
+                            let from = transform.translation;
+                            let sampled = samplers.get(container).unwrap().sample(&mut gen_rng);
+                            *transform = Transform::from(sampled);
+                            match attempts.get_mut(container) {
+                                Ok(mut a) => a.attempts += 1,
+                                Err(_) => { commands.entity(container).insert(OverlapAttempts { attempts: 1, distance: 0.0 }); }
+                            }
+                            pending.0.push((loser, winner));
+                            OverlapAction::Resampled { container, from, to: transform.translation }
+                        } else {
+                            OverlapAction::Removed
+                        }
+                    }
+                    None => OverlapAction::Removed,
+                }
+            }
+        };
+
+        debug!(target: OVERLAP, entity = ?loser, name = name.as_deref(), against = ?winner, action = ?action, "overlap resolved");
+        if action == OverlapAction::Removed {
+            commands.queue(move |world: &mut World| {
+                if let Ok(ent) = world.get_entity_mut(loser) {
+                    ent.despawn_recursive();
+                }
+            });
+        }
+        report.records.push(OverlapRecord { entity: loser, name, against: winner, action });
+    }
+}
+
+// Whether a nudge of `distance` stays within the strategy's attempt and total distance budget
+fn nudge_within_budget(spent: Option<&OverlapAttempts>, distance: f32, max_distance: f32, max_attempts: u8) -> bool {
+    let (attempts, moved) = spent.map_or((0, 0.0), |a| (a.attempts, a.distance));
+    attempts < max_attempts && moved + distance <= max_distance
+}
+
+// World-space vector that moves `loser` out of `winner`, or None when they are not penetrating.
+// The push is kept horizontal so ground-placed objects stay on the ground.
+fn penetration(context: &RapierContext, loser: Entity, winner: Entity) -> Option<Vec3> {
+    let pair = context.contact_pair(loser, winner)?;
+    let (manifold, contact) = pair.find_deepest_contact()?;
+    if contact.dist() >= 0.0 {
+        return None;
+    }
+    // The manifold normal points from collider1 towards collider2
+    let normal = if pair.collider1() == loser { -manifold.normal() } else { manifold.normal() };
+    let horizontal = Vec3::new(normal.x, 0.0, normal.z).try_normalize().unwrap_or(Vec3::X);
+    Some(horizontal * (-contact.dist() + NUDGE_MARGIN))
+}
+
+fn world_to_parent_space(
+    entity: Entity,
+    world_delta: Vec3,
+    parents: &Query<&Parent>,
+    global_transforms: &Query<&GlobalTransform>,
+) -> Vec3 {
+    parents
+        .get(entity)
+        .ok()
+        .and_then(|parent| global_transforms.get(parent.get()).ok())
+        .map(|global| global.affine().inverse().transform_vector3(world_delta))
+        .unwrap_or(world_delta)
+}
+
+// Nearest ancestor (or the entity itself) that was placed by a sampling spawner
+fn find_sampler(entity: Entity, samplers: &Query<&SpawnSampler>, parents: &Query<&Parent>) -> Option<Entity> {
+    let mut current = entity;
+    loop {
+        if samplers.contains(current) {
+            return Some(current);
+        }
+        current = parents.get(current).ok()?.get();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn nudge_budget_counts_attempts_and_distance() {
+        assert!(nudge_within_budget(None, 0.5, 1.0, 2));
+        assert!(!nudge_within_budget(None, 1.5, 1.0, 2), "a single push past the distance budget");
+        let spent = OverlapAttempts { attempts: 1, distance: 0.6 };
+        assert!(nudge_within_budget(Some(&spent), 0.4, 1.0, 2), "exactly at the distance budget");
+        assert!(!nudge_within_budget(Some(&spent), 0.5, 1.0, 2), "the total exceeds the distance budget");
+        let exhausted = OverlapAttempts { attempts: 2, distance: 0.1 };
+        assert!(!nudge_within_budget(Some(&exhausted), 0.1, 1.0, 2), "out of attempts");
+        assert!(!nudge_within_budget(None, 0.1, 1.0, 0), "nudging disabled");
+    }
+
+    #[test]
+    fn find_sampler_walks_up_to_the_sampled_container() {
+        let mut world = World::new();
+        let sampler = SpawnSampler::Rand { rand: RandData::Linear(1.0), transform: EulerTransform::default() };
+        let container = world.spawn(sampler).id();
+        let object = world.spawn_empty().set_parent(container).id();
+        let part = world.spawn_empty().set_parent(object).id();
+        let unsampled = world.spawn_empty().id();
+        let unsampled_child = world.spawn_empty().set_parent(unsampled).id();
+
+        let found = world.run_system_once(move |samplers: Query<&SpawnSampler>, parents: Query<&Parent>| {
+            [container, object, part, unsampled, unsampled_child].map(|e| find_sampler(e, &samplers, &parents))
+        }).unwrap();
+        assert_eq!(found, [Some(container), Some(container), Some(container), None, None]);
+    }
+}
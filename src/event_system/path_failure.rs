@@ -0,0 +1,287 @@
+use bevy::ecs::system::SystemState;
+use bevy::prelude::*;
+use crate::core::path_fallback::{PathFallback, DEFAULT_PATH_RETRIES};
+use crate::core::structure_reference::StructureReference;
+use crate::core::tags::Tags;
+use crate::spawning::euler_transform::EulerTransform;
+use crate::event_system::event_listeners::{CurrentPass, HighestPassIndex, PendingNests, ResolvedPathSpawns};
+use crate::event_system::generation_error::{GenerationError, GenerationErrorKind, PendingErrors};
+use crate::event_system::generation_log::PATH;
+use crate::event_system::generation_report::ReportSamples;
+use crate::event_system::spawn_events::{NestSpawnEvent, PathSpawnEvent, PathToAllTagsSpawnEvent, PathToTagSpawnEvent};
+use crate::event_system::work_tickets::{send_tracked, WorkTickets};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathFailureReason {
+    // No entity carries the requested tag
+    NoTaggedEntity,
+    // The navmesh was not built (or still baking) for the whole retry budget
+    NavMeshUnavailable,
+    // Neither the start nor any probe around it has a navmesh path to the target
+    NoPath,
+    // Still unresolved when PathResolve timed out, e.g. waiting on a navmesh that never finished baking
+    TimedOut,
+}
+
+// Sent when a PathToTag/PathToAllTags request gives up, right before its fallback is applied
+#[derive(Event, Debug, Clone)]
+pub struct PathResolutionFailed {
+    pub tag: String,
+    // World-space start of the path
+    pub start: Vec3,
+    pub reason: PathFailureReason,
+}
+
+// Where and why a request failed; `end` is the world-space target when one was found
+pub struct PathFailure {
+    pub reason: PathFailureReason,
+    pub start: Vec3,
+    pub end: Option<Vec3>,
+}
+
+// The parts of PathToTagSpawnEvent and PathToAllTagsSpawnEvent that retrying and falling back need
+pub trait PathRequest: Event + Clone {
+    fn tag(&self) -> &str;
+    // Start of the path, local to the request's transform
+    fn start(&self) -> Vec3;
+    fn transform(&self) -> &EulerTransform;
+    fn retries(&self) -> u32;
+    fn retries_mut(&mut self) -> &mut u32;
+    fn retry_budget(&self) -> u32;
+    fn fallback(&self) -> &PathFallback;
+    fn reference(&self) -> &StructureReference;
+    fn parent(&self) -> Option<Entity>;
+    // Whether the request paths to every tagged entity rather than the nearest one
+    fn every_target(&self) -> bool;
+    // PathSpawnEvent along `points` (local to the request's transform) with the request's spread settings
+    fn path_spawn(&self, points: Vec<Vec3>) -> PathSpawnEvent;
+    // NestSpawnEvent for `reference` at the request's transform
+    fn nest(&self, reference: StructureReference) -> NestSpawnEvent;
+}
+
+macro_rules! impl_path_request {
+    ($event:ty, $every_target:expr) => {
+        impl PathRequest for $event {
+            fn tag(&self) -> &str { &self.tag }
+            fn start(&self) -> Vec3 { self.start }
+            fn transform(&self) -> &EulerTransform { &self.transform }
+            fn retries(&self) -> u32 { self.retries }
+            fn retries_mut(&mut self) -> &mut u32 { &mut self.retries }
+            fn retry_budget(&self) -> u32 { self.max_retries.unwrap_or(DEFAULT_PATH_RETRIES) }
+            fn fallback(&self) -> &PathFallback { &self.fallback }
+            fn reference(&self) -> &StructureReference { &self.reference }
+            fn parent(&self) -> Option<Entity> { self.parent }
+            fn every_target(&self) -> bool { $every_target }
+            fn path_spawn(&self, points: Vec<Vec3>) -> PathSpawnEvent {
+                PathSpawnEvent {
+                    reference: self.reference.clone(),
+                    points,
+                    tension: self.tension,
+                    spread: self.spread.clone(),
+                    count: self.count,
+                    // A straight line has nothing to shape
+                    shaping: None,
+                    placement: self.placement.clone(),
+                    transform: self.transform.clone(),
+                    parent: self.parent,
+                }
+            }
+            fn nest(&self, reference: StructureReference) -> NestSpawnEvent {
+                NestSpawnEvent { reference, transform: self.transform.clone(), parent: self.parent, sampler: None, claimed: None }
+            }
+        }
+    };
+}
+
+impl_path_request!(PathToTagSpawnEvent, false);
+impl_path_request!(PathToAllTagsSpawnEvent, true);
+
+// Re-send the request for another try next frame. Returns false once its retry budget is spent,
+// in which case the caller gives up through fail_path_request.
+pub fn retry_path_request<E: PathRequest>(commands: &mut Commands, event: &E, listener: &'static str) -> bool {
+    if event.retries() >= event.retry_budget() {
+        return false;
+    }
+    let mut ev = event.clone();
+    *ev.retries_mut() += 1;
+    commands.queue(move |world: &mut World| {
+        if let Some(samples) = world.get_resource::<ReportSamples>() {
+            samples.record_path_retry(listener);
+        }
+        send_tracked(world, ev);
+    });
+    true
+}
+
+// Re-send the request for next frame without spending a retry, for requests waiting on a navmesh that
+// is still baking. PathResolve's timeout bounds how long this can go on.
+pub fn defer_path_request<E: PathRequest>(commands: &mut Commands, event: &E) {
+    let ev = event.clone();
+    commands.queue(move |world: &mut World| { send_tracked(world, ev); });
+}
+
+// Give up on a request: send PathResolutionFailed and apply its fallback. Returns true when the
+// fallback buffered spawns for the next Generating pass.
+pub fn fail_path_request<E: PathRequest>(
+    commands: &mut Commands,
+    event: &E,
+    failure: PathFailure,
+    world_tf: &Transform,
+    resolved: &mut ResolvedPathSpawns,
+    pending_nests: &mut PendingNests,
+    errors: &PendingErrors,
+) -> bool {
+    let failed = PathResolutionFailed { tag: event.tag().to_string(), start: failure.start, reason: failure.reason };
+    commands.queue(move |world: &mut World| { world.send_event(failed); });
+
+    match (event.fallback(), failure.end) {
+        (PathFallback::StraightLine, Some(end)) => {
+            let inv_world = world_tf.compute_matrix().inverse();
+            let points = vec![inv_world.transform_point3(failure.start), inv_world.transform_point3(end)];
+            debug!(target: PATH, tag = event.tag(), reason = ?failure.reason, "path unresolved; falling back to a straight line");
+            resolved.0.push(event.path_spawn(points));
+            true
+        }
+        (PathFallback::SpawnAlternate(alternate), _) => {
+            debug!(target: PATH, tag = event.tag(), reason = ?failure.reason, alternate = alternate.name(), "path unresolved; spawning alternate");
+            pending_nests.0.push(event.nest(alternate.clone()));
+            true
+        }
+        // Skip, or a straight line without a target to draw it to
+        _ => {
+            GenerationError::new(
+                GenerationErrorKind::PathUnresolved,
+                format!("no path to tag '{}' ({:?}) after {} retries", event.tag(), failure.reason, event.retries()),
+            )
+            .with_structure(event.reference().name())
+            .with_parent(event.parent())
+            .report(errors);
+            false
+        }
+    }
+}
+
+// World positions a request gives up on: the tagged entity nearest its start, or every tagged
+// entity for a request that paths to all of them
+fn tagged_targets<E: PathRequest>(request: &E, start: Vec3, tagged: &Query<(&GlobalTransform, &Tags)>) -> Vec<Vec3> {
+    let positions = tagged.iter()
+        .filter(|(_, tags)| tags.0.iter().any(|t| t == request.tag()))
+        .map(|(gt, _)| gt.translation());
+    if request.every_target() {
+        return positions.collect();
+    }
+    // Compare with the start raised to each candidate's height, as PathToTag does
+    positions
+        .min_by(|a, b| a.distance_squared(start.with_y(a.y)).total_cmp(&b.distance_squared(start.with_y(b.y))))
+        .into_iter()
+        .collect()
+}
+
+type TimeOutParams<'w, 's> = (
+    Commands<'w, 's>,
+    (Query<'w, 's, (&'static GlobalTransform, &'static Tags)>, Query<'w, 's, &'static GlobalTransform>),
+    (ResMut<'w, ResolvedPathSpawns>, ResMut<'w, PendingNests>, Res<'w, PendingErrors>),
+    (Option<Res<'w, CurrentPass>>, ResMut<'w, HighestPassIndex>),
+);
+
+// Give up on the requests of type E still outstanding when PathResolve times out: each one is failed
+// through fail_path_request with PathFailureReason::TimedOut, so its fallback still applies, and the
+// queue is cleared so none is handled later. Returns how many were given up on.
+pub fn time_out_path_requests<E: PathRequest>(world: &mut World) -> usize {
+    let Some(ids) = world.get_resource_mut::<WorkTickets>().map(|mut tickets| tickets.take_outstanding::<E>()) else { return 0; };
+    let Some(mut events) = world.get_resource_mut::<Events<E>>() else { return 0; };
+    let requests: Vec<E> = ids.iter().filter_map(|&id| events.get_event(id).map(|(event, _)| event.clone())).collect();
+    events.clear();
+
+    let mut state = SystemState::<TimeOutParams>::new(world);
+    let (mut commands, (tagged, transforms), (mut resolved, mut pending_nests, errors), (cur_pass, mut highest)) = state.get_mut(world);
+    for request in requests.iter() {
+        let local = Transform::from(request.transform().clone());
+        let world_tf = match request.parent().and_then(|p| transforms.get(p).ok()) {
+            Some(parent_gt) => parent_gt.compute_transform() * local,
+            None => local,
+        };
+        let start = world_tf.translation + world_tf.rotation * request.start();
+        let targets = tagged_targets(request, start, &tagged);
+        let mut buffered = false;
+        if targets.is_empty() {
+            let failure = PathFailure { reason: PathFailureReason::TimedOut, start, end: None };
+            buffered |= fail_path_request(&mut commands, request, failure, &world_tf, &mut resolved, &mut pending_nests, &errors);
+        }
+        // One failure per target, so a StraightLine fallback draws a line to each
+        for end in targets {
+            let failure = PathFailure { reason: PathFailureReason::TimedOut, start: start.with_y(end.y + 0.05), end: Some(end) };
+            buffered |= fail_path_request(&mut commands, request, failure, &world_tf, &mut resolved, &mut pending_nests, &errors);
+        }
+        if buffered {
+            if let Some(cp) = cur_pass.as_ref() { if highest.0 < cp.0.saturating_add(1) { highest.0 = cp.0.saturating_add(1); } }
+        }
+    }
+    state.apply(world);
+    requests.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::spread_data::SpreadData;
+    use crate::spawning::object_logic::Ownership;
+    use super::*;
+
+    fn request(fallback: PathFallback) -> PathToTagSpawnEvent {
+        PathToTagSpawnEvent {
+            reference: StructureReference::Ref { structure: "road.ron".to_string(), ownership: Ownership::Inherit },
+            start: Vec3::ZERO,
+            manual_points: None,
+            tag: "gate".to_string(),
+            tension: 0.0,
+            spread: SpreadData::Regular,
+            count: 4,
+            wobble: None,
+            store_as: None,
+            max_retries: None,
+            fallback,
+            cost: None,
+            shaping: None,
+            placement: None,
+            agent: None,
+            retries: 0,
+            transform: EulerTransform::default(),
+            parent: None,
+        }
+    }
+
+    fn world_with(request: PathToTagSpawnEvent) -> World {
+        let mut world = World::new();
+        world.init_resource::<Events<PathToTagSpawnEvent>>();
+        world.init_resource::<Events<PathResolutionFailed>>();
+        world.init_resource::<ResolvedPathSpawns>();
+        world.init_resource::<PendingNests>();
+        world.init_resource::<PendingErrors>();
+        world.init_resource::<HighestPassIndex>();
+        world.insert_resource(CurrentPass(0));
+        world.spawn((GlobalTransform::from_translation(Vec3::new(8.0, 0.0, 0.0)), Tags(vec!["gate".to_string()])));
+        send_tracked(&mut world, request);
+        world
+    }
+
+    #[test]
+    fn timed_out_request_draws_its_straight_line_fallback() {
+        let mut world = world_with(request(PathFallback::StraightLine));
+        assert_eq!(time_out_path_requests::<PathToTagSpawnEvent>(&mut world), 1);
+        let resolved = world.resource::<ResolvedPathSpawns>();
+        assert_eq!(resolved.0.len(), 1);
+        assert!(resolved.0[0].points[1].distance(Vec3::new(8.0, 0.0, 0.0)) < 1e-4);
+        assert_eq!(world.resource::<HighestPassIndex>().0, 1);
+        assert!(world.resource::<PendingErrors>().is_empty());
+    }
+
+    #[test]
+    fn timed_out_request_spawns_its_alternate() {
+        let alternate = StructureReference::Ref { structure: "sign.ron".to_string(), ownership: Ownership::Inherit };
+        let mut world = world_with(request(PathFallback::SpawnAlternate(alternate)));
+        time_out_path_requests::<PathToTagSpawnEvent>(&mut world);
+        assert_eq!(world.resource::<PendingNests>().0.len(), 1);
+        assert_eq!(world.resource::<Events<PathResolutionFailed>>().len(), 1);
+        assert_eq!(world.resource::<WorkTickets>().outstanding_of::<PathToTagSpawnEvent>(), 0);
+    }
+}
@@ -1,4 +1,7 @@
 use bevy::prelude::*;
+use crate::core::collider::{ColliderType, OverlapResolution};
+use crate::event_system::overlap_resolution::SpawnSampler;
+use crate::spawning::occupancy::OccupancyClaimed;
 use crate::core::fbm_data::FBMData;
 use crate::core::rand_data::RandData;
 use crate::core::sample_size::SampleSize;
@@ -7,6 +10,12 @@ use crate::core::structure_reference::StructureReference;
 use crate::spawning::euler_transform::EulerTransform;
 use crate::core::tmaterial::TMaterial;
 use crate::core::wobble::WobbleParams;
+use crate::core::path_fallback::PathFallback;
+use crate::core::path_cost::PathCost;
+use crate::core::path_shaping::PathShaping;
+use crate::core::path_placement::PathPlacement;
+use crate::core::navmesh_config::{NavAreaType, NavMeshConfig};
+use crate::core::primitive_shape::PrimitiveShape;
 use crate::core::structure_key::StructureKey;
 
 #[derive(Debug, Clone, Event)]
@@ -14,6 +23,11 @@ pub struct MeshSpawnEvent {
     pub mesh: Mesh,
     pub transform: EulerTransform,
     pub material: TMaterial,
+    // None keeps the old behaviour of a cuboid fitted to the mesh AABB
+    pub collider_type: Option<ColliderType>,
+    // Only set for explicitly authored colliders; blockout geometry stays out of priority despawning
+    pub priority: Option<i8>,
+    pub overlap: OverlapResolution,
     pub parent: Option<Entity>,
 }
 
@@ -88,6 +102,30 @@ pub struct DistanceFogSpawnEvent {
     pub fog: DistanceFog,
 }
 
+#[derive(Debug, Clone, Event)]
+pub struct NavMeshSettingsSpawnEvent {
+    pub config: NavMeshConfig,
+}
+
+#[derive(Debug, Clone, Event)]
+pub struct NavLinkSpawnEvent {
+    pub from: Vec3,
+    pub to: Vec3,
+    pub bidirectional: bool,
+    pub cost: f32,
+    pub transform: EulerTransform,
+    pub parent: Option<Entity>,
+}
+
+#[derive(Debug, Clone, Event)]
+pub struct NavAreaSpawnEvent {
+    pub shape: PrimitiveShape,
+    pub area_type: NavAreaType,
+    pub cost: Option<f32>,
+    pub transform: EulerTransform,
+    pub parent: Option<Entity>,
+}
+
 #[derive(Debug, Clone, Event)]
 pub struct AtmosphereNishitaSpawnEvent {
     pub sun_position: Vec3,
@@ -112,6 +150,10 @@ pub struct NestSpawnEvent {
     pub reference: StructureReference,
     pub transform: EulerTransform,
     pub parent: Option<Entity>,
+    // Set by sampling spawners (Rand, RandDistDir) so overlaps can be resolved with a new point
+    pub sampler: Option<SpawnSampler>,
+    // Set when the structure's footprint is already in the OccupancyGrid
+    pub claimed: Option<OccupancyClaimed>,
 }
 
 #[derive(Debug, Clone, Event)]
@@ -199,6 +241,8 @@ pub struct PathSpawnEvent {
     pub tension: f32,
     pub spread: SpreadData,
     pub count: u32,
+    pub shaping: Option<PathShaping>,
+    pub placement: Option<PathPlacement>,
     pub transform: EulerTransform,
     pub parent: Option<Entity>,
 }
@@ -216,6 +260,14 @@ pub struct PathToTagSpawnEvent {
     // Optional: if provided, the computed world polyline will be stored on an entity
     // carrying this label in its Tags. If such an entity doesn't exist, one will be created.
     pub store_as: Option<String>,
+    pub max_retries: Option<u32>,
+    pub fallback: PathFallback,
+    pub cost: Option<PathCost>,
+    pub shaping: Option<PathShaping>,
+    pub placement: Option<PathPlacement>,
+    pub agent: Option<String>,
+    // Frames this request has already been retried
+    pub retries: u32,
     pub transform: EulerTransform,
     pub parent: Option<Entity>,
 }
@@ -232,6 +284,14 @@ pub struct PathToAllTagsSpawnEvent {
     pub wobble: Option<WobbleParams>,
     // Optional: same behavior as PathToTagSpawnEvent
     pub store_as: Option<String>,
+    pub max_retries: Option<u32>,
+    pub fallback: PathFallback,
+    pub cost: Option<PathCost>,
+    pub shaping: Option<PathShaping>,
+    pub placement: Option<PathPlacement>,
+    pub agent: Option<String>,
+    // Frames this request has already been retried
+    pub retries: u32,
     pub transform: EulerTransform,
     pub parent: Option<Entity>,
 }
@@ -267,6 +327,8 @@ pub struct SelectiveReplacementFinalizeEvent {
 #[derive(Debug, Clone, Event)]
 pub struct InPassSpawnEvent {
     pub index: u8,
+    // Named pass from the GenerationPipeline; takes precedence over `index` when set
+    pub pass: Option<String>,
     pub reference: StructureReference,
     pub transform: EulerTransform,
     pub parent: Option<Entity>,
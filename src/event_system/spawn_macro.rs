@@ -2,7 +2,7 @@
 macro_rules! spawn {
     ($commands:expr, $event:expr) => {
         $commands.queue(move |w: &mut bevy::prelude::World| {
-            w.send_event($event);
+            $crate::event_system::work_tickets::send_tracked(w, $event);
         });
     };
 }
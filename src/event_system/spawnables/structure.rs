@@ -3,23 +3,48 @@ use crate::core::structure::Structure;
 use crate::management::structure_management::import_structure;
 use crate::core::structure_key::StructureKey;
 use crate::event_system::spawn_events::StructureSpawnEvent;
+use crate::event_system::work_tickets::{complete_tickets, WorkTickets};
+use crate::event_system::generation_log::SPAWN;
+use crate::event_system::generation_budget::{defer_over_budget, FrameBudget};
+use crate::event_system::generation_error::{GenerationError, GenerationErrorKind, PendingErrors};
+use crate::event_system::generation_report::{expansion_depth, ExpansionDepth, GenerationReport, ListenerTimer, ReportSamples};
 use crate::spawning::euler_transform::EulerTransform;
 use crate::core::tags::Tags;
 
 pub fn structure_spawn_listener(
     mut commands: Commands,
     mut reader: EventReader<StructureSpawnEvent>,
+    mut report: ResMut<GenerationReport>,
+    hierarchy: Query<(Option<&ExpansionDepth>, Option<&Parent>)>,
+    mut tickets: ResMut<WorkTickets>,
+    (samples, budget): (Res<ReportSamples>, Res<FrameBudget>),
+    errors: Res<PendingErrors>,
 ) {
-    for event in reader.read() {
-        if let Err(e) = spawn_structure(&mut commands, event) {
-            eprintln!("Error spawning structure: {}", e);
+    let _timer = ListenerTimer::start("structure_spawn_listener", &samples);
+    let mut handled = Vec::new();
+    for (event, id) in reader.read_with_id() {
+        // Structures are the entry point of a run and may be sent untracked by the app
+        tickets.adopt(id);
+        handled.push(id);
+        if defer_over_budget(&mut commands, event, &budget) { continue; }
+        let depth = expansion_depth(event.parent, &hierarchy);
+        report.record_structure(&event.structure, depth);
+        let _span = debug_span!(target: SPAWN, "structure", structure = %event.structure, depth).entered();
+        match spawn_structure(&mut commands, event, depth) {
+            Ok(entity) => debug!(target: SPAWN, entity = ?entity, "structure spawned"),
+            Err(e) => GenerationError::new(GenerationErrorKind::ImportFailed, e)
+                .with_structure(event.structure.clone())
+                .with_parent(event.parent)
+                .report(&errors),
         }
     }
+    complete_tickets(&mut commands, handled);
 }
 
 fn spawn_structure(
     commands: &mut Commands,
     event: &StructureSpawnEvent,
+    depth: u32,
 ) -> Result<Option<Entity>, String> {
     let structure_name = &event.structure;
 
@@ -29,6 +54,7 @@ fn spawn_structure(
         .insert(Transform::from(event.transform.clone()))
         .insert(GlobalTransform::default())
         .insert(InheritedVisibility::default())
+        .insert(ExpansionDepth(depth))
         .id();
 
     // Attach to parent if specified
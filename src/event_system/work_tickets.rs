@@ -0,0 +1,186 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use bevy::ecs::event::EventId;
+use bevy::prelude::*;
+use crate::event_system::generation_log::SPAWN;
+
+// One unit of outstanding generation work: a spawn event that has been sent but not yet handled.
+// The id is the event's own EventId, so the listener can count it out without extra event fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WorkTicket {
+    kind: TypeId,
+    id: usize,
+}
+
+impl WorkTicket {
+    pub fn of<E: Event>(id: EventId<E>) -> Self {
+        WorkTicket { kind: TypeId::of::<E>(), id: id.id }
+    }
+}
+
+struct TicketInfo {
+    event_name: &'static str,
+    // Whether the event has left the double buffer, i.e. it can no longer be read by anyone
+    dropped: fn(&World, usize) -> bool,
+    // Clears the event's queue, used when generation is cancelled
+    clear: fn(&mut World),
+}
+
+fn event_dropped<E: Event>(world: &World, id: usize) -> bool {
+    world
+        .get_resource::<Events<E>>()
+        .map(|events| id < events.oldest_event_count())
+        .unwrap_or(true)
+}
+
+fn clear_events<E: Event>(world: &mut World) {
+    if let Some(mut events) = world.get_resource_mut::<Events<E>>() {
+        events.clear();
+    }
+}
+
+// Spawn events counted in when sent and counted out once their listener has handled them.
+// Generating is finished exactly when nothing is outstanding (see generation_state_driver).
+#[derive(Resource, Default)]
+pub struct WorkTickets {
+    outstanding: HashMap<WorkTicket, TicketInfo>,
+    issued_this_phase: u32,
+    completed: u32,
+}
+
+impl WorkTickets {
+    pub fn issue<E: Event>(&mut self, id: EventId<E>) -> WorkTicket {
+        let ticket = WorkTicket::of(id);
+        self.outstanding.insert(ticket, TicketInfo {
+            event_name: std::any::type_name::<E>(),
+            dropped: event_dropped::<E>,
+            clear: clear_events::<E>,
+        });
+        self.issued_this_phase = self.issued_this_phase.saturating_add(1);
+        ticket
+    }
+
+    // Count in an event that was sent without send_tracked (e.g. through an app's own EventWriter)
+    // when its listener picks it up, so entry-point events still open the phase they start
+    pub fn adopt<E: Event>(&mut self, id: EventId<E>) {
+        if !self.outstanding.contains_key(&WorkTicket::of(id)) {
+            self.issue(id);
+        }
+    }
+
+    // Completing a ticket that was never issued (an untracked send) is a no-op
+    pub fn complete(&mut self, ticket: WorkTicket) -> bool {
+        let known = self.outstanding.remove(&ticket).is_some();
+        if known {
+            self.completed = self.completed.saturating_add(1);
+        }
+        known
+    }
+
+    pub fn outstanding(&self) -> usize {
+        self.outstanding.len()
+    }
+
+    // Outstanding tickets for events of type E
+    pub fn outstanding_of<E: Event>(&self) -> usize {
+        let kind = TypeId::of::<E>();
+        self.outstanding.keys().filter(|ticket| ticket.kind == kind).count()
+    }
+
+    // Drop the outstanding tickets for events of type E, returning their event ids in send order
+    pub fn take_outstanding<E: Event>(&mut self) -> Vec<usize> {
+        let kind = TypeId::of::<E>();
+        let mut ids: Vec<usize> = self.outstanding.keys().filter(|ticket| ticket.kind == kind).map(|ticket| ticket.id).collect();
+        ids.sort_unstable();
+        for &id in ids.iter() {
+            self.complete(WorkTicket { kind, id });
+        }
+        ids
+    }
+
+    pub fn issued_this_phase(&self) -> u32 {
+        self.issued_this_phase
+    }
+
+    // Tickets completed since the last reset_completed (i.e. in the current generation run)
+    pub fn completed(&self) -> u32 {
+        self.completed
+    }
+
+    pub fn reset_completed(&mut self) {
+        self.completed = 0;
+    }
+
+    // Work already in flight when a phase starts belongs to that phase
+    pub fn begin_phase(&mut self) {
+        self.issued_this_phase = self.outstanding.len() as u32;
+    }
+
+    pub fn clear(&mut self) {
+        self.outstanding.clear();
+        self.issued_this_phase = 0;
+        self.completed = 0;
+    }
+}
+
+// Send a spawn event and count it in. Its listener counts it out with complete_tickets.
+pub fn send_tracked<E: Event>(world: &mut World, event: E) {
+    if let Some(id) = world.send_event(event) {
+        world.get_resource_or_insert_with(WorkTickets::default).issue(id);
+    }
+}
+
+// Count out the events a listener handled. This is queued rather than applied directly so it lands
+// after any child events the listener dispatched through the same Commands, keeping the count above
+// zero while work is still being handed down.
+pub fn complete_tickets<E: Event>(commands: &mut Commands, ids: Vec<EventId<E>>) {
+    if ids.is_empty() {
+        return;
+    }
+    commands.queue(move |world: &mut World| {
+        if let Some(mut tickets) = world.get_resource_mut::<WorkTickets>() {
+            for id in ids {
+                tickets.complete(WorkTicket::of(id));
+            }
+        }
+    });
+}
+
+// Drop tickets whose events expired unread (e.g. sent while their listener's state was inactive),
+// so a lost event cannot hold generation open forever.
+pub fn expire_dropped_tickets(world: &mut World) {
+    let expired: Vec<(WorkTicket, &'static str)> = {
+        let Some(tickets) = world.get_resource::<WorkTickets>() else { return; };
+        tickets
+            .outstanding
+            .iter()
+            .filter(|(ticket, info)| (info.dropped)(world, ticket.id))
+            .map(|(ticket, info)| (*ticket, info.event_name))
+            .collect()
+    };
+    if expired.is_empty() {
+        return;
+    }
+    let mut tickets = world.resource_mut::<WorkTickets>();
+    for (ticket, event_name) in expired {
+        warn!(target: SPAWN, event = event_name, ticket = ticket.id, "spawn event expired without being handled");
+        tickets.complete(ticket);
+    }
+}
+
+// Cancel all outstanding work: clear the queues of every event type with a ticket out, so nothing
+// sent for the cancelled run is picked up later, and reset the tickets.
+pub fn drop_outstanding_work(world: &mut World) {
+    let clears: Vec<fn(&mut World)> = {
+        let Some(tickets) = world.get_resource::<WorkTickets>() else { return; };
+        let mut by_kind: HashMap<TypeId, fn(&mut World)> = HashMap::new();
+        for (ticket, info) in &tickets.outstanding {
+            by_kind.insert(ticket.kind, info.clear);
+        }
+        by_kind.into_values().collect()
+    };
+    for clear in clears {
+        clear(world);
+    }
+    world.resource_mut::<WorkTickets>().clear();
+}
@@ -10,6 +10,7 @@ use bevy::image::ImageLoader;
 use bevy::image::CompressedImageSaver;
 use bevy_asset_loader::prelude::*;
 use crate::serialization::caching::MaterialCache;
+use crate::management::metallic_roughness_packer::MetallicRoughnessPackLoader;
 
 pub struct MaterialAutoloader;
 
@@ -34,8 +35,13 @@ impl Plugin for MaterialAutoloader {
         app.register_asset_processor::<LoadTransformAndSave<ImageLoader, IdentityAssetTransformer<_>, CompressedImageSaver>>
             (LoadTransformAndSave::from(CompressedImageSaver));
 
-        app.set_default_asset_processor::<LoadTransformAndSave<ImageLoader, IdentityAssetTransformer<_>, CompressedImageSaver>>(
-            ".png",
+        // Same as above, but packs split `_metallic`/`_roughness` maps into a metallicRoughness texture on import
+        app.register_asset_loader(MetallicRoughnessPackLoader::default());
+        app.register_asset_processor::<LoadTransformAndSave<MetallicRoughnessPackLoader, IdentityAssetTransformer<_>, CompressedImageSaver>>
+            (LoadTransformAndSave::from(CompressedImageSaver));
+
+        app.set_default_asset_processor::<LoadTransformAndSave<MetallicRoughnessPackLoader, IdentityAssetTransformer<_>, CompressedImageSaver>>(
+            "png",
         );
 
         app.init_state::<GameState>() // ✅ Initialize game state
@@ -51,6 +57,9 @@ impl Plugin for MaterialAutoloader {
 }
 
 // 🚀 Step 2: Convert Loaded Textures into Materials
+// Albedo, AO, normal and metallic-roughness texture paths of one material
+type MaterialTexturePaths = (Option<String>, Option<String>, Option<String>, Option<String>);
+
 fn preload_materials_system(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
@@ -58,7 +67,9 @@ fn preload_materials_system(
     material_textures: Res<MaterialTextures>,
 ) {
     let mut material_cache = MaterialCache::new();
-    let mut material_sets: HashMap<String, (Option<String>, Option<String>, Option<String>, Option<String>)> = HashMap::new();
+    let mut material_sets: HashMap<String, MaterialTexturePaths> = HashMap::new();
+    // Split maps that the asset processor packed into metallicRoughness; used when no packed file was authored
+    let mut packed_fallbacks: HashMap<String, (Option<String>, Option<String>)> = HashMap::new();
 
     for file_path in material_textures.textures.keys() {
         debug!("Loading material: {}", file_path);
@@ -75,10 +86,20 @@ fn preload_materials_system(
             "ao" => entry.1 = Some(file_path.to_string()),
             "normal" => entry.2 = Some(file_path.to_string()),
             "met_roughness" | "metallicRoughness" => entry.3 = Some(file_path.to_string()),
+            "roughness" => packed_fallbacks.entry(mat_name).or_default().0 = Some(file_path.to_string()),
+            "metallic" => packed_fallbacks.entry(mat_name).or_default().1 = Some(file_path.to_string()),
             _ => {}
         }
     }
 
+    for (mat_name, (roughness, metallic)) in packed_fallbacks.into_iter() {
+        let entry = material_sets.entry(mat_name).or_insert((None, None, None, None));
+        if entry.3.is_none() {
+            // The roughness map carries the packed result when both halves exist
+            entry.3 = roughness.or(metallic);
+        }
+    }
+
     for (material_name, textures) in material_sets.iter() {
         let base_tex = textures.0.as_ref().map(|path| asset_server.load(path));
         let ao_tex = textures.1.as_ref().map(|path| asset_server.load(path));
@@ -107,7 +128,8 @@ fn preload_materials_system(
 // 🚀 Utility: Extract Material Data
 fn extract_tex_data(tex_name: &str) -> (String, String) {
     // Support multiple common conventions for texture suffixes
-    let texture_types = ["albedo", "ao", "normal", "met_roughness", "metallicRoughness"];
+    // Packed variants come before "roughness"/"metallic" since their suffixes contain those names
+    let texture_types = ["albedo", "ao", "normal", "met_roughness", "metallicRoughness", "roughness", "metallic"];
     // Normalize separators in case any path contains backslashes
     let normalized = tex_name.replace('\\', "/");
     let parts: Vec<&str> = normalized.split('/').collect();
@@ -0,0 +1,192 @@
+use std::path::{Path, PathBuf};
+use bevy::asset::io::{Reader, VecReader};
+use bevy::asset::{AssetLoader, AssetPath, LoadContext};
+use bevy::image::{CompressedImageFormats, Image, ImageLoader, ImageLoaderError, ImageLoaderSettings};
+use image::imageops::FilterType;
+use image::{DynamicImage, Rgb, RgbImage};
+
+// Which half of a split metallic/roughness pair a texture file is, based on its suffix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SplitChannel {
+    Metallic,
+    Roughness,
+}
+
+impl SplitChannel {
+    fn from_path(path: &Path) -> Option<(Self, String)> {
+        let file_name = path.file_name()?.to_str()?;
+        let lower = file_name.to_lowercase();
+        // Already-packed maps ("_metallicRoughness", "_met_roughness") pass through untouched
+        if lower.ends_with("_metallicroughness.png") || lower.ends_with("_met_roughness.png") {
+            return None;
+        }
+        let (channel, suffix) = if lower.ends_with("_metallic.png") {
+            (SplitChannel::Metallic, "_metallic.png")
+        } else if lower.ends_with("_roughness.png") {
+            (SplitChannel::Roughness, "_roughness.png")
+        } else {
+            return None;
+        };
+        let prefix = file_name[..file_name.len() - suffix.len()].to_string();
+        Some((channel, prefix))
+    }
+}
+
+// Image loader used by the asset processor to pack separate metallic and roughness maps into a single
+// glTF metallicRoughness texture (roughness in G, metallic in B). The packed result replaces the
+// `_roughness` texture; a lone `_metallic` texture is packed on its own. Everything else is loaded
+// exactly like `ImageLoader` would.
+pub struct MetallicRoughnessPackLoader {
+    inner: ImageLoader,
+}
+
+impl Default for MetallicRoughnessPackLoader {
+    fn default() -> Self {
+        // Source textures are plain PNGs; compressed formats only matter for the processed output.
+        MetallicRoughnessPackLoader { inner: ImageLoader::new(CompressedImageFormats::NONE) }
+    }
+}
+
+impl AssetLoader for MetallicRoughnessPackLoader {
+    type Asset = Image;
+    type Settings = ImageLoaderSettings;
+    type Error = ImageLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        settings: &ImageLoaderSettings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Image, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        if let Some((channel, prefix)) = SplitChannel::from_path(load_context.path()) {
+            // An authored packed map next to this one wins; the split maps are left as they are
+            if read_sibling(load_context, &prefix, "_metallicRoughness.png").await.is_some() {
+                return self.load_passthrough(bytes, settings, load_context).await;
+            }
+            let (metallic, roughness) = match channel {
+                SplitChannel::Roughness => {
+                    let metallic = read_sibling(load_context, &prefix, "_metallic.png").await.and_then(|b| decode(&b));
+                    (metallic, decode(&bytes))
+                }
+                SplitChannel::Metallic => {
+                    // When a roughness sibling exists it owns the packed output; keep this map as-is.
+                    if read_sibling(load_context, &prefix, "_roughness.png").await.is_some() {
+                        return self.load_passthrough(bytes, settings, load_context).await;
+                    }
+                    (decode(&bytes), None)
+                }
+            };
+
+            if let Some(packed) = pack_metallic_roughness(metallic, roughness) {
+                let mut image = Image::from_dynamic(DynamicImage::ImageRgb8(packed), false, settings.asset_usage);
+                image.sampler = settings.sampler.clone();
+                return Ok(image);
+            }
+        }
+
+        self.load_passthrough(bytes, settings, load_context).await
+    }
+
+    fn extensions(&self) -> &[&str] {
+        // Only selected explicitly by the asset processor; `ImageLoader` keeps owning the extensions.
+        &[]
+    }
+}
+
+impl MetallicRoughnessPackLoader {
+    async fn load_passthrough(
+        &self,
+        bytes: Vec<u8>,
+        settings: &ImageLoaderSettings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Image, ImageLoaderError> {
+        let mut reader = VecReader::new(bytes);
+        self.inner.load(&mut reader, settings, load_context).await
+    }
+}
+
+// Bytes of a texture that sits next to the current one, sharing its prefix; None when there is none.
+// Reading through the load context registers it as a dependency, so editing it re-triggers processing.
+async fn read_sibling(load_context: &mut LoadContext<'_>, prefix: &str, suffix: &str) -> Option<Vec<u8>> {
+    let sibling: PathBuf = load_context.path().with_file_name(format!("{}{}", prefix, suffix));
+    let source = load_context.asset_path().source().clone_owned();
+    let asset_path = AssetPath::from_path(&sibling).with_source(source).into_owned();
+    load_context.read_asset_bytes(asset_path).await.ok()
+}
+
+fn decode(bytes: &[u8]) -> Option<DynamicImage> {
+    image::load_from_memory(bytes).ok()
+}
+
+// Pack metallic into B and roughness into G (glTF convention). A missing map is treated as fully
+// white, matching what the old build-script packer produced.
+pub fn pack_metallic_roughness(metallic: Option<DynamicImage>, roughness: Option<DynamicImage>) -> Option<RgbImage> {
+    let (width, height) = match (&roughness, &metallic) {
+        (Some(r), _) => (r.width(), r.height()),
+        (None, Some(m)) => (m.width(), m.height()),
+        (None, None) => return None,
+    };
+
+    let to_size = |img: DynamicImage| -> RgbImage {
+        let rgb = img.to_rgb8();
+        if rgb.width() == width && rgb.height() == height {
+            rgb
+        } else {
+            image::imageops::resize(&rgb, width, height, FilterType::Triangle)
+        }
+    };
+    let metallic = metallic.map(to_size);
+    let roughness = roughness.map(to_size);
+
+    let mut packed = RgbImage::new(width, height);
+    for (x, y, pixel) in packed.enumerate_pixels_mut() {
+        let roughness_green = roughness.as_ref().map_or(255, |img| img.get_pixel(x, y)[1]);
+        let metallic_blue = metallic.as_ref().map_or(255, |img| img.get_pixel(x, y)[1]);
+        *pixel = Rgb([0, roughness_green, metallic_blue]);
+    }
+    Some(packed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GrayImage, Luma};
+
+    fn gray(width: u32, height: u32, value: u8) -> DynamicImage {
+        DynamicImage::ImageLuma8(GrayImage::from_pixel(width, height, Luma([value])))
+    }
+
+    #[test]
+    fn packs_roughness_into_green_and_metallic_into_blue() {
+        let packed = pack_metallic_roughness(Some(gray(4, 4, 10)), Some(gray(4, 4, 200))).unwrap();
+        assert_eq!((packed.width(), packed.height()), (4, 4));
+        assert!(packed.pixels().all(|p| *p == Rgb([0, 200, 10])));
+    }
+
+    #[test]
+    fn missing_map_is_white() {
+        let packed = pack_metallic_roughness(None, Some(gray(2, 2, 50))).unwrap();
+        assert!(packed.pixels().all(|p| *p == Rgb([0, 50, 255])));
+        let packed = pack_metallic_roughness(Some(gray(2, 2, 70)), None).unwrap();
+        assert!(packed.pixels().all(|p| *p == Rgb([0, 255, 70])));
+        assert!(pack_metallic_roughness(None, None).is_none());
+    }
+
+    #[test]
+    fn metallic_is_resized_to_roughness() {
+        let packed = pack_metallic_roughness(Some(gray(2, 2, 90)), Some(gray(8, 4, 30))).unwrap();
+        assert_eq!((packed.width(), packed.height()), (8, 4));
+        assert!(packed.pixels().all(|p| *p == Rgb([0, 30, 90])));
+    }
+
+    #[test]
+    fn split_channel_from_suffix() {
+        assert_eq!(SplitChannel::from_path(Path::new("a/Soil_roughness.png")), Some((SplitChannel::Roughness, "Soil".to_string())));
+        assert_eq!(SplitChannel::from_path(Path::new("a/Soil_Metallic.png")), Some((SplitChannel::Metallic, "Soil".to_string())));
+        assert_eq!(SplitChannel::from_path(Path::new("a/Soil_metallicRoughness.png")), None);
+        assert_eq!(SplitChannel::from_path(Path::new("a/Soil_albedo.png")), None);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod structure_management;
 pub mod audio_management;
 pub mod material_autoloader;
-pub mod scene_io;
\ No newline at end of file
+pub mod scene_io;
+pub mod metallic_roughness_packer;
\ No newline at end of file
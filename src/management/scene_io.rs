@@ -36,7 +36,7 @@ fn save_scene_system(world: &mut World) {
     bevy::tasks::IoTaskPool::get()
         .spawn(async move {
             // Write the scene RON data to file
-            File::create("assets/test_scene.ron".to_string())
+            File::create("assets/test_scene.ron")
                 .and_then(|mut file| file.write(serialized_scene.unwrap().as_bytes()))
                 .expect("Error while writing scene to file");
         })
@@ -4,6 +4,8 @@ use std::fs::File;
 use std::path::{PathBuf};
 use ron::de::{from_reader, SpannedError};
 use lazy_static::lazy_static;
+use bevy::log::{debug, debug_span, trace};
+use crate::event_system::generation_log::IMPORT;
 use crate::core::structure::Structure;
 
 lazy_static! {
@@ -14,7 +16,7 @@ lazy_static! {
 /// by splitting on both '/' and '\\' and appending the .arch extension.
 fn normalized_structure_relpath(structure_name: &str) -> PathBuf {
     let mut pb = PathBuf::new();
-    for seg in structure_name.split(|c| c == '/' || c == '\\') {
+    for seg in structure_name.split(['/', '\\']) {
         if !seg.is_empty() {
             pb.push(seg);
         }
@@ -26,16 +28,15 @@ fn normalized_structure_relpath(structure_name: &str) -> PathBuf {
 pub fn import_structure(structure_name: String) -> Result<Structure, ron::Error> {
     let mut cache = STRUCTURE_CACHE.lock().unwrap();
 
+    let _span = debug_span!(target: IMPORT, "import", structure = %structure_name).entered();
     if let Some(cached_structure) = cache.get(&structure_name) {
+        trace!(target: IMPORT, "cache hit");
         return Ok(cached_structure.clone());
     }
 
     // Determine candidate roots for assets, depending on where the app was launched from
     let base_path = std::env::current_dir().unwrap();
-    #[cfg(feature = "debug")]
-    {
-        eprintln!("[structure_management] CWD: {}", base_path.display());
-    }
+    trace!(target: IMPORT, cwd = %base_path.display());
     let candidates = [
         base_path.join("assets/structures"),
         base_path.join("tests/assets/structures"),
@@ -47,10 +48,7 @@ pub fn import_structure(structure_name: String) -> Result<Structure, ron::Error>
     let mut chosen_path = None;
     for root in candidates.iter() {
         let candidate = root.join(&rel);
-        #[cfg(feature = "debug")]
-        {
-            eprintln!("[structure_management] Trying: {} -> exists: {}", candidate.display(), candidate.exists());
-        }
+        trace!(target: IMPORT, candidate = %candidate.display(), exists = candidate.exists(), "trying");
         if candidate.exists() {
             chosen_path = Some(candidate);
             break;
@@ -58,19 +56,13 @@ pub fn import_structure(structure_name: String) -> Result<Structure, ron::Error>
     }
 
     let file_path = if let Some(path) = chosen_path {
-        #[cfg(feature = "debug")]
-        {
-            eprintln!("[structure_management] Using structure file: {}", path.display());
-        }
+        debug!(target: IMPORT, path = %path.display(), "using structure file");
         path
     } else {
         let tried_paths = candidates
             .iter()
             .map(|root| root.join(&rel))
             .collect::<Vec<_>>();
-        for p in &tried_paths {
-            eprintln!("❌ ERROR: Structure file NOT FOUND: {}", p.display());
-        }
         let tried_joined = tried_paths
             .iter()
             .map(|p| format!("{}", p.display()))
@@ -90,7 +82,10 @@ pub fn import_structure(structure_name: String) -> Result<Structure, ron::Error>
             cache.insert(structure_name.clone(), structure.clone());
             Ok(structure)
         }
-        Err(e) => Err(e.into())
+        Err(e) => {
+            debug!(target: IMPORT, path = %file_path.display(), error = %e, "failed to parse structure file");
+            Err(e.into())
+        }
     }
 }
 
@@ -1,3 +1,6 @@
+// The ShaderType derive below asserts field traits in helper functions it never calls, which current
+// compilers report as dead code
+#![allow(dead_code)]
 use bevy::prelude::*;
 use bevy_pbr::{ExtendedMaterial, MaterialExtension, StandardMaterial};
 use bevy::reflect::TypePath;
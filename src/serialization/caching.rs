@@ -10,7 +10,7 @@ pub struct StructureModels {
     //pub(crate) models: Vec<UntypedHandle>
 }
 
-#[derive(Resource)]
+#[derive(Resource, Default)]
 pub struct MaterialCache {
     map: HashMap<String, Handle<StandardMaterial>>,
 }
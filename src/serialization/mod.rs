@@ -1,2 +1,3 @@
+#[allow(clippy::module_inception)]
 pub mod serialization;
 pub mod caching;
\ No newline at end of file
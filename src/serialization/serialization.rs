@@ -1,5 +1,5 @@
 use serde::{Serialize, Deserialize};
-use bevy::prelude::{Color};
+use bevy::prelude::{AlphaMode, Color};
 use bevy::pbr::{PointLight, SpotLight, DirectionalLight, AmbientLight};
 use bevy::math::Vec3;
 use bevy::pbr::FogFalloff;
@@ -78,3 +78,15 @@ pub enum SerializableFogFalloff {
         inscattering: Vec3,
     },
 }
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "AlphaMode")]
+pub enum SerializableAlphaMode {
+    Opaque,
+    Mask(f32),
+    Blend,
+    Premultiplied,
+    AlphaToCoverage,
+    Add,
+    Multiply,
+}
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use bevy::prelude::*;
+use bevy::tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task};
+use bevy_rapier3d::parry::shape::TypedShape;
+use bevy_rapier3d::prelude::{Collider, ColliderDisabled, ColliderView};
+use oxidized_navigation::colliders::OxidizedCollider;
+use oxidized_navigation::conversion::{ColliderType, GeometryCollection, GeometryToConvert};
+use oxidized_navigation::tiles::{NavMeshTile, NavMeshTiles};
+use oxidized_navigation::{build_tile_sync, Area, NavMeshAffector, NavMeshAreaType, NavMeshSettings};
+use crate::core::navmesh_config::NavMeshAuthoring;
+use crate::event_system::generation_log::PATH;
+
+// Largest tile side, in cells, a profile navmesh may use; coarser cells are used past it
+const MAX_PROFILE_TILE_WIDTH: f32 = 2048.0;
+
+pub struct AgentNavMesh {
+    pub settings: NavMeshSettings,
+    pub tiles: Arc<RwLock<NavMeshTiles>>,
+    task: Option<Task<NavMeshTile>>,
+}
+
+// Navmeshes of the agent profiles in NavMeshConfig, rebuilt whenever the main navmesh is
+#[derive(Resource, Default)]
+pub struct AgentNavMeshes {
+    meshes: HashMap<String, AgentNavMesh>,
+}
+
+impl AgentNavMeshes {
+    // Tiles and settings of a finished profile navmesh
+    pub fn get(&self, name: &str) -> Option<(Arc<RwLock<NavMeshTiles>>, &NavMeshSettings)> {
+        self.meshes.get(name)
+            .filter(|mesh| mesh.task.is_none())
+            .map(|mesh| (mesh.tiles.clone(), &mesh.settings))
+    }
+
+    pub fn is_building(&self) -> bool {
+        self.meshes.values().any(|mesh| mesh.task.is_some())
+    }
+}
+
+// Owner of a compound collider whose parts carve the navmesh as NavMeshAffectorPart children.
+// oxidized_navigation (and collect_geometry) skip rapier compounds, so compound colliders and convex
+// decompositions would otherwise never block the navmesh.
+#[derive(Component)]
+pub struct NavMeshAffectorParts;
+
+// One part of a compound affector. Disabled for physics; the owner's compound still collides.
+#[derive(Component)]
+pub struct NavMeshAffectorPart;
+
+// The shapes of a compound collider with their placement relative to the owner, unscaled since the
+// owner's scale reaches the parts through the hierarchy
+fn compound_parts(collider: &Collider) -> Option<Vec<(Collider, Transform)>> {
+    let ColliderView::Compound(compound) = collider.as_unscaled_typed_shape() else { return None; };
+    Some(compound.raw.shapes().iter()
+        .map(|(position, shape)| {
+            let transform = Transform::from_translation(Vec3::from(position.translation.vector))
+                .with_rotation(Quat::from(position.rotation));
+            (Collider::from(shape.clone()), transform)
+        })
+        .collect())
+}
+
+// Make `entity` a navmesh affector. Compounds get one affector child per part instead.
+pub fn add_navmesh_affector(commands: &mut Commands, entity: Entity, collider: Option<&Collider>) {
+    let Some(parts) = collider.and_then(compound_parts) else {
+        commands.entity(entity).insert(NavMeshAffector);
+        return;
+    };
+    trace!(target: PATH, ?entity, parts = parts.len(), "compound navmesh affector split into parts");
+    commands.entity(entity).insert(NavMeshAffectorParts).with_children(|owner| {
+        for (collider, transform) in parts {
+            owner.spawn((collider, transform, ColliderDisabled, NavMeshAffector, NavMeshAffectorPart, Name::new("NavMeshAffectorPart")));
+        }
+    });
+}
+
+type AffectorQuery<'w, 's> = Query<'w, 's, (&'static Collider, &'static GlobalTransform, Option<&'static NavMeshAreaType>), With<NavMeshAffector>>;
+
+// Geometry of every navmesh affector, in the form oxidized_navigation voxelizes, and the largest
+// horizontal distance from the origin it reaches
+fn collect_geometry(affectors: &AffectorQuery) -> (Vec<GeometryCollection>, f32) {
+    let mut geometry = Vec::new();
+    let mut reach: f32 = 0.0;
+    for (collider, global, area_type) in affectors.iter() {
+        let area = area_type.map_or(Some(Area(0)), |a| a.0);
+        let shape = match collider.oxidized_into_typed_shape() {
+            TypedShape::Ball(ball) => GeometryToConvert::Collider(ColliderType::Ball(*ball)),
+            TypedShape::Cuboid(cuboid) => GeometryToConvert::Collider(ColliderType::Cuboid(*cuboid)),
+            TypedShape::Capsule(capsule) => GeometryToConvert::Collider(ColliderType::Capsule(*capsule)),
+            TypedShape::Cylinder(cylinder) => GeometryToConvert::Collider(ColliderType::Cylinder(*cylinder)),
+            TypedShape::Cone(cone) => GeometryToConvert::Collider(ColliderType::Cone(*cone)),
+            TypedShape::Triangle(triangle) => GeometryToConvert::Collider(ColliderType::Triangle(*triangle)),
+            TypedShape::RoundCuboid(round) => GeometryToConvert::Collider(ColliderType::Cuboid(round.inner_shape)),
+            TypedShape::RoundCylinder(round) => GeometryToConvert::Collider(ColliderType::Cylinder(round.inner_shape)),
+            TypedShape::RoundCone(round) => GeometryToConvert::Collider(ColliderType::Cone(round.inner_shape)),
+            TypedShape::RoundTriangle(round) => GeometryToConvert::Collider(ColliderType::Triangle(round.inner_shape)),
+            TypedShape::TriMesh(trimesh) => GeometryToConvert::ParryTriMesh(
+                trimesh.vertices().to_vec().into_boxed_slice(),
+                trimesh.indices().to_vec().into_boxed_slice(),
+            ),
+            TypedShape::ConvexPolyhedron(polyhedron) => {
+                let (vertices, indices) = polyhedron.to_trimesh();
+                GeometryToConvert::ParryTriMesh(vertices.into_boxed_slice(), indices.into_boxed_slice())
+            }
+            TypedShape::RoundConvexPolyhedron(round) => {
+                let (vertices, indices) = round.inner_shape.to_trimesh();
+                GeometryToConvert::ParryTriMesh(vertices.into_boxed_slice(), indices.into_boxed_slice())
+            }
+            // Heightfields and compounds are left to the main navmesh; lines and planes have no area
+            _ => {
+                trace!(target: PATH, "agent navmesh: skipping unsupported collider shape");
+                continue;
+            }
+        };
+        let transform = global.compute_transform();
+        let aabb = collider.oxidized_compute_local_aabb();
+        let half_extents = Vec3::new(aabb.half_extents().x, aabb.half_extents().y, aabb.half_extents().z) * transform.scale.abs().max_element();
+        let centre = transform.translation;
+        reach = reach.max(centre.x.abs().max(centre.z.abs()) + half_extents.length());
+        geometry.push(GeometryCollection { transform, geometry_to_convert: shape, area });
+    }
+    (geometry, reach)
+}
+
+// On entering NavMeshBuilding (after the affectors are activated), start one build per agent profile.
+// Each profile navmesh is a single tile centred on the origin that spans every affector.
+pub fn start_agent_navmesh_builds(
+    authoring: Res<NavMeshAuthoring>,
+    settings: Option<Res<NavMeshSettings>>,
+    affectors: AffectorQuery,
+    mut meshes: ResMut<AgentNavMeshes>,
+) {
+    let profiles = authoring.effective().agent_profiles;
+    meshes.meshes.retain(|name, _| profiles.iter().any(|p| &p.name == name));
+    if profiles.is_empty() { return; }
+    let Some(base) = settings else {
+        warn!(target: PATH, "agent navmesh profiles configured without NavMeshSettings; skipping");
+        return;
+    };
+    let reach = collect_geometry(&affectors).1;
+    // Margin of a meter so geometry at the edge still gets a border
+    let half_extents = (reach + 1.0).min(base.world_half_extents);
+
+    for profile in profiles.iter() {
+        let mut profile = profile.clone();
+        let min_cell_width = 2.0 * half_extents / MAX_PROFILE_TILE_WIDTH;
+        if profile.cell_width.unwrap_or(base.cell_width) < min_cell_width {
+            warn!(target: PATH, profile = %profile.name, cell_width = min_cell_width, "agent navmesh: area too large for the profile's cells; coarsening");
+            profile.cell_width = Some(min_cell_width);
+        }
+        let mut settings = profile.settings(&base);
+        settings.world_half_extents = half_extents;
+        settings.tile_width = (2.0 * half_extents / settings.cell_width).ceil().max(8.0) as u16;
+
+        let (geometry, _) = collect_geometry(&affectors);
+        let affector_count = geometry.len();
+        let task_settings = settings.clone();
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            build_tile_sync(geometry, UVec2::ZERO, Vec::new().into_boxed_slice(), &task_settings)
+        });
+        debug!(target: PATH, profile = %profile.name, tile_width = settings.tile_width, affectors = affector_count, "agent navmesh: building");
+        meshes.meshes.insert(profile.name.clone(), AgentNavMesh {
+            settings,
+            tiles: Arc::new(RwLock::new(NavMeshTiles::default())),
+            task: Some(task),
+        });
+    }
+}
+
+// Store finished profile tiles
+pub fn poll_agent_navmesh_builds(mut meshes: ResMut<AgentNavMeshes>) {
+    if !meshes.is_building() { return; }
+    for (name, mesh) in meshes.meshes.iter_mut() {
+        let Some(task) = mesh.task.as_mut() else { continue; };
+        let Some(tile) = block_on(future::poll_once(task)) else { continue; };
+        mesh.task = None;
+        if let Ok(mut tiles) = mesh.tiles.write() {
+            tiles.tiles.insert(UVec2::ZERO, tile);
+            tiles.tile_generations.insert(UVec2::ZERO, 1);
+        }
+        debug!(target: PATH, profile = %name, "agent navmesh: built");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy::transform::systems::{propagate_transforms, sync_simple_transforms};
+    use crate::core::collider::{create_collider_from_geometry, ColliderGeometry};
+    use crate::core::collider::ColliderType as ShapeType;
+    use crate::event_system::generation_error::PendingErrors;
+
+    // Whether a navmesh triangle below `below` covers the XZ position of `p`
+    fn walkable_at(tile: &NavMeshTile, p: Vec2, below: f32) -> bool {
+        tile.polygons.iter().any(|polygon| {
+            let [a, b, c] = polygon.indices.map(|i| tile.vertices[i as usize]);
+            if a.y.max(b.y).max(c.y) > below { return false; }
+            let side = |u: Vec3, v: Vec3| (v.xz() - u.xz()).perp_dot(p - u.xz());
+            let (ab, bc, ca) = (side(a, b), side(b, c), side(c, a));
+            (ab >= 0.0 && bc >= 0.0 && ca >= 0.0) || (ab <= 0.0 && bc <= 0.0 && ca <= 0.0)
+        })
+    }
+
+    #[test]
+    fn decomposed_collider_blocks_the_navmesh() {
+        // Transform propagation runs on the compute pool
+        bevy::tasks::ComputeTaskPool::get_or_init(bevy::tasks::TaskPool::default);
+        let mut world = World::new();
+        world.spawn((Collider::cuboid(8.0, 0.1, 8.0), Transform::from_xyz(5.0, 0.0, 5.0), NavMeshAffector));
+        let block = ColliderGeometry::from_mesh(&Mesh::from(Cuboid::new(2.0, 3.0, 2.0)));
+        let decomposed = create_collider_from_geometry(&ShapeType::ConvexDecomposition, Some(&block), &PendingErrors::default()).unwrap();
+        assert!(decomposed.as_compound().is_some());
+        let obstacle = world.spawn((decomposed, Transform::from_xyz(5.0, 1.6, 5.0))).id();
+
+        world.run_system_once(move |mut commands: Commands, colliders: Query<&Collider>| {
+            add_navmesh_affector(&mut commands, obstacle, colliders.get(obstacle).ok());
+        }).unwrap();
+        world.run_system_once(sync_simple_transforms).unwrap();
+        world.run_system_once(propagate_transforms).unwrap();
+        assert!(world.get::<NavMeshAffector>(obstacle).is_none());
+        assert!(world.get::<NavMeshAffectorParts>(obstacle).is_some());
+
+        let settings = NavMeshSettings::from_agent_and_bounds(0.5, 2.0, 100.0, -10.0);
+        let tile_coord = settings.get_tile_containing_position(Vec2::splat(5.0));
+        let geometry = world.run_system_once(|affectors: AffectorQuery| collect_geometry(&affectors).0).unwrap();
+        let tile = build_tile_sync(geometry, tile_coord, Vec::new().into_boxed_slice(), &settings);
+        assert!(walkable_at(&tile, Vec2::new(10.0, 10.0), 1.0), "open ground stays walkable");
+        assert!(!walkable_at(&tile, Vec2::new(5.0, 5.0), 1.0), "the ground under the obstacle is carved out");
+    }
+}
@@ -1,7 +1,5 @@
 use bevy::prelude::*;
 use bevy_prng::WyRand;
-use bevy_rapier3d::prelude::*;
-use crate::core::collider::ColliderType;
 use crate::core::rand_data::RandData;
 use statrs::distribution::{Normal};
 use rand::{Rng, SeedableRng};
@@ -53,37 +51,21 @@ pub fn jiggle_transform(
     }
 }
 
-pub fn create_collider(collider_type: &ColliderType) -> Option<Collider> {
-    match collider_type {
-        ColliderType::None => None,
-        ColliderType::Ball { radius } => Some(Collider::ball(*radius)),
-        ColliderType::Cylinder { half_height, radius } => Some(Collider::cylinder(*half_height, *radius)),
-        ColliderType::RoundCylinder { half_height, radius, border_radius } =>
-            Some(Collider::round_cylinder(*half_height, *radius, *border_radius)),
-        ColliderType::Cone { half_height, radius } => Some(Collider::cone(*half_height, *radius)),
-        ColliderType::RoundCone { half_height, radius, border_radius } =>
-            Some(Collider::round_cone(*half_height, *radius, *border_radius)),
-        ColliderType::Capsule { start, end, radius } => Some(Collider::capsule(*start, *end, *radius)),
-        ColliderType::CapsuleX { half_height, radius } => Some(Collider::capsule_x(*half_height, *radius)),
-        ColliderType::CapsuleY { half_height, radius } => Some(Collider::capsule_y(*half_height, *radius)),
-        ColliderType::CapsuleZ { half_height, radius } => Some(Collider::capsule_z(*half_height, *radius)),
-        ColliderType::Cuboid { hx, hy, hz } => Some(Collider::cuboid(*hx, *hy, *hz)),
-        ColliderType::RoundCuboid { half_x, half_y, half_z, border_radius } =>
-            Some(Collider::round_cuboid(*half_x, *half_y, *half_z, *border_radius)),
-        ColliderType::Segment { a, b } => Some(Collider::segment(*a, *b)),
-        ColliderType::Triangle { a, b, c } => Some(Collider::triangle(*a, *b, *c)),
-        ColliderType::RoundTriangle { a, b, c, border_radius } =>
-            Some(Collider::round_triangle(*a, *b, *c, *border_radius)),
-    }
-}
+// Kept here for existing callers; the implementation lives in core::collider
+pub use crate::core::collider::create_collider;
 
+// The generator and the seed it was created from (reported in logs and spans)
 #[derive(Resource)]
-pub struct GenRng(WyRand);
+pub struct GenRng(WyRand, u64);
 
 impl GenRng {
 
     pub fn new(seed: u64) -> Self {
-        GenRng(WyRand::seed_from_u64(seed)) // Use the appropriate public method to create WyRand
+        GenRng(WyRand::seed_from_u64(seed), seed) // Use the appropriate public method to create WyRand
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.1
     }
 
     pub fn rng_mut(&mut self) -> &mut WyRand {
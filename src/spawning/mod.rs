@@ -3,4 +3,10 @@ pub mod transformation;
 pub mod light_spawning;
 pub mod helpers;
 pub mod object_logic;
+pub mod occupancy;
 pub mod euler_transform;
+pub mod path_solver;
+pub mod path_shaping;
+pub mod pathfinding;
+pub mod agent_navmesh;
+pub mod nav_links;
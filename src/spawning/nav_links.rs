@@ -0,0 +1,291 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use bevy::prelude::*;
+use oxidized_navigation::{Area, NavMeshAffector, NavMeshAreaType};
+use crate::core::navmesh_config::NavAreaType;
+use crate::event_system::generation_log::PATH;
+
+// Off-mesh connection placed by StructureKey::NavLink; `from` and `to` are local to the entity
+#[derive(Component, Debug, Clone)]
+pub struct NavLinkMarker {
+    pub from: Vec3,
+    pub to: Vec3,
+    pub bidirectional: bool,
+    pub cost: f32,
+}
+
+// Volume placed by StructureKey::NavArea. Its collider becomes a navmesh affector with the area type
+// once the navmesh is built.
+#[derive(Component, Debug, Clone)]
+pub struct NavAreaVolume {
+    pub area_type: NavAreaType,
+    pub cost: Option<f32>,
+}
+
+// A registered off-mesh connection in world space
+#[derive(Debug, Clone, Copy)]
+pub struct NavLinkSpan {
+    pub from: Vec3,
+    pub to: Vec3,
+    pub bidirectional: bool,
+    // Multiplier on the straight-line length of the crossing
+    pub cost: f32,
+}
+
+// Off-mesh connections of the generated world, registered on entering NavMeshBuilding
+#[derive(Resource, Default, Clone)]
+pub struct NavLinks(pub Vec<NavLinkSpan>);
+
+// Path cost multiplier per navmesh area id (missing ids cost 1), as oxidized_navigation takes them
+#[derive(Resource, Default, Clone)]
+pub struct NavAreaCosts(pub Vec<f32>);
+
+impl NavAreaCosts {
+    pub fn multipliers(&self) -> Option<&[f32]> {
+        (!self.0.is_empty()).then_some(self.0.as_slice())
+    }
+}
+
+// On entering NavMeshBuilding, collect the links and turn the area volumes into navmesh affectors
+pub fn register_nav_connections(
+    mut commands: Commands,
+    links: Query<(&NavLinkMarker, &GlobalTransform)>,
+    areas: Query<(Entity, &NavAreaVolume), Without<NavMeshAffector>>,
+    all_areas: Query<&NavAreaVolume>,
+    mut nav_links: ResMut<NavLinks>,
+    mut area_costs: ResMut<NavAreaCosts>,
+) {
+    nav_links.0 = links.iter()
+        .map(|(link, gt)| NavLinkSpan {
+            from: gt.transform_point(link.from),
+            to: gt.transform_point(link.to),
+            bidirectional: link.bidirectional,
+            cost: link.cost.max(0.0),
+        })
+        .collect();
+
+    area_costs.0.clear();
+    for volume in all_areas.iter() {
+        let (NavAreaType::Area(id), Some(cost)) = (volume.area_type, volume.cost) else { continue; };
+        let id = id as usize;
+        if area_costs.0.len() <= id { area_costs.0.resize(id + 1, 1.0); }
+        area_costs.0[id] = cost.max(0.0);
+    }
+    for (entity, volume) in areas.iter() {
+        let area = match volume.area_type {
+            NavAreaType::Area(id) => Some(Area(id)),
+            NavAreaType::Blocked => None,
+        };
+        commands.entity(entity).insert((NavMeshAreaType(area), NavMeshAffector));
+    }
+    debug!(target: PATH, links = nav_links.0.len(), area_costs = area_costs.0.len(), "registered navmesh links and areas");
+}
+
+// Links considered per search: those with an end where the detour through it is at most LINK_DETOUR
+// times the straight start-end distance (plus LINK_DETOUR_SLACK meters), nearest to that line first
+const MAX_SEARCH_LINKS: usize = 8;
+const LINK_DETOUR: f32 = 2.0;
+const LINK_DETOUR_SLACK: f32 = 10.0;
+
+fn distance_to_segment(p: Vec3, a: Vec3, b: Vec3) -> f32 {
+    let ab = b - a;
+    let t = if ab.length_squared() > 1.0e-8 { ((p - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0) } else { 0.0 };
+    p.distance(a + ab * t)
+}
+
+fn point_key(p: Vec3) -> [u32; 3] {
+    [p.x.to_bits(), p.y.to_bits(), p.z.to_bits()]
+}
+
+// Walks found by earlier searches and their costs, keyed by their exact end points. Link ends are the
+// same in every search over one set of links, so searches sharing a cache (the tries of one path
+// request, or the pairs of a connectivity check) walk between any two of them once.
+#[derive(Default)]
+pub struct LinkWalks(HashMap<([u32; 3], [u32; 3]), Option<Walk>>);
+
+// A walked polyline and its cost
+type Walk = (Vec<Vec3>, f32);
+
+impl LinkWalks {
+    fn get_or_walk(
+        &mut self,
+        from: Vec3,
+        to: Vec3,
+        walk: &mut impl FnMut(Vec3, Vec3) -> Option<Vec<Vec3>>,
+        walk_cost: &impl Fn(&[Vec3]) -> f32,
+    ) -> Option<&Walk> {
+        self.0.entry((point_key(from), point_key(to)))
+            .or_insert_with(|| walk(from, to).map(|points| { let cost = walk_cost(&points); (points, cost) }))
+            .as_ref()
+    }
+}
+
+#[derive(PartialEq)]
+struct Open {
+    cost: f32,
+    node: usize,
+}
+
+impl Eq for Open {}
+
+impl Ord for Open {
+    // Reversed so BinaryHeap pops the cheapest first
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost).then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+impl PartialOrd for Open {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Cheapest route from start to end that may cross off-mesh links, walking between them with `walk`
+// (a navmesh search) weighted by `walk_cost` (the solver's cost of a walked polyline). Without links
+// nearby this is `walk(start, end)`.
+pub fn find_path_with_links(
+    links: &[NavLinkSpan],
+    start: Vec3,
+    end: Vec3,
+    walks: &mut LinkWalks,
+    mut walk: impl FnMut(Vec3, Vec3) -> Option<Vec<Vec3>>,
+    walk_cost: impl Fn(&[Vec3]) -> f32,
+) -> Option<Vec<Vec3>> {
+    let reach = start.distance(end) * LINK_DETOUR + LINK_DETOUR_SLACK;
+    let in_corridor = |p: Vec3| p.distance(start) + p.distance(end) <= reach;
+    let mut nearby: Vec<&NavLinkSpan> = links.iter().filter(|link| in_corridor(link.from) || in_corridor(link.to)).collect();
+    if nearby.is_empty() {
+        return walks.get_or_walk(start, end, &mut walk, &walk_cost).map(|(points, _)| points.clone());
+    }
+    nearby.sort_by(|a, b| {
+        let da = distance_to_segment(a.from, start, end).min(distance_to_segment(a.to, start, end));
+        let db = distance_to_segment(b.from, start, end).min(distance_to_segment(b.to, start, end));
+        da.total_cmp(&db)
+    });
+    nearby.truncate(MAX_SEARCH_LINKS);
+
+    // Nodes: 0 start, 1 end, then each link's `from` and `to`. Each link end has at most one crossing.
+    let mut nodes = vec![start, end];
+    let mut crossings: Vec<Option<(usize, f32)>> = vec![None, None];
+    for (i, link) in nearby.iter().enumerate() {
+        let (a, b) = (2 + i * 2, 3 + i * 2);
+        let cost = link.from.distance(link.to) * link.cost;
+        nodes.push(link.from);
+        nodes.push(link.to);
+        crossings.push(Some((b, cost)));
+        crossings.push(link.bidirectional.then_some((a, cost)));
+    }
+    // Walking on after a walk is never cheaper than walking there directly, so walks only leave the
+    // start and the far ends of crossings, and only lead to the end or to where a crossing starts
+    let walk_targets: Vec<usize> = (1..nodes.len()).filter(|&n| n == 1 || crossings[n].is_some()).collect();
+
+    // Dijkstra; walking edges are searched lazily when their node is settled
+    let mut best: Vec<f32> = vec![f32::INFINITY; nodes.len()];
+    let mut came_from: Vec<Option<(usize, Option<Vec<Vec3>>)>> = vec![None; nodes.len()];
+    let mut settled = vec![false; nodes.len()];
+    let mut open = BinaryHeap::new();
+    best[0] = 0.0;
+    open.push(Open { cost: 0.0, node: 0 });
+    while let Some(Open { cost, node }) = open.pop() {
+        if settled[node] { continue; }
+        settled[node] = true;
+        if node == 1 { break; }
+        if let Some((next, step_cost)) = crossings[node] {
+            if !settled[next] && cost + step_cost < best[next] {
+                best[next] = cost + step_cost;
+                came_from[next] = Some((node, None));
+                open.push(Open { cost: best[next], node: next });
+            }
+        }
+        let walked_here = came_from[node].as_ref().is_some_and(|(_, segment)| segment.is_some());
+        if walked_here { continue; }
+        for &next in walk_targets.iter() {
+            if settled[next] || next == node { continue; }
+            let Some((points, step_cost)) = walks.get_or_walk(nodes[node], nodes[next], &mut walk, &walk_cost) else { continue; };
+            if cost + step_cost < best[next] {
+                best[next] = cost + step_cost;
+                came_from[next] = Some((node, Some(points.clone())));
+                open.push(Open { cost: best[next], node: next });
+            }
+        }
+    }
+    if !settled[1] { return None; }
+
+    let mut pieces: Vec<Vec<Vec3>> = Vec::new();
+    let mut node = 1;
+    while let Some((prev, segment)) = came_from[node].take() {
+        pieces.push(segment.unwrap_or_else(|| vec![nodes[prev], nodes[node]]));
+        node = prev;
+    }
+    pieces.reverse();
+    let mut points: Vec<Vec3> = Vec::new();
+    for piece in pieces {
+        for p in piece {
+            if points.last().is_none_or(|last| last.distance(p) > 1.0e-4) { points.push(p); }
+        }
+    }
+    Some(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Ground on both sides of a gap at x = 0; walks are straight lines and cannot cross the gap
+    fn walk_sides(calls: &mut usize) -> impl FnMut(Vec3, Vec3) -> Option<Vec<Vec3>> + '_ {
+        move |a, b| {
+            *calls += 1;
+            (a.x.signum() == b.x.signum()).then(|| vec![a, b])
+        }
+    }
+
+    fn length(points: &[Vec3]) -> f32 {
+        points.windows(2).map(|w| w[0].distance(w[1])).sum()
+    }
+
+    fn bridge(z: f32) -> NavLinkSpan {
+        NavLinkSpan { from: Vec3::new(-1.0, 0.0, z), to: Vec3::new(1.0, 0.0, z), bidirectional: false, cost: 1.0 }
+    }
+
+    #[test]
+    fn crosses_a_link_over_the_gap() {
+        let mut calls = 0;
+        let (start, end) = (Vec3::new(-5.0, 0.0, 0.0), Vec3::new(5.0, 0.0, 0.0));
+        let path = find_path_with_links(&[bridge(2.0)], start, end, &mut LinkWalks::default(), walk_sides(&mut calls), length).unwrap();
+        assert_eq!(path, vec![start, Vec3::new(-1.0, 0.0, 2.0), Vec3::new(1.0, 0.0, 2.0), end]);
+    }
+
+    #[test]
+    fn links_off_the_corridor_are_not_walked_to() {
+        let mut calls = 0;
+        let (start, end) = (Vec3::new(-5.0, 0.0, 0.0), Vec3::new(-3.0, 0.0, 0.0));
+        let path = find_path_with_links(&[bridge(500.0)], start, end, &mut LinkWalks::default(), walk_sides(&mut calls), length);
+        assert_eq!(path, Some(vec![start, end]));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn walks_are_shared_between_searches() {
+        let mut calls = 0;
+        let links = [bridge(2.0), bridge(-2.0)];
+        let mut walks = LinkWalks::default();
+        let (start, end) = (Vec3::new(-5.0, 0.0, 0.0), Vec3::new(5.0, 0.0, 0.0));
+        find_path_with_links(&links, start, end, &mut walks, walk_sides(&mut calls), length).unwrap();
+        let first = calls;
+        calls = 0;
+        find_path_with_links(&links, start, end, &mut walks, walk_sides(&mut calls), length).unwrap();
+        assert!(first > 0);
+        assert_eq!(calls, 0);
+    }
+
+    #[test]
+    fn walks_are_weighted_by_their_cost() {
+        let walk = |a: Vec3, b: Vec3| Some(vec![a, b]);
+        // Walking along z = 0 costs ten times its length, so the detour over the link at z = 2 wins
+        let cost = |points: &[Vec3]| if points.iter().all(|p| p.z == 0.0) { length(points) * 10.0 } else { length(points) };
+        let (start, end) = (Vec3::new(-5.0, 0.0, 0.0), Vec3::new(5.0, 0.0, 0.0));
+        let path = find_path_with_links(&[bridge(2.0)], start, end, &mut LinkWalks::default(), walk, cost).unwrap();
+        assert_eq!(path.len(), 4);
+    }
+}
@@ -1,7 +1,7 @@
 use bevy::ecs::reflect::ReflectComponent;
 use bevy::app::{App, Plugin};
 use bevy::math::Vec3;
-use bevy::prelude::{Component, Reflect};
+use bevy::prelude::{Color, Component, Reflect, Resource};
 use bevy::tasks::Task;
 use bevy_inspector_egui::InspectorOptions;
 use serde::{Deserialize, Serialize};
@@ -26,7 +26,42 @@ pub enum Ownership {
     Inherit,
 }
 
-#[derive(InspectorOptions, Serialize, Deserialize, Debug, Clone, Component, Reflect)]
+// Colours used to tint team-owned objects. Submeshes of an object owned by `Ownership::Team(id)`
+// whose glTF name matches `mesh_pattern` get `colors[id]` (wrapping) as their base colour.
+#[derive(Resource, Debug, Clone)]
+pub struct TeamPalette {
+    pub colors: Vec<Color>,
+    pub mesh_pattern: String,
+}
+
+impl Default for TeamPalette {
+    fn default() -> Self {
+        TeamPalette {
+            colors: vec![
+                Color::srgb(0.80, 0.15, 0.15),
+                Color::srgb(0.15, 0.35, 0.85),
+                Color::srgb(0.15, 0.65, 0.20),
+                Color::srgb(0.90, 0.75, 0.10),
+                Color::srgb(0.55, 0.20, 0.75),
+                Color::srgb(0.95, 0.50, 0.10),
+                Color::srgb(0.10, 0.70, 0.75),
+                Color::srgb(0.85, 0.85, 0.85),
+            ],
+            mesh_pattern: "*_team".to_string(),
+        }
+    }
+}
+
+impl TeamPalette {
+    pub fn color(&self, team_id: u8) -> Option<Color> {
+        if self.colors.is_empty() {
+            return None;
+        }
+        Some(self.colors[team_id as usize % self.colors.len()])
+    }
+}
+
+#[derive(InspectorOptions, Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Component, Reflect)]
 #[reflect(Component, InspectorOptions)]
 pub enum ObjectType {
     Building,
@@ -43,14 +78,24 @@ pub struct Selectable {
     pub is_selected: bool,
 }
 
+// Driven by the pathfinding systems: send a PathfinderGoal to search, then the unit walks the path
 #[derive(Component)]
 pub struct Pathfinder {
     pub path: PathState,
+    // Meters per second
+    pub speed: f32,
+    // How close a waypoint must be before the unit moves on to the next one
+    pub arrival_radius: f32,
+    // Set when the navmesh changes under a running search; its result is dropped for a fresh search
+    pub replan: bool,
 }
 
+// Resolves to the path (None when the navmesh could not be read) and the goal it was searched for
+pub type PathTask = Task<(Result<Option<Vec<Vec3>>, FindPathError>, Vec3)>;
+
 pub enum PathState {
     Ready(Vec3),
-    Calculating(Task<(Result<Option<Vec<Vec3>>, FindPathError>, Vec3)>),
+    Calculating(PathTask),
     Pathing(Vec<Vec3>),
 }
 
@@ -58,6 +103,9 @@ impl Default for Pathfinder {
     fn default() -> Self {
         Pathfinder {
             path: PathState::Ready(Vec3::ZERO),
+            speed: 4.0,
+            arrival_radius: 0.25,
+            replan: false,
         }
     }
 }
\ No newline at end of file
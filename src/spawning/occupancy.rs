@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+use bevy::prelude::*;
+use crate::core::collider::{transformed_corners, ColliderInfo, ColliderType};
+use crate::core::primitive_shape::MeshCollider;
+use crate::core::structure::Structure;
+use crate::core::structure_key::StructureKey;
+use crate::core::structure_reference::StructureReference;
+
+// How deep Nest keys are followed when predicting a structure's footprint
+const MAX_FOOTPRINT_DEPTH: u8 = 4;
+
+// Collider bounds of one object inside a structure, in the structure's local space
+#[derive(Debug, Clone, Copy)]
+pub struct LocalFootprint {
+    pub min: Vec3,
+    pub max: Vec3,
+    pub priority: i8,
+}
+
+impl LocalFootprint {
+    pub fn from_collider(info: &ColliderInfo, transform: &Transform) -> Option<Self> {
+        Self::from_collider_type(&info.collider_type, info.priority, transform)
+    }
+
+    pub fn from_collider_type(collider_type: &ColliderType, priority: i8, transform: &Transform) -> Option<Self> {
+        let (min, max) = collider_type.local_bounds()?;
+        let corners = transformed_corners(min, max, transform);
+        Some(LocalFootprint {
+            min: corners.iter().copied().fold(Vec3::INFINITY, Vec3::min),
+            max: corners.iter().copied().fold(Vec3::NEG_INFINITY, Vec3::max),
+            priority,
+        })
+    }
+}
+
+// Set on a structure container whose predicted footprint a scatter spawner already claimed, so the
+// objects it spawns are not registered again. Holds the Nest depth inside the claimed structure.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct OccupancyClaimed(pub u8);
+
+impl OccupancyClaimed {
+    // Claim for a Nest key of the claimed structure, as far as structure_footprint follows them
+    pub fn nested(&self) -> Option<Self> {
+        (self.0 < MAX_FOOTPRINT_DEPTH).then_some(OccupancyClaimed(self.0 + 1))
+    }
+}
+
+// Horizontal (XZ) world-space rectangle occupied by a placed object
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Footprint {
+    pub min: Vec2,
+    pub max: Vec2,
+    pub priority: i8,
+}
+
+impl Footprint {
+    pub fn from_local(local: &LocalFootprint, world: &Transform) -> Self {
+        let corners = transformed_corners(local.min, local.max, world);
+        let (mut min, mut max) = (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY));
+        for corner in corners {
+            min = min.min(corner.xz());
+            max = max.max(corner.xz());
+        }
+        Footprint { min, max, priority: local.priority }
+    }
+
+    pub fn overlaps(&self, other: &Footprint) -> bool {
+        self.min.x < other.max.x && other.min.x < self.max.x
+            && self.min.y < other.max.y && other.min.y < self.max.y
+    }
+}
+
+pub fn footprints_at(local: &[LocalFootprint], world: &Transform) -> Vec<Footprint> {
+    local.iter().map(|fp| Footprint::from_local(fp, world)).collect()
+}
+
+// Footprints of the colliders a reference is known to spawn: its Object keys and explicitly authored
+// Mesh colliders, following Nest keys. Randomised keys (Rand, Choose, ...) cannot be predicted and
+// are left to the physics-based overlap resolution.
+pub fn structure_footprint(reference: &StructureReference) -> Vec<LocalFootprint> {
+    let mut out = Vec::new();
+    if let Ok(structure) = Structure::try_from(reference) {
+        collect_footprints(&structure, Transform::IDENTITY, 0, &mut out);
+    }
+    out
+}
+
+fn collect_footprints(structure: &Structure, parent: Transform, depth: u8, out: &mut Vec<LocalFootprint>) {
+    for (key, local) in &structure.data {
+        let transform = parent * Transform::from(local.clone());
+        match key {
+            StructureKey::Object { collider: Some(info), .. }
+            | StructureKey::Mesh { collider: MeshCollider::Explicit(info), .. } => {
+                out.extend(LocalFootprint::from_collider(info, &transform));
+            }
+            StructureKey::Nest(reference) if depth < MAX_FOOTPRINT_DEPTH => {
+                if let Ok(nested) = Structure::try_from(reference) {
+                    collect_footprints(&nested, transform, depth + 1, out);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// Optional pre-spawn broadphase of already placed footprints. When enabled, scatter spawners
+// (NoiseSpawn, PathSpawn, RandDistDir, Loop) reject placements that would land on an object of equal
+// or higher ColliderPriority, and RandDistDir draws up to max_retries new points first. This is
+// immediate and deterministic; combine it with `collision_despawn: false` passes to skip the physics step.
+#[derive(Resource, Debug)]
+pub struct OccupancyGrid {
+    pub enabled: bool,
+    pub cell_size: f32,
+    pub max_retries: u8,
+    // Placements rejected during the current generation run
+    pub rejected: u32,
+    footprints: Vec<Footprint>,
+    cells: HashMap<IVec2, Vec<usize>>,
+}
+
+impl Default for OccupancyGrid {
+    fn default() -> Self {
+        OccupancyGrid {
+            enabled: false,
+            cell_size: 4.0,
+            max_retries: 8,
+            rejected: 0,
+            footprints: Vec::new(),
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl OccupancyGrid {
+    fn cells_of(&self, footprint: &Footprint) -> impl Iterator<Item = IVec2> {
+        let size = self.cell_size.max(0.01);
+        let min = (footprint.min / size).floor().as_ivec2();
+        let max = (footprint.max / size).floor().as_ivec2();
+        (min.x..=max.x).flat_map(move |x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+    }
+
+    // Whether none of `candidate` overlaps a placed footprint of equal or higher priority
+    pub fn is_free(&self, candidate: &[Footprint]) -> bool {
+        candidate.iter().all(|fp| {
+            self.cells_of(fp).all(|cell| {
+                self.cells.get(&cell).is_none_or(|indices| {
+                    indices.iter().all(|&i| {
+                        let placed = &self.footprints[i];
+                        placed.priority < fp.priority || !placed.overlaps(fp)
+                    })
+                })
+            })
+        })
+    }
+
+    pub fn insert(&mut self, footprints: &[Footprint]) {
+        for fp in footprints {
+            let index = self.footprints.len();
+            self.footprints.push(*fp);
+            let cells: Vec<IVec2> = self.cells_of(fp).collect();
+            for cell in cells {
+                self.cells.entry(cell).or_default().push(index);
+            }
+        }
+    }
+
+    // Place `candidate` if it is free; otherwise count a rejection
+    pub fn try_claim(&mut self, candidate: &[Footprint]) -> bool {
+        self.place_first(std::iter::once(((), candidate.to_vec()))).is_some()
+    }
+
+    // Place the first candidate whose footprints are free and return its payload. When every
+    // candidate collides, nothing is placed and a single rejection is counted.
+    pub fn place_first<T>(&mut self, candidates: impl IntoIterator<Item = (T, Vec<Footprint>)>) -> Option<T> {
+        for (payload, footprints) in candidates {
+            if self.is_free(&footprints) {
+                self.insert(&footprints);
+                return Some(payload);
+            }
+        }
+        self.rejected = self.rejected.saturating_add(1);
+        None
+    }
+
+    pub fn len(&self) -> usize {
+        self.footprints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.footprints.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.footprints.clear();
+        self.cells.clear();
+        self.rejected = 0;
+    }
+}
+
+pub fn reset_occupancy_grid(mut grid: ResMut<OccupancyGrid>) {
+    grid.clear();
+}
+
+// World transform of an entity composed from local Transforms, since GlobalTransform has not been
+// propagated yet for containers spawned earlier in the same frame
+pub fn world_transform(entity: Option<Entity>, hierarchy: &Query<(&Transform, Option<&Parent>)>) -> Transform {
+    let mut result = Transform::IDENTITY;
+    let mut current = entity;
+    while let Some(e) = current {
+        let Ok((transform, parent)) = hierarchy.get(e) else { break; };
+        result = *transform * result;
+        current = parent.map(|p| p.get());
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(min: (f32, f32), max: (f32, f32), priority: i8) -> Footprint {
+        Footprint { min: Vec2::new(min.0, min.1), max: Vec2::new(max.0, max.1), priority }
+    }
+
+    fn grid() -> OccupancyGrid {
+        OccupancyGrid { enabled: true, cell_size: 4.0, ..default() }
+    }
+
+    #[test]
+    fn is_free_rejects_equal_or_higher_priority_only() {
+        let mut grid = grid();
+        grid.insert(&[rect((0.0, 0.0), (2.0, 2.0), 1)]);
+        assert!(!grid.is_free(&[rect((1.0, 1.0), (3.0, 3.0), 1)]), "equal priority collides");
+        assert!(!grid.is_free(&[rect((1.0, 1.0), (3.0, 3.0), 0)]), "lower priority collides");
+        assert!(grid.is_free(&[rect((1.0, 1.0), (3.0, 3.0), 2)]), "higher priority may be placed over it");
+        // Edges that only touch do not overlap
+        assert!(grid.is_free(&[rect((2.0, 0.0), (4.0, 2.0), 1)]));
+        // Every footprint of a multi-part candidate has to be free
+        assert!(!grid.is_free(&[rect((10.0, 10.0), (11.0, 11.0), 1), rect((1.5, 1.5), (2.5, 2.5), 1)]));
+    }
+
+    #[test]
+    fn buckets_span_negative_coordinates() {
+        let mut grid = grid();
+        // Straddles the origin, so it lands in cells on both sides of zero
+        grid.insert(&[rect((-1.0, -1.0), (1.0, 1.0), 0)]);
+        let cells: Vec<IVec2> = grid.cells_of(&rect((-1.0, -1.0), (1.0, 1.0), 0)).collect();
+        assert_eq!(cells, vec![IVec2::new(-1, -1), IVec2::new(-1, 0), IVec2::new(0, -1), IVec2::new(0, 0)]);
+        for probe in [(-0.9, -0.9), (-0.9, 0.5), (0.5, -0.9), (0.5, 0.5)] {
+            let p = Vec2::new(probe.0, probe.1);
+            assert!(!grid.is_free(&[rect((p.x, p.y), (p.x + 0.1, p.y + 0.1), 0)]), "overlap at {:?} is found", probe);
+        }
+        // -4.5 floors into cell -2, which the footprint never reached
+        assert!(grid.is_free(&[rect((-4.5, -4.5), (-4.1, -4.1), 0)]));
+    }
+
+    #[test]
+    fn place_first_counts_one_rejection_per_call() {
+        let mut grid = grid();
+        grid.insert(&[rect((0.0, 0.0), (2.0, 2.0), 1)]);
+        let blocked = || vec![rect((0.5, 0.5), (1.0, 1.0), 1)];
+        assert_eq!(grid.place_first([(1, blocked()), (2, blocked()), (3, blocked())]), None);
+        assert_eq!(grid.rejected, 1, "all candidates failing is a single rejection");
+        assert_eq!(grid.len(), 1, "nothing was placed");
+
+        assert_eq!(grid.place_first([(1, blocked()), (2, vec![rect((5.0, 5.0), (6.0, 6.0), 1)])]), Some(2));
+        assert_eq!(grid.rejected, 1, "placing a later candidate is not a rejection");
+        assert_eq!(grid.len(), 2);
+
+        assert!(!grid.try_claim(&blocked()));
+        assert_eq!(grid.rejected, 2);
+    }
+
+    #[test]
+    fn claims_follow_nest_keys_as_deep_as_footprints() {
+        let mut claim = Some(OccupancyClaimed::default());
+        for _ in 0..MAX_FOOTPRINT_DEPTH {
+            claim = claim.and_then(|c| c.nested());
+            assert!(claim.is_some());
+        }
+        assert!(claim.and_then(|c| c.nested()).is_none());
+    }
+}
@@ -0,0 +1,317 @@
+use bevy::prelude::*;
+use crate::core::path_shaping::{PathShaping, PathSmoothing};
+use crate::spawning::path_solver::PathQuery;
+
+// Spacing of the samples the walkability check takes along a shaped path, in meters
+const WALKABLE_STEP: f32 = 0.25;
+// How far a sample may sit from the navmesh horizontally and still count as on it
+const WALKABLE_TOLERANCE: f32 = 0.05;
+
+// Apply every enabled shaping step to a polyline; the first and last points are kept as they are
+pub fn shape_path(points: &[Vec3], shaping: &PathShaping) -> Vec<Vec3> {
+    if points.len() < 2 { return points.to_vec(); }
+    let mut shaped = simplify(points, shaping.simplify_tolerance);
+    if shaping.corner_radius > 0.0 {
+        shaped = round_corners(&shaped, shaping.corner_radius);
+    }
+    shaped = match shaping.smoothing {
+        PathSmoothing::None => shaped,
+        PathSmoothing::Chaikin { iterations } => chaikin(&shaped, iterations),
+        PathSmoothing::CatmullRom { samples_per_segment } => catmull_rom(&shaped, samples_per_segment),
+    };
+    if shaping.min_turn_radius > 0.0 {
+        shaped = enforce_turn_radius(shaped, shaping.min_turn_radius);
+    }
+    if shaping.resample_spacing > 0.0 {
+        shaped = resample(&shaped, shaping.resample_spacing);
+    }
+    shaped
+}
+
+// Shape a navmesh path, keeping it walkable: when the shaped curve leaves the navmesh (or comes closer
+// than `clearance` to its edge), gentler shaping is tried, and the unshaped path is returned last.
+pub fn shape_walkable_path(points: &[Vec3], shaping: &PathShaping, query: &PathQuery) -> Vec<Vec3> {
+    let mut attempt = shaping.clone();
+    for _ in 0..3 {
+        let shaped = shape_path(points, &attempt);
+        if is_walkable(&shaped, shaping.clearance, query) {
+            return shaped;
+        }
+        attempt = soften(&attempt);
+    }
+    points.to_vec()
+}
+
+fn soften(shaping: &PathShaping) -> PathShaping {
+    PathShaping {
+        simplify_tolerance: shaping.simplify_tolerance * 0.5,
+        corner_radius: shaping.corner_radius * 0.5,
+        smoothing: match shaping.smoothing {
+            PathSmoothing::Chaikin { iterations } if iterations > 1 => PathSmoothing::Chaikin { iterations: iterations - 1 },
+            // Catmull-Rom can overshoot the corners; Chaikin stays inside them
+            PathSmoothing::CatmullRom { .. } => PathSmoothing::Chaikin { iterations: 2 },
+            _ => PathSmoothing::None,
+        },
+        min_turn_radius: shaping.min_turn_radius * 0.5,
+        ..shaping.clone()
+    }
+}
+
+fn on_navmesh(p: Vec3, query: &PathQuery) -> bool {
+    query.tiles
+        .find_closest_polygon_in_box(query.settings, p, 1.0)
+        .is_some_and(|(_, _, closest)| closest.xz().distance(p.xz()) <= WALKABLE_TOLERANCE)
+}
+
+// Whether samples along the polyline, and points `clearance` to either side of them, are on the navmesh
+pub fn is_walkable(points: &[Vec3], clearance: f32, query: &PathQuery) -> bool {
+    for w in points.windows(2) {
+        let length = w[0].distance(w[1]);
+        let side = Vec3::Y.cross(w[1] - w[0]).normalize_or_zero();
+        let steps = (length / WALKABLE_STEP).ceil().max(1.0) as usize;
+        for i in 0..=steps {
+            let p = w[0].lerp(w[1], i as f32 / steps as f32);
+            if !on_navmesh(p, query) { return false; }
+            if clearance > 0.0 && (!on_navmesh(p + side * clearance, query) || !on_navmesh(p - side * clearance, query)) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+fn distance_to_segment(p: Vec3, a: Vec3, b: Vec3) -> f32 {
+    let ab = b - a;
+    let t = if ab.length_squared() > 1.0e-8 { ((p - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0) } else { 0.0 };
+    p.distance(a + ab * t)
+}
+
+// Douglas-Peucker
+fn simplify(points: &[Vec3], tolerance: f32) -> Vec<Vec3> {
+    if points.len() < 3 || tolerance <= 0.0 { return points.to_vec(); }
+    let last = points.len() - 1;
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[last] = true;
+    let mut stack = vec![(0, last)];
+    while let Some((a, b)) = stack.pop() {
+        let mut worst = (0.0, a);
+        for i in a + 1..b {
+            let d = distance_to_segment(points[i], points[a], points[b]);
+            if d > worst.0 { worst = (d, i); }
+        }
+        if worst.0 > tolerance {
+            keep[worst.1] = true;
+            stack.push((a, worst.1));
+            stack.push((worst.1, b));
+        }
+    }
+    points.iter().zip(keep).filter(|(_, k)| *k).map(|(p, _)| *p).collect()
+}
+
+// Replace every interior corner with a circular arc tangent to both segments
+fn round_corners(points: &[Vec3], radius: f32) -> Vec<Vec3> {
+    if points.len() < 3 { return points.to_vec(); }
+    let mut out = vec![points[0]];
+    for i in 1..points.len() - 1 {
+        let (a, b, c) = (points[i - 1], points[i], points[i + 1]);
+        let to_a = (a - b).normalize_or_zero();
+        let to_c = (c - b).normalize_or_zero();
+        let angle = to_a.angle_between(to_c);
+        // Nearly straight, or folding back on itself: keep the corner point
+        if !(0.01..std::f32::consts::PI - 0.01).contains(&angle) {
+            out.push(b);
+            continue;
+        }
+        // Tangent points sit where the arc meets each segment; shrink the radius so they stay within
+        // half of each segment
+        let half = angle * 0.5;
+        let max_tangent = (a.distance(b).min(c.distance(b)) * 0.5).max(0.0);
+        let tangent = (radius / half.tan()).min(max_tangent);
+        if tangent <= 1.0e-4 {
+            out.push(b);
+            continue;
+        }
+        let r = tangent * half.tan();
+        let start = b + to_a * tangent;
+        let end = b + to_c * tangent;
+        let centre = b + (to_a + to_c).normalize() * (r / half.sin());
+        let from = start - centre;
+        let rotation = Quat::from_rotation_arc(from.normalize(), (end - centre).normalize());
+        let sweep = std::f32::consts::PI - angle;
+        let segments = (sweep / (std::f32::consts::PI / 12.0)).ceil().max(1.0) as usize;
+        for k in 0..=segments {
+            out.push(centre + Quat::IDENTITY.slerp(rotation, k as f32 / segments as f32) * from);
+        }
+    }
+    out.push(points[points.len() - 1]);
+    out
+}
+
+fn chaikin(points: &[Vec3], iterations: u8) -> Vec<Vec3> {
+    let mut current = points.to_vec();
+    for _ in 0..iterations {
+        if current.len() < 3 { break; }
+        let mut next = Vec::with_capacity(current.len() * 2);
+        next.push(current[0]);
+        for w in current.windows(2) {
+            next.push(w[0].lerp(w[1], 0.25));
+            next.push(w[0].lerp(w[1], 0.75));
+        }
+        next.push(current[current.len() - 1]);
+        current = next;
+    }
+    current
+}
+
+// Uniform Catmull-Rom through every point, with mirrored end points so the curve reaches both ends
+fn catmull_rom(points: &[Vec3], samples_per_segment: u8) -> Vec<Vec3> {
+    if points.len() < 3 || samples_per_segment == 0 { return points.to_vec(); }
+    let n = points.len();
+    let at = |i: isize| -> Vec3 {
+        if i < 0 { 2.0 * points[0] - points[1] }
+        else if i as usize >= n { 2.0 * points[n - 1] - points[n - 2] }
+        else { points[i as usize] }
+    };
+    let mut out = Vec::with_capacity((n - 1) * samples_per_segment as usize + 1);
+    for i in 0..n - 1 {
+        let (p0, p1, p2, p3) = (at(i as isize - 1), at(i as isize), at(i as isize + 1), at(i as isize + 2));
+        for k in 0..samples_per_segment {
+            let t = k as f32 / samples_per_segment as f32;
+            let (t2, t3) = (t * t, t * t * t);
+            out.push(0.5 * (2.0 * p1 + (p2 - p0) * t + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2 + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3));
+        }
+    }
+    out.push(points[n - 1]);
+    out
+}
+
+// Radius of the circle through three points (infinite when they are collinear)
+fn turn_radius(a: Vec3, b: Vec3, c: Vec3) -> f32 {
+    let cross = (b - a).cross(c - b).length();
+    if cross <= 1.0e-8 { return f32::INFINITY; }
+    (b - a).length() * (c - b).length() * (c - a).length() / (2.0 * cross)
+}
+
+// Drop the corner point of the tightest bend until no bend is tighter than `min_radius`
+fn enforce_turn_radius(mut points: Vec<Vec3>, min_radius: f32) -> Vec<Vec3> {
+    while points.len() > 2 {
+        let tightest = (1..points.len() - 1)
+            .map(|i| (i, turn_radius(points[i - 1], points[i], points[i + 1])))
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        match tightest {
+            Some((i, r)) if r < min_radius => { points.remove(i); }
+            _ => break,
+        }
+    }
+    points
+}
+
+// Points at even arclength spacing, always ending on the last point
+fn resample(points: &[Vec3], spacing: f32) -> Vec<Vec3> {
+    if points.len() < 2 { return points.to_vec(); }
+    let mut out = vec![points[0]];
+    let mut carried = 0.0;
+    for w in points.windows(2) {
+        let length = w[0].distance(w[1]);
+        let mut s = spacing - carried;
+        while s < length {
+            out.push(w[0].lerp(w[1], s / length));
+            s += spacing;
+        }
+        carried = length - (s - spacing);
+    }
+    let last = points[points.len() - 1];
+    if out.last().is_some_and(|p| p.distance(last) > spacing * 0.5) || out.len() == 1 {
+        out.push(last);
+    } else if let Some(p) = out.last_mut() {
+        *p = last;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn xz(x: f32, z: f32) -> Vec3 {
+        Vec3::new(x, 0.0, z)
+    }
+
+    fn assert_ends(shaped: &[Vec3], points: &[Vec3]) {
+        assert_eq!(shaped.first(), points.first());
+        assert_eq!(shaped.last(), points.last());
+    }
+
+    #[test]
+    fn simplify_drops_only_points_within_tolerance() {
+        let points = [xz(0.0, 0.0), xz(1.0, 0.05), xz(2.0, 0.0), xz(3.0, 1.0), xz(4.0, 0.0)];
+        let simplified = simplify(&points, 0.1);
+        assert_eq!(simplified, vec![xz(0.0, 0.0), xz(2.0, 0.0), xz(3.0, 1.0), xz(4.0, 0.0)]);
+    }
+
+    #[test]
+    fn round_corners_stays_within_segment_halves() {
+        // The radius is far larger than the segments allow, so the arc is shrunk to fit
+        let points = [xz(0.0, 0.0), xz(2.0, 0.0), xz(2.0, 4.0)];
+        let rounded = round_corners(&points, 5.0);
+        assert_ends(&rounded, &points);
+        let arc = &rounded[1..rounded.len() - 1];
+        assert!(arc.len() > 2);
+        assert!(arc.first().unwrap().distance(xz(1.0, 0.0)) < 1.0e-4);
+        assert!(arc.last().unwrap().distance(xz(2.0, 1.0)) < 1.0e-4);
+        assert!(arc.iter().all(|p| p.x >= 1.0 - 1.0e-4 && p.x <= 2.0 + 1.0e-4 && p.z >= -1.0e-4 && p.z <= 1.0 + 1.0e-4));
+        // Every arc point sits on the circle tangent to both segments
+        let centre = xz(1.0, 1.0);
+        assert!(arc.iter().all(|p| (p.distance(centre) - 1.0).abs() < 1.0e-3));
+    }
+
+    #[test]
+    fn round_corners_keeps_straight_and_folded_corners() {
+        let points = [xz(0.0, 0.0), xz(1.0, 0.0), xz(2.0, 0.0), xz(0.5, 0.0)];
+        assert_eq!(round_corners(&points, 1.0), points.to_vec());
+    }
+
+    #[test]
+    fn chaikin_cuts_corners_and_keeps_ends() {
+        let points = [xz(0.0, 0.0), xz(4.0, 0.0), xz(4.0, 4.0)];
+        let smoothed = chaikin(&points, 2);
+        assert_ends(&smoothed, &points);
+        // Each pass keeps both ends and cuts every segment twice: 3 points, then 6, then 12
+        assert_eq!(smoothed.len(), 12);
+        assert!(!smoothed.contains(&xz(4.0, 0.0)));
+    }
+
+    #[test]
+    fn catmull_rom_passes_through_every_point() {
+        let points = [xz(0.0, 0.0), xz(4.0, 0.0), xz(4.0, 4.0), xz(8.0, 6.0)];
+        let smoothed = catmull_rom(&points, 5);
+        assert_ends(&smoothed, &points);
+        assert_eq!(smoothed.len(), 3 * 5 + 1);
+        for (i, p) in points.iter().enumerate() {
+            assert!(smoothed[i * 5].distance(*p) < 1.0e-4);
+        }
+    }
+
+    #[test]
+    fn enforce_turn_radius_drops_tight_bends() {
+        let points = vec![xz(0.0, 0.0), xz(1.0, 0.0), xz(1.0, 1.0), xz(2.0, 1.0), xz(12.0, 1.0)];
+        let relaxed = enforce_turn_radius(points.clone(), 2.0);
+        assert_ends(&relaxed, &points);
+        assert!(relaxed.windows(3).all(|w| turn_radius(w[0], w[1], w[2]) >= 2.0));
+        assert!(relaxed.len() < points.len());
+    }
+
+    #[test]
+    fn resample_spaces_points_evenly_and_ends_on_the_last_point() {
+        // Split across two segments so the spacing carries over the joint
+        let points = [xz(0.0, 0.0), xz(4.0, 0.0), xz(10.0, 0.0)];
+        let resampled = resample(&points, 3.0);
+        assert_eq!(resampled, vec![xz(0.0, 0.0), xz(3.0, 0.0), xz(6.0, 0.0), xz(10.0, 0.0)]);
+
+        let resampled = resample(&points, 4.0);
+        assert_ends(&resampled, &points);
+        assert!(resampled.windows(2).all(|w| (w[0].distance(w[1]) - 4.0).abs() < 1.0e-4 || w[1] == xz(10.0, 0.0)));
+        assert!(resampled.iter().rev().nth(1).unwrap().distance(xz(10.0, 0.0)) > 2.0);
+    }
+}
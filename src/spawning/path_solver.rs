@@ -0,0 +1,360 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::Arc;
+use bevy::prelude::*;
+use libnoise::{Generator, Source};
+use oxidized_navigation::NavMeshSettings;
+use oxidized_navigation::tiles::NavMeshTiles;
+use crate::core::generated_paths::polyline_length;
+use crate::core::path_cost::PathCost;
+use crate::core::seeded_or_not::SeededOrNot;
+use crate::core::tags::Tags;
+use crate::spawning::nav_links::NavLinkSpan;
+
+// Everything a solver may consult besides the start and end points
+pub struct PathQuery<'a> {
+    pub tiles: &'a NavMeshTiles,
+    pub settings: &'a NavMeshSettings,
+    // World position and tags of every tagged entity
+    pub tagged: &'a [(Vec3, &'a Tags)],
+    // World-space polylines of the paths resolved earlier in the run
+    pub roads: &'a [Vec<Vec3>],
+    // Run seed, used by unseeded noise costs
+    pub seed: u64,
+    // Off-mesh links of the run. Solvers search walkable ground only; the listeners cross links by
+    // wrapping every solver in find_path_with_links
+    pub links: &'a [NavLinkSpan],
+    // Cost multiplier per navmesh area id; NavMeshSolver and CostGridSolver both weight by it
+    pub area_costs: Option<&'a [f32]>,
+}
+
+pub trait PathSolver: Send + Sync + 'static {
+    // World-space polyline from start to end, or None when the end cannot be reached
+    fn find_path(&self, query: &PathQuery, start: Vec3, end: Vec3) -> Option<Vec<Vec3>>;
+
+    // Cost of walking a polyline this solver found, compared against link crossings by
+    // find_path_with_links. Defaults to its length weighted by the navmesh area costs.
+    fn path_cost(&self, query: &PathQuery, points: &[Vec3]) -> f32 {
+        area_weighted_length(query.tiles, query.settings, query.area_costs, points)
+    }
+}
+
+// Length of each step of area sampling along a walked polyline, in meters
+const AREA_SAMPLE_STEP: f32 = 1.0;
+
+// Length of a polyline on the navmesh with each stretch weighted by the area cost of the polygon under
+// it, sampled every AREA_SAMPLE_STEP; the plain length without area costs
+pub fn area_weighted_length(tiles: &NavMeshTiles, settings: &NavMeshSettings, area_costs: Option<&[f32]>, points: &[Vec3]) -> f32 {
+    let Some(costs) = area_costs else { return polyline_length(points); };
+    let mut total = 0.0;
+    for w in points.windows(2) {
+        let length = w[0].distance(w[1]);
+        let steps = (length / AREA_SAMPLE_STEP).ceil().max(1.0) as usize;
+        for k in 0..steps {
+            let p = w[0].lerp(w[1], (k as f32 + 0.5) / steps as f32);
+            let cost = tiles.find_closest_polygon_in_box(settings, p, AREA_SAMPLE_STEP)
+                .map_or(1.0, |(tile, polygon, _)| polygon_area_cost(tiles, costs, tile, polygon));
+            total += length / steps as f32 * cost;
+        }
+    }
+    total
+}
+
+// Shortest walkable route over the navmesh
+pub struct NavMeshSolver;
+
+impl PathSolver for NavMeshSolver {
+    fn find_path(&self, query: &PathQuery, start: Vec3, end: Vec3) -> Option<Vec<Vec3>> {
+        match oxidized_navigation::query::find_path(query.tiles, query.settings, start, end, None, query.area_costs) {
+            Ok(points) if points.len() >= 2 => Some(points),
+            _ => None,
+        }
+    }
+}
+
+// Solver for path keys without a cost model. Insert another to plug in a custom solver:
+// `app.insert_resource(DefaultPathSolver(Arc::new(MySolver)))`
+#[derive(Resource, Clone)]
+pub struct DefaultPathSolver(pub Arc<dyn PathSolver>);
+
+impl Default for DefaultPathSolver {
+    fn default() -> Self {
+        DefaultPathSolver(Arc::new(NavMeshSolver))
+    }
+}
+
+// Upper bound on the cells one search may lay out; long paths get a coarser grid instead
+const MAX_GRID_CELLS: usize = 128 * 128;
+
+// A* over a grid of navmesh samples, weighted by a PathCost and the area cost of the polygon under each
+// cell. Cells off the navmesh are not walkable. Off-mesh links are crossed by find_path_with_links,
+// which runs this solver for each walk between them.
+pub struct CostGridSolver {
+    pub cost: PathCost,
+}
+
+#[derive(PartialEq)]
+struct OpenCell {
+    f: f32,
+    cell: IVec2,
+}
+
+impl Eq for OpenCell {}
+
+impl Ord for OpenCell {
+    // Reversed so BinaryHeap pops the lowest f first; ties broken by cell for a deterministic order
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.f.total_cmp(&self.f).then_with(|| (other.cell.x, other.cell.y).cmp(&(self.cell.x, self.cell.y)))
+    }
+}
+
+impl PartialOrd for OpenCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Cost multiplier of the navmesh area a polygon belongs to (missing ids cost 1, as in find_path)
+fn area_cost(query: &PathQuery, tile: UVec2, polygon: u16) -> f32 {
+    query.area_costs.map_or(1.0, |costs| polygon_area_cost(query.tiles, costs, tile, polygon))
+}
+
+fn polygon_area_cost(tiles: &NavMeshTiles, costs: &[f32], tile: UVec2, polygon: u16) -> f32 {
+    tiles.tiles.get(&tile)
+        .and_then(|t| t.polygons.get(polygon as usize))
+        .and_then(|p| costs.get(p.area.0 as usize))
+        .copied()
+        .unwrap_or(1.0)
+}
+
+fn distance_to_segment(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let t = if ab.length_squared() > 1.0e-8 { ((p - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0) } else { 0.0 };
+    p.distance(a + ab * t)
+}
+
+impl PathSolver for CostGridSolver {
+    fn find_path(&self, query: &PathQuery, start: Vec3, end: Vec3) -> Option<Vec<Vec3>> {
+        let cost = &self.cost;
+        let min = start.xz().min(end.xz()) - Vec2::splat(cost.margin.max(0.0));
+        let max = start.xz().max(end.xz()) + Vec2::splat(cost.margin.max(0.0));
+        let size = max - min;
+
+        let mut cell_size = cost.cell_size.max(0.05);
+        while ((size.x / cell_size).ceil() as usize + 1) * ((size.y / cell_size).ceil() as usize + 1) > MAX_GRID_CELLS {
+            cell_size *= 1.5;
+        }
+        let width = (size.x / cell_size).ceil() as i32 + 1;
+        let depth = (size.y / cell_size).ceil() as i32 + 1;
+        let to_cell = |p: Vec3| {
+            IVec2::new(((p.x - min.x) / cell_size).round() as i32, ((p.z - min.y) / cell_size).round() as i32)
+                .clamp(IVec2::ZERO, IVec2::new(width - 1, depth - 1))
+        };
+        let centre = |c: IVec2| Vec2::new(min.x + c.x as f32 * cell_size, min.y + c.y as f32 * cell_size);
+
+        // Cost sources within reach of the grid
+        let clearances: Vec<(Vec2, f32, f32)> = cost.clearances.iter()
+            .flat_map(|c| {
+                query.tagged.iter()
+                    .filter(|(pos, tags)| tags.contains(&c.tag)
+                        && pos.xz().cmpge(min - c.distance).all()
+                        && pos.xz().cmple(max + c.distance).all())
+                    .map(|(pos, _)| (pos.xz(), c.distance, c.weight))
+            })
+            .collect();
+        let road_segments: Vec<(Vec2, Vec2)> = if cost.road_reuse > 0.0 {
+            query.roads.iter()
+                .flat_map(|road| road.windows(2).map(|w| (w[0].xz(), w[1].xz())))
+                .filter(|(a, b)| a.min(*b).cmple(max).all() && a.max(*b).cmpge(min).all())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let road_factor = 1.0 - cost.road_reuse.clamp(0.0, 0.95);
+        let noise = cost.noise.as_ref().map(|n| {
+            let seed = match n.fbm.seed {
+                SeededOrNot::Seeded(s) => s,
+                SeededOrNot::Unseeded => query.seed,
+            };
+            let generator = Source::<2>::simplex(seed)
+                .fbm(n.fbm.octaves as u32, n.fbm.frequency as f64, n.fbm.lacunarity as f64, n.fbm.persistence as f64)
+                .scale([n.fbm.scale as f64; 2]);
+            (generator, n.weight)
+        });
+
+        // Cost multiplier of entering a cell, before slope
+        let mut multipliers: HashMap<IVec2, f32> = HashMap::new();
+        let mut multiplier_at = |c: IVec2| -> f32 {
+            *multipliers.entry(c).or_insert_with(|| {
+                let p = centre(c);
+                let mut m = 1.0;
+                for (pos, distance, weight) in clearances.iter() {
+                    let d = p.distance(*pos);
+                    if d < *distance { m += weight * (1.0 - d / distance); }
+                }
+                if let Some((generator, weight)) = noise.as_ref() {
+                    let v = generator.sample([p.x as f64, p.y as f64]) as f32;
+                    m += weight * ((v + 1.0) * 0.5).clamp(0.0, 1.0);
+                }
+                if road_segments.iter().any(|(a, b)| distance_to_segment(p, *a, *b) <= cell_size) {
+                    m *= road_factor;
+                }
+                m
+            })
+        };
+
+        let start_cell = to_cell(start);
+        let goal_cell = to_cell(end);
+
+        // Navmesh height and area cost of a cell, or None when the cell is off the navmesh. `hint` is
+        // the height of the cell the search came from, so the lookup follows the terrain.
+        let pinned_area = |p: Vec3| {
+            query.tiles.find_closest_polygon_in_box(query.settings, p, cell_size * 2.0)
+                .map_or(1.0, |(tile, polygon, _)| area_cost(query, tile, polygon))
+        };
+        let mut cells_on_mesh: HashMap<IVec2, Option<(f32, f32)>> = HashMap::new();
+        cells_on_mesh.insert(start_cell, Some((start.y, pinned_area(start))));
+        cells_on_mesh.insert(goal_cell, Some((end.y, pinned_area(end))));
+        let mut on_mesh = |c: IVec2, hint: f32| -> Option<(f32, f32)> {
+            *cells_on_mesh.entry(c).or_insert_with(|| {
+                let p = centre(c);
+                let (tile, polygon, closest) = query.tiles.find_closest_polygon_in_box(query.settings, Vec3::new(p.x, hint, p.y), cell_size * 2.0)?;
+                (closest.xz().distance(p) <= cell_size * 0.5).then(|| (closest.y, area_cost(query, tile, polygon)))
+            })
+        };
+
+        // Cheaper areas scale the estimate down so it never overestimates
+        let cheapest_area = query.area_costs.map_or(1.0, |costs| costs.iter().copied().fold(1.0, f32::min).max(0.0));
+        let goal = centre(goal_cell);
+        let heuristic = |c: IVec2| centre(c).distance(goal) * road_factor * cheapest_area;
+
+        let mut open = BinaryHeap::new();
+        let mut g_score: HashMap<IVec2, f32> = HashMap::new();
+        let mut came_from: HashMap<IVec2, IVec2> = HashMap::new();
+        g_score.insert(start_cell, 0.0);
+        open.push(OpenCell { f: heuristic(start_cell), cell: start_cell });
+
+        let mut reached = false;
+        while let Some(OpenCell { f, cell }) = open.pop() {
+            let g = g_score[&cell];
+            if f > g + heuristic(cell) + 1.0e-4 { continue; } // stale entry
+            if cell == goal_cell { reached = true; break; }
+            let Some((h, _)) = on_mesh(cell, start.y) else { continue; };
+            let in_grid = |c: IVec2| c.x >= 0 && c.y >= 0 && c.x < width && c.y < depth;
+            for (dx, dz) in [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)] {
+                let next = cell + IVec2::new(dx, dz);
+                if !in_grid(next) { continue; }
+                let Some((next_h, area)) = on_mesh(next, h) else { continue; };
+                // A diagonal step may not squeeze past a corner: both cells beside it must be walkable
+                if dx != 0 && dz != 0 {
+                    let beside = [cell + IVec2::new(dx, 0), cell + IVec2::new(0, dz)];
+                    if beside.iter().any(|&c| !in_grid(c) || on_mesh(c, h).is_none()) { continue; }
+                }
+                let run = centre(next).distance(centre(cell));
+                let rise = (next_h - h).abs();
+                let step = (run * run + rise * rise).sqrt() * (multiplier_at(next) * area + cost.slope * rise / run);
+                let tentative = g + step;
+                if g_score.get(&next).is_none_or(|&old| tentative < old) {
+                    g_score.insert(next, tentative);
+                    came_from.insert(next, cell);
+                    open.push(OpenCell { f: tentative + heuristic(next), cell: next });
+                }
+            }
+        }
+        if !reached { return None; }
+
+        let mut cells = vec![goal_cell];
+        let mut current = goal_cell;
+        while let Some(&prev) = came_from.get(&current) {
+            cells.push(prev);
+            current = prev;
+        }
+        cells.reverse();
+
+        // Keep only the cells where the direction changes, then pin the exact start and end
+        let mut points = vec![start];
+        for i in 1..cells.len().saturating_sub(1) {
+            if cells[i] - cells[i - 1] != cells[i + 1] - cells[i] {
+                let p = centre(cells[i]);
+                let y = cells_on_mesh.get(&cells[i]).copied().flatten().map_or(start.y, |(h, _)| h);
+                points.push(Vec3::new(p.x, y, p.y));
+            }
+        }
+        points.push(end);
+        Some(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxidized_navigation::Area;
+    use oxidized_navigation::tiles::{NavMeshTile, Polygon};
+
+    // Flat navmesh at height `y` made of axis-aligned rectangles (min x, min z, max x, max z, area id)
+    fn navmesh(y: f32, rects: &[(f32, f32, f32, f32, u16)]) -> (NavMeshTiles, NavMeshSettings) {
+        let settings = NavMeshSettings::from_agent_and_bounds(0.5, 2.0, 100.0, -10.0);
+        let mut vertices = Vec::new();
+        let mut polygons = Vec::new();
+        for &(x0, z0, x1, z1, area) in rects {
+            let i = vertices.len() as u32;
+            vertices.extend([Vec3::new(x0, y, z0), Vec3::new(x1, y, z0), Vec3::new(x1, y, z1), Vec3::new(x0, y, z1)]);
+            for indices in [[i, i + 1, i + 2], [i, i + 2, i + 3]] {
+                polygons.push(Polygon { indices, links: Default::default(), area: Area(area) });
+            }
+        }
+        let mut tiles = NavMeshTiles::default();
+        tiles.tiles.insert(settings.get_tile_containing_position(Vec2::splat(5.0)), NavMeshTile {
+            vertices: vertices.into_boxed_slice(),
+            polygons: polygons.into_boxed_slice(),
+            edges: Vec::new().into_boxed_slice(),
+        });
+        (tiles, settings)
+    }
+
+    fn solve(tiles: &NavMeshTiles, settings: &NavMeshSettings, area_costs: Option<&[f32]>, start: Vec3, end: Vec3) -> Option<Vec<Vec3>> {
+        let query = PathQuery { tiles, settings, tagged: &[], roads: &[], seed: 0, links: &[], area_costs };
+        let solver = CostGridSolver { cost: PathCost { cell_size: 1.0, margin: 4.0, ..default() } };
+        solver.find_path(&query, start, end)
+    }
+
+    #[test]
+    fn keeps_exact_endpoints() {
+        let (tiles, settings) = navmesh(1.0, &[(0.0, 0.0, 10.0, 10.0, 0)]);
+        let (start, end) = (Vec3::new(1.2, 1.0, 1.7), Vec3::new(8.9, 1.0, 6.1));
+        let path = solve(&tiles, &settings, None, start, end).unwrap();
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&end));
+        assert!(path.iter().all(|p| p.y == 1.0));
+    }
+
+    #[test]
+    fn diagonal_does_not_cut_corners() {
+        // Two floors that only meet at a corner, with no walkable cell on either side of the diagonal
+        let (tiles, settings) = navmesh(0.0, &[(0.0, 5.1, 4.9, 10.0, 0), (5.1, 0.0, 10.0, 4.9, 0)]);
+        assert!(solve(&tiles, &settings, None, Vec3::new(2.5, 0.0, 7.5), Vec3::new(7.5, 0.0, 2.5)).is_none());
+
+        // Joined by a square at the corner, the same search goes around it
+        let (tiles, settings) = navmesh(0.0, &[(0.0, 5.1, 4.9, 10.0, 0), (5.1, 0.0, 10.0, 4.9, 0), (4.0, 4.0, 6.0, 6.0, 0)]);
+        assert!(solve(&tiles, &settings, None, Vec3::new(2.5, 0.0, 7.5), Vec3::new(7.5, 0.0, 2.5)).is_some());
+    }
+
+    #[test]
+    fn detours_around_costly_areas() {
+        // Area 1 is a band across the straight line, open only at its far end
+        let (tiles, settings) = navmesh(0.0, &[
+            (0.0, 0.0, 3.0, 10.0, 0),
+            (7.0, 0.0, 10.0, 10.0, 0),
+            (3.0, 6.5, 7.0, 10.0, 0),
+            (3.0, 0.0, 7.0, 6.5, 1),
+        ]);
+        let (start, end) = (Vec3::new(1.0, 0.0, 5.0), Vec3::new(9.0, 0.0, 5.0));
+
+        let straight = solve(&tiles, &settings, None, start, end).unwrap();
+        assert_eq!(straight, vec![start, end]);
+
+        let detour = solve(&tiles, &settings, Some(&[1.0, 10.0]), start, end).unwrap();
+        assert_eq!((detour.first(), detour.last()), (Some(&start), Some(&end)));
+        assert!(detour.iter().any(|p| p.z > 6.5));
+    }
+}
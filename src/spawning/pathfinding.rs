@@ -0,0 +1,171 @@
+use std::collections::HashSet;
+use bevy::prelude::*;
+use bevy::tasks::{block_on, futures_lite::future, AsyncComputeTaskPool};
+use oxidized_navigation::query::find_path;
+use oxidized_navigation::{NavMesh, NavMeshSettings, TileGenerated};
+use crate::event_system::generation_log::PATH;
+use crate::spawning::nav_links::{find_path_with_links, LinkWalks, NavAreaCosts, NavLinks};
+use crate::spawning::path_solver::area_weighted_length;
+use crate::spawning::object_logic::{Pathfinder, PathState, PathTask};
+
+// Send to move a unit: `commands.send_event(PathfinderGoal { entity, goal })`
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PathfinderGoal {
+    pub entity: Entity,
+    pub goal: Vec3,
+}
+
+// Search `find_path` off the main thread, crossing off-mesh links where that is cheaper. Resolves to
+// Ok(None) when the navmesh lock is poisoned.
+pub fn spawn_path_task(
+    nav_mesh: &NavMesh,
+    settings: &NavMeshSettings,
+    (links, area_costs): (&NavLinks, &NavAreaCosts),
+    start: Vec3,
+    goal: Vec3,
+) -> PathTask {
+    let tiles = nav_mesh.get();
+    let settings = settings.clone();
+    let links = links.0.clone();
+    let area_costs = area_costs.clone();
+    AsyncComputeTaskPool::get().spawn(async move {
+        let Ok(tiles) = tiles.read() else { return (Ok(None), goal); };
+        let costs = area_costs.multipliers();
+        let linked = find_path_with_links(
+            &links, start, goal, &mut LinkWalks::default(),
+            |a, b| find_path(&tiles, &settings, a, b, None, costs).ok(),
+            |points| area_weighted_length(&tiles, &settings, costs, points),
+        );
+        match linked {
+            Some(points) => (Ok(Some(points)), goal),
+            // Search again directly for the error
+            None => (find_path(&tiles, &settings, start, goal, None, costs).map(Some), goal),
+        }
+    })
+}
+
+// Start a search for every unit that got a new goal; a search still running for it is dropped
+pub fn request_pathfinder_goals(
+    mut reader: EventReader<PathfinderGoal>,
+    nav_mesh: Option<Res<NavMesh>>,
+    settings: Option<Res<NavMeshSettings>>,
+    (links, area_costs): (Res<NavLinks>, Res<NavAreaCosts>),
+    mut units: Query<(&GlobalTransform, &mut Pathfinder)>,
+) {
+    let (Some(nav_mesh), Some(settings)) = (nav_mesh, settings) else {
+        if !reader.is_empty() {
+            warn!(target: PATH, goals = reader.len(), "PathfinderGoal without a navmesh; ignoring");
+            reader.clear();
+        }
+        return;
+    };
+    for goal in reader.read() {
+        let Ok((transform, mut pathfinder)) = units.get_mut(goal.entity) else {
+            warn!(target: PATH, entity = ?goal.entity, "PathfinderGoal for an entity without Pathfinder");
+            continue;
+        };
+        trace!(target: PATH, entity = ?goal.entity, goal = ?goal.goal, "pathfinder: searching");
+        pathfinder.path = PathState::Calculating(spawn_path_task(&nav_mesh, &settings, (&links, &area_costs), transform.translation(), goal.goal));
+    }
+}
+
+// Turn finished searches into waypoints; a failed search leaves the unit Ready where it is. Searches
+// marked for replan are run again to the same goal.
+pub fn poll_pathfinder_tasks(
+    nav_mesh: Option<Res<NavMesh>>,
+    settings: Option<Res<NavMeshSettings>>,
+    (links, area_costs): (Res<NavLinks>, Res<NavAreaCosts>),
+    mut units: Query<(Entity, &GlobalTransform, &mut Pathfinder)>,
+) {
+    for (entity, transform, mut pathfinder) in units.iter_mut() {
+        let PathState::Calculating(task) = &mut pathfinder.path else { continue; };
+        let Some((result, goal)) = block_on(future::poll_once(task)) else { continue; };
+        if std::mem::take(&mut pathfinder.replan) {
+            if let (Some(nav_mesh), Some(settings)) = (nav_mesh.as_deref(), settings.as_deref()) {
+                trace!(target: PATH, ?entity, ?goal, "pathfinder: navmesh changed during search; re-planning");
+                pathfinder.path = PathState::Calculating(spawn_path_task(nav_mesh, settings, (&links, &area_costs), transform.translation(), goal));
+                continue;
+            }
+        }
+        pathfinder.path = match result {
+            Ok(Some(points)) if !points.is_empty() => {
+                trace!(target: PATH, ?entity, waypoints = points.len(), "pathfinder: path found");
+                PathState::Pathing(points)
+            }
+            Ok(_) => {
+                warn!(target: PATH, ?entity, ?goal, "pathfinder: navmesh unavailable");
+                PathState::Ready(transform.translation())
+            }
+            Err(err) => {
+                debug!(target: PATH, ?entity, ?goal, error = ?err, "pathfinder: no path");
+                PathState::Ready(transform.translation())
+            }
+        };
+    }
+}
+
+// Walk units along their waypoints on the ground plane; the height stays with the unit's physics
+pub fn follow_pathfinder_paths(time: Res<Time>, mut units: Query<(&mut Transform, &GlobalTransform, &mut Pathfinder)>) {
+    for (mut transform, global, mut pathfinder) in units.iter_mut() {
+        let speed = pathfinder.speed;
+        let arrival_radius = pathfinder.arrival_radius.max(0.01);
+        let PathState::Pathing(points) = &mut pathfinder.path else { continue; };
+        // Waypoints are world-space; units may sit under a transformed parent
+        let to_local = (global.affine() * transform.compute_affine().inverse()).inverse();
+        let mut step = speed * time.delta_secs();
+        while let Some(&next) = points.first() {
+            let next = to_local.transform_point3(next);
+            let to_next = Vec3::new(next.x - transform.translation.x, 0.0, next.z - transform.translation.z);
+            let distance = to_next.length();
+            if distance <= arrival_radius {
+                points.remove(0);
+                continue;
+            }
+            if step <= 0.0 { break; }
+            let direction = to_next / distance;
+            let moved = step.min(distance);
+            transform.translation += direction * moved;
+            transform.rotation = Quat::from_rotation_y(direction.x.atan2(direction.z));
+            step -= moved;
+        }
+        if points.is_empty() {
+            pathfinder.path = PathState::Ready(to_local.inverse().transform_point3(transform.translation));
+        }
+    }
+}
+
+// Re-plan to the same goal when a tile under the remaining route is rebuilt. Searches still running
+// may have read the old tiles, so they are marked to run again once they finish.
+pub fn replan_on_tile_rebuild(
+    mut reader: EventReader<TileGenerated>,
+    nav_mesh: Option<Res<NavMesh>>,
+    settings: Option<Res<NavMeshSettings>>,
+    (links, area_costs): (Res<NavLinks>, Res<NavAreaCosts>),
+    mut units: Query<(Entity, &GlobalTransform, &mut Pathfinder)>,
+) {
+    let rebuilt: HashSet<UVec2> = reader.read().map(|ev| ev.0).collect();
+    if rebuilt.is_empty() { return; }
+    let (Some(nav_mesh), Some(settings)) = (nav_mesh, settings) else { return; };
+    let step = settings.get_tile_size() * 0.5;
+
+    for (entity, transform, mut pathfinder) in units.iter_mut() {
+        if matches!(pathfinder.path, PathState::Calculating(_)) {
+            pathfinder.replan = true;
+            continue;
+        }
+        let PathState::Pathing(points) = &pathfinder.path else { continue; };
+        let Some(&goal) = points.last() else { continue; };
+        let start = transform.translation();
+        let route: Vec<Vec3> = std::iter::once(start).chain(points.iter().copied()).collect();
+        let crosses = route.windows(2).any(|w| {
+            let samples = (w[0].xz().distance(w[1].xz()) / step).ceil().max(1.0) as usize;
+            (0..=samples).any(|i| {
+                let p = w[0].lerp(w[1], i as f32 / samples as f32);
+                rebuilt.contains(&settings.get_tile_containing_position(p.xz()))
+            })
+        });
+        if !crosses { continue; }
+        trace!(target: PATH, ?entity, ?goal, "pathfinder: navmesh changed under route; re-planning");
+        pathfinder.path = PathState::Calculating(spawn_path_task(&nav_mesh, &settings, (&links, &area_costs), start, goal));
+    }
+}
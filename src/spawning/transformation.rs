@@ -6,6 +6,11 @@ use rand::Rng;
 use crate::core::seeded_or_not::SeededOrNot;
 use crate::spawning::euler_transform::EulerTransform;
 use crate::spawning::helpers::GenRng;
+use crate::event_system::generation_report::ReportSamples;
+use crate::core::fbm_data::FBMData;
+use crate::core::path_placement::{PathFacing, PathPlacement, PathSides};
+use statrs::distribution::Normal;
+use rand::distributions::Distribution;
 
 pub fn get_looped_position_list(origin: Vec3, transform: EulerTransform, x_times: usize) -> Vec<Vec3> {
     let mut positions = Vec::new();
@@ -34,6 +39,7 @@ pub fn get_looped_position_list(origin: Vec3, transform: EulerTransform, x_times
 pub fn generate_noise_spawn_points(
     data: &StructureKey,
     gen_rng: &mut ResMut<GenRng>,
+    samples: &ReportSamples,
 ) -> Vec<(f32, f32, f32)> {
     let (fbm, sample_size, count, exclusivity_radius, resolution_modifier) = if let StructureKey::NoiseSpawn {
         fbm,
@@ -56,29 +62,23 @@ pub fn generate_noise_spawn_points(
         SampleSize::UBiDim(x) => {
             generate_noise_spawn_points_2d(
                 (x, x),
-                fbm.scale,
-                fbm.octaves,
-                fbm.frequency,
-                fbm.lacunarity,
-                fbm.persistence,
+                fbm,
+                seed,
                 count,
                 exclusivity_radius,
                 resolution_modifier,
-                seed,
+                samples,
             )
         }
         SampleSize::BiDim(x, y) => {
             generate_noise_spawn_points_2d(
                 (x, y),
-                fbm.scale,
-                fbm.octaves,
-                fbm.frequency,
-                fbm.lacunarity,
-                fbm.persistence,
+                fbm,
+                seed,
                 count,
                 exclusivity_radius,
                 resolution_modifier,
-                seed,
+                samples,
             )
         }
         _ => {
@@ -89,15 +89,13 @@ pub fn generate_noise_spawn_points(
 
 pub fn generate_noise_spawn_points_2d(
     sample_size: (&i32, &i32),
-    scale: f32,
-    octaves: u8,
-    frequency: f32,
-    lacunarity: f32,
-    persistence: f32,
+    fbm: &FBMData,
+    // Used instead of fbm.seed, which the caller has resolved
+    seed: u64,
     spawn_count: &u32,
     exclusivity_radius: &f32,
     resolution_modifier: &f32,
-    seed: u64,
+    samples: &ReportSamples,
 ) -> Vec<(f32, f32, f32)> {
     let effective_width = *sample_size.0 as f32 * resolution_modifier;
     let effective_height = *sample_size.1 as f32 * resolution_modifier;
@@ -108,8 +106,8 @@ pub fn generate_noise_spawn_points_2d(
             "Effective dimensions (sample size multiplied by resolution modifier) must result in integers divisible by 2.");
 
     let generator = Source::simplex(seed)
-        .fbm(octaves as u32, frequency as f64, lacunarity as f64, persistence as f64)
-        .scale([scale as f64; 3]);
+        .fbm(fbm.octaves as u32, fbm.frequency as f64, fbm.lacunarity as f64, fbm.persistence as f64)
+        .scale([fbm.scale as f64; 3]);
 
     let start_sample_x = sample_size.0 / 2 * *resolution_modifier as i32;
     let end_sample_x = 3 * sample_size.0 / 2 * *resolution_modifier as i32;
@@ -146,7 +144,7 @@ pub fn generate_noise_spawn_points_2d(
 
     values_and_coords.sort_by(|a, b| b.3.partial_cmp(&a.3).unwrap_or(std::cmp::Ordering::Equal));
 
-    filter_by_exclusivity(&values_and_coords, spawn_count, exclusivity_radius)
+    filter_by_exclusivity(&values_and_coords, spawn_count, exclusivity_radius, samples)
         .iter()
         .map(|&(x, y, _)| (
             (x - centerpoint.x) / centerpoint.x,
@@ -157,9 +155,10 @@ pub fn generate_noise_spawn_points_2d(
 }
 
 fn filter_by_exclusivity(
-    sorted_values: &Vec<(f32, f32, f32, f64)>,
+    sorted_values: &[(f32, f32, f32, f64)],
     n: &u32,
     radius: &f32,
+    samples: &ReportSamples,
 ) -> Vec<(f32, f32, f32)> {
     let mut results = Vec::new();
     let mut candidates = std::collections::VecDeque::from(sorted_values.to_vec());
@@ -170,10 +169,10 @@ fn filter_by_exclusivity(
         if let Some((x, y, z, _)) = candidates.pop_front() {
             results.push((x, y, z));
 
-            candidates = candidates.into_iter().filter(|&(cx, cy, cz, _)| {
+            candidates.retain(|&(cx, cy, cz, _)| {
                 let square_distance = (x - cx).powi(2) + (y - cy).powi(2) + (z - cz).powi(2);
                 square_distance > square_radius
-            }).collect();
+            });
 
             if results.len() >= *n as usize {
                 break;
@@ -181,5 +180,211 @@ fn filter_by_exclusivity(
         }
     }
 
+    samples.record_exclusivity(sorted_values.len(), results.len());
     results
 }
+
+// Point at arclength `s` along a polyline
+pub fn polyline_point_at(points: &[Vec3], s: f32) -> Vec3 {
+    let mut s_rem = s.max(0.0);
+    for w in points.windows(2) {
+        let l = w[0].distance(w[1]);
+        if l <= 1e-6 { continue; }
+        if s_rem <= l {
+            return w[0].lerp(w[1], s_rem / l);
+        }
+        s_rem -= l;
+    }
+    points.last().copied().unwrap_or(Vec3::ZERO)
+}
+
+// Spawn points along a polyline where an FBM field is highest: candidates every
+// sample_size / resolution_modifier meters, kept best-first at least exclusivity_radius apart.
+// Returned in path order.
+pub fn generate_noise_path_points(
+    points: &[Vec3],
+    fbm: &FBMData,
+    sample_size: f32,
+    exclusivity_radius: f32,
+    resolution_modifier: f32,
+    count: u32,
+    (gen_rng, samples): (&mut ResMut<GenRng>, &ReportSamples),
+) -> Result<Vec<Vec3>, String> {
+    if sample_size <= 0.0 || resolution_modifier <= 0.0 {
+        return Err(format!(
+            "Noise spread needs a positive sample_size and resolution_modifier (got {} and {})",
+            sample_size, resolution_modifier
+        ));
+    }
+    let seed = match fbm.seed {
+        SeededOrNot::Seeded(s) => s,
+        SeededOrNot::Unseeded => gen_rng.rng_mut().gen::<u64>(),
+    };
+    let generator = Source::<3>::simplex(seed)
+        .fbm(fbm.octaves as u32, fbm.frequency as f64, fbm.lacunarity as f64, fbm.persistence as f64)
+        .scale([fbm.scale as f64; 3]);
+
+    let total_len: f32 = points.windows(2).map(|w| w[0].distance(w[1])).sum();
+    let step = sample_size / resolution_modifier;
+    let mut in_path_order: Vec<(f32, f32, f32, f64)> = Vec::new();
+    let mut s = 0.0;
+    while s <= total_len + 1.0e-4 {
+        let p = polyline_point_at(points, s);
+        in_path_order.push((p.x, p.y, p.z, generator.sample([p.x as f64, p.y as f64, p.z as f64])));
+        s += step;
+    }
+
+    let mut sorted = in_path_order.clone();
+    sorted.sort_by(|a, b| b.3.partial_cmp(&a.3).unwrap_or(std::cmp::Ordering::Equal));
+    let kept = filter_by_exclusivity(&sorted, &count, &exclusivity_radius, samples);
+    Ok(in_path_order
+        .into_iter()
+        .filter(|&(x, y, z, _)| kept.contains(&(x, y, z)))
+        .map(|(x, y, z, _)| Vec3::new(x, y, z))
+        .collect())
+}
+
+// Offsets each point by a normal-distributed amount along the path and sideways to it (horizontally)
+pub fn jitter_path_points(
+    positions: Vec<Vec3>,
+    standard_deviation: f32,
+    gen_rng: &mut ResMut<GenRng>,
+) -> Result<Vec<Vec3>, String> {
+    if standard_deviation == 0.0 { return Ok(positions); }
+    let normal_dist = Normal::new(0.0, standard_deviation as f64)
+        .map_err(|e| format!("Gaussian spread with standard deviation {}: {}", standard_deviation, e))?;
+    let n = positions.len();
+    let mut jittered = Vec::with_capacity(n);
+    for i in 0..n {
+        let prev = positions[i.saturating_sub(1)];
+        let next = positions[(i + 1).min(n - 1)];
+        let mut along = next - prev;
+        along.y = 0.0;
+        let along = along.normalize_or_zero();
+        let side = Vec3::Y.cross(along);
+        let offset_along = normal_dist.sample(gen_rng.rng_mut()) as f32;
+        let offset_side = normal_dist.sample(gen_rng.rng_mut()) as f32;
+        jittered.push(positions[i] + along * offset_along + side * offset_side);
+    }
+    Ok(jittered)
+}
+
+// Polyline with `start` meters cut from its beginning and `end` meters from its end; empty when
+// nothing is left
+pub fn trim_polyline(points: &[Vec3], start: f32, end: f32) -> Vec<Vec3> {
+    let start = start.max(0.0);
+    let end = end.max(0.0);
+    if start == 0.0 && end == 0.0 { return points.to_vec(); }
+    let total_len: f32 = points.windows(2).map(|w| w[0].distance(w[1])).sum();
+    let stop = total_len - end;
+    if stop <= start { return Vec::new(); }
+
+    let mut trimmed = vec![polyline_point_at(points, start)];
+    let mut s = 0.0;
+    for w in points.windows(2) {
+        s += w[0].distance(w[1]);
+        if s > start && s < stop { trimmed.push(w[1]); }
+    }
+    trimmed.push(polyline_point_at(points, stop));
+    trimmed
+}
+
+// The spawn positions and +Z directions for one point of a path with the given placement. `tangent` is
+// the horizontal path direction at the point and `index` its position in the spread (for Alternate).
+pub fn path_placements(point: Vec3, tangent: Vec3, index: usize, placement: Option<&PathPlacement>) -> Vec<(Vec3, Vec3)> {
+    let Some(placement) = placement else { return vec![(point, tangent)]; };
+    let left = Vec3::Y.cross(tangent).normalize_or_zero();
+    let sides: &[f32] = match placement.sides {
+        PathSides::Center => return vec![(point, tangent)],
+        PathSides::Left => &[1.0],
+        PathSides::Right => &[-1.0],
+        // Both sides coincide without an offset
+        PathSides::Both if placement.lateral_offset == 0.0 => &[1.0],
+        PathSides::Both => &[1.0, -1.0],
+        PathSides::Alternate => if index.is_multiple_of(2) { &[1.0] } else { &[-1.0] },
+    };
+    sides.iter().map(|side| {
+        let outward = left * *side;
+        let facing = match placement.facing {
+            PathFacing::AlongTangent => tangent,
+            PathFacing::TowardPath => -outward,
+            PathFacing::AwayFromPath => outward,
+        };
+        (point + outward * placement.lateral_offset, facing)
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line() -> Vec<Vec3> {
+        vec![Vec3::ZERO, Vec3::new(4.0, 0.0, 0.0), Vec3::new(4.0, 0.0, 6.0)]
+    }
+
+    #[test]
+    fn point_at_walks_the_arclength() {
+        assert_eq!(polyline_point_at(&line(), -1.0), Vec3::ZERO);
+        assert_eq!(polyline_point_at(&line(), 3.0), Vec3::new(3.0, 0.0, 0.0));
+        assert_eq!(polyline_point_at(&line(), 7.0), Vec3::new(4.0, 0.0, 3.0));
+        assert_eq!(polyline_point_at(&line(), 20.0), Vec3::new(4.0, 0.0, 6.0));
+    }
+
+    #[test]
+    fn trim_cuts_both_ends_and_keeps_inner_corners() {
+        assert_eq!(trim_polyline(&line(), 0.0, 0.0), line());
+        assert_eq!(trim_polyline(&line(), 1.0, 2.0), vec![Vec3::new(1.0, 0.0, 0.0), Vec3::new(4.0, 0.0, 0.0), Vec3::new(4.0, 0.0, 4.0)]);
+        // Past the corner, only the last segment is left
+        assert_eq!(trim_polyline(&line(), 5.0, 0.0), vec![Vec3::new(4.0, 0.0, 1.0), Vec3::new(4.0, 0.0, 6.0)]);
+    }
+
+    #[test]
+    fn trim_longer_than_the_path_leaves_nothing() {
+        assert!(trim_polyline(&line(), 6.0, 4.0).is_empty());
+        assert!(trim_polyline(&line(), 11.0, 0.0).is_empty());
+    }
+
+    #[test]
+    fn placement_offsets_to_the_chosen_sides() {
+        let (point, tangent) = (Vec3::new(1.0, 2.0, 3.0), Vec3::Z);
+        assert_eq!(path_placements(point, tangent, 0, None), vec![(point, tangent)]);
+
+        let mut placement = PathPlacement { lateral_offset: 2.0, sides: PathSides::Both, ..default() };
+        let both = path_placements(point, tangent, 0, Some(&placement));
+        assert_eq!(both, vec![(point + Vec3::X * 2.0, tangent), (point - Vec3::X * 2.0, tangent)]);
+
+        placement.sides = PathSides::Left;
+        assert_eq!(path_placements(point, tangent, 0, Some(&placement)), vec![(point + Vec3::X * 2.0, tangent)]);
+        placement.sides = PathSides::Right;
+        assert_eq!(path_placements(point, tangent, 0, Some(&placement)), vec![(point - Vec3::X * 2.0, tangent)]);
+        placement.sides = PathSides::Alternate;
+        assert_eq!(path_placements(point, tangent, 0, Some(&placement))[0].0, point + Vec3::X * 2.0);
+        assert_eq!(path_placements(point, tangent, 1, Some(&placement))[0].0, point - Vec3::X * 2.0);
+    }
+
+    #[test]
+    fn placement_without_an_offset_spawns_once_per_point() {
+        let (point, tangent) = (Vec3::new(1.0, 2.0, 3.0), Vec3::Z);
+        let mut placement = PathPlacement { facing: PathFacing::TowardPath, ..default() };
+        assert_eq!(path_placements(point, tangent, 0, Some(&placement)), vec![(point, tangent)]);
+        // Center keeps to the path whatever the offset
+        placement.lateral_offset = 2.0;
+        assert_eq!(path_placements(point, tangent, 0, Some(&placement)), vec![(point, tangent)]);
+        placement.sides = PathSides::Both;
+        placement.lateral_offset = 0.0;
+        assert_eq!(path_placements(point, tangent, 0, Some(&placement)).len(), 1);
+    }
+
+    #[test]
+    fn placement_faces_toward_or_away_from_the_path() {
+        let (point, tangent) = (Vec3::ZERO, Vec3::Z);
+        let mut placement = PathPlacement { lateral_offset: 1.0, sides: PathSides::Both, facing: PathFacing::TowardPath, ..default() };
+        for (position, facing) in path_placements(point, tangent, 0, Some(&placement)) {
+            assert_eq!(facing, (point - position).normalize());
+        }
+        placement.facing = PathFacing::AwayFromPath;
+        for (position, facing) in path_placements(point, tangent, 0, Some(&placement)) {
+            assert_eq!(facing, (position - point).normalize());
+        }
+    }
+}
@@ -1,12 +1,9 @@
 use std::fs;
 use std::fs::write;
-use std::path::{Path, PathBuf};
-use image::ImageReader as ImageReader;
-use image::{RgbImage, Rgb, DynamicImage};
-use image::GenericImageView;
+use std::path::Path;
 
 fn main() {
-    generate_metallic_roughness_maps();
+    // Split metallic/roughness maps are packed by proc_gen's asset processor at import time
     generate_texture_meta_files();
     //generate_ui_sprites();
     //generate_ui_meta_files();
@@ -74,12 +71,14 @@ fn generate_texture_meta_files() {
                 if material_path.extension().unwrap_or_default() == "png" {
                     let meta_path = material_path.with_extension("png.meta");
                     if !meta_path.exists() {
-                        let is_normal_map = material_path.file_stem()
+                        let file_stem = material_path.file_stem()
                             .unwrap()
                             .to_str()
                             .unwrap()
-                            .ends_with("_normal");
-                        let meta_content = create_meta_content(is_normal_map);
+                            .to_lowercase();
+                        let is_normal_map = file_stem.ends_with("_normal");
+                        let is_split_metallic_roughness = file_stem.ends_with("_metallic") || file_stem.ends_with("_roughness");
+                        let meta_content = create_meta_content(is_normal_map, is_split_metallic_roughness);
                         write(&meta_path, meta_content).unwrap();
                     }
                 }
@@ -88,11 +87,16 @@ fn generate_texture_meta_files() {
     }
 }
 
-fn create_meta_content(is_normal_map: bool) -> String {
+fn create_meta_content(is_normal_map: bool, is_split_metallic_roughness: bool) -> String {
+    let loader = if is_split_metallic_roughness {
+        "proc_gen::management::metallic_roughness_packer::MetallicRoughnessPackLoader"
+    } else {
+        "bevy_image::image_loader::ImageLoader"
+    };
     format!(r#"(
     meta_format_version: "1.0",
     asset: Process(
-        processor: "bevy_asset::processor::process::LoadTransformAndSave<bevy_image::image_loader::ImageLoader, bevy_asset::transformer::IdentityAssetTransformer<bevy_image::image::Image>, bevy_image::compressed_image_saver::CompressedImageSaver>",
+        processor: "bevy_asset::processor::process::LoadTransformAndSave<{}, bevy_asset::transformer::IdentityAssetTransformer<bevy_image::image::Image>, bevy_image::compressed_image_saver::CompressedImageSaver>",
             settings: (
             loader_settings: (
                 format: FromExtension,
@@ -118,138 +122,5 @@ fn create_meta_content(is_normal_map: bool) -> String {
         ),
     ),
 )
-"#, if is_normal_map { "false" } else { "true" })
-}
-
-fn generate_metallic_roughness_maps() {
-    let materials_path = Path::new("assets/materials");
-    for entry in fs::read_dir(materials_path).unwrap() {
-        let path = entry.unwrap().path();
-        if path.is_dir() {
-            let mut metallic_path = None;
-            let mut roughness_path = None;
-
-            let mut exists = false;
-
-            for inner_entry in fs::read_dir(&path).unwrap() {
-                let inner_path = inner_entry.unwrap().path();
-                if let Some(file_name) = inner_path.file_name().and_then(|n| n.to_str()) {
-                    if file_name.to_lowercase().ends_with("_metallicroughness.png") {
-                        exists=true;
-                        continue;
-                    }
-                    if file_name.to_lowercase().ends_with("_metallic.png") {
-                        metallic_path = Some(inner_path.clone());
-                        continue;
-                    }
-                    if file_name.to_lowercase().ends_with("_roughness.png") {
-                        roughness_path = Some(inner_path.clone());
-                        continue;
-                    }
-                }
-            }
-
-            if exists==true {
-                continue;
-            }
-
-            println!("cargo:warning=MetallicRoughness map not existent; creating!");
-
-            if let (Some(metallic_path), Some(roughness_path)) = (metallic_path.clone(), roughness_path.clone()) {
-                combine_metallic_roughness(&metallic_path, &roughness_path);
-            } else if let Some(metallic_path) = metallic_path {
-                combine_metallic_roughness(&metallic_path, &PathBuf::new());
-            } else if let Some(roughness_path) = roughness_path {
-                combine_metallic_roughness(&PathBuf::new(), &roughness_path);
-            }
-        }
-    }
-}
-
-fn combine_metallic_roughness(
-    input_metallic_path: &Path,
-    input_roughness_path: &Path
-) {
-    // Determine which path exists
-    let existing_path = if input_metallic_path.exists() {
-        input_metallic_path
-    } else {
-        input_roughness_path
-    };
-
-    // Extract file stem up to underscore and append "metallicRoughness"
-    let file_stem = existing_path.file_stem().unwrap().to_string_lossy();
-    let combined_filename = format!("{}_metallicRoughness.png", &file_stem[..file_stem.rfind('_').unwrap()]);
-    let output_path = existing_path.with_file_name(combined_filename);
-
-
-    // Load metallic and roughness images
-    let metallic_img_maybe = load_image(input_metallic_path);
-    let roughness_img_maybe = load_image(input_roughness_path);
-
-    // Use the dimensions of the existing images or default to 1 if both images are missing
-    let width = metallic_img_maybe
-        .as_ref()
-        .map_or_else(|| roughness_img_maybe
-            .as_ref()
-            .map_or(1, |img| img.width()), |img| img.width());
-    let height = metallic_img_maybe
-        .as_ref()
-        .map_or_else(|| roughness_img_maybe
-            .as_ref()
-            .map_or(1, |img| img.height()), |img| img.height());
-
-    let mut combined_img = RgbImage::new(width, height);
-
-    match (metallic_img_maybe, roughness_img_maybe) {
-        (Some(metallic_img), Some(roughness_img)) => {
-            // Both images are successfully loaded
-            for x in 0..width {
-                for y in 0..height {
-                    let metallic_blue = metallic_img.get_pixel(x, y)[1];
-                    let roughness_green = roughness_img.get_pixel(x, y)[1];
-                    combined_img.put_pixel(x, y, Rgb([0, roughness_green, metallic_blue]));
-                }
-            }
-        }
-        (Some(metallic_img), _) => {
-            let roughness_green = 255;
-            // Only metallic_img is successfully loaded
-            for x in 0..width {
-                for y in 0..height {
-                    let metallic_blue = metallic_img.get_pixel(x, y)[1];
-                    combined_img.put_pixel(x, y, Rgb([0, roughness_green, metallic_blue]));
-                }
-            }
-        }
-        (_, Some(roughness_img)) => {
-            // Only roughness_img is successfully loaded
-            let metallic_blue = 255;
-            for x in 0..width {
-                for y in 0..height {
-                    let roughness_green = roughness_img.get_pixel(x, y)[1];
-                    combined_img.put_pixel(x, y, Rgb([0, roughness_green, metallic_blue]));
-                }
-            }
-        }
-        _ => {}
-    }
-
-    // Attempt to save the image
-    if let Err(error) = combined_img.save(&output_path) {
-        println!(
-            "cargo:warning=Error saving image: {}",
-            error
-        );
-    }
+"#, loader, if is_normal_map || is_split_metallic_roughness { "false" } else { "true" })
 }
-
-fn load_image(path: &Path) -> Option<DynamicImage> {
-    match ImageReader::open(path) {
-        Ok(reader) => match reader.decode() {
-            Ok(img) => Some(img),
-            Err(_) => None,
-        },
-        Err(_) => None,
-    }
-}
\ No newline at end of file
@@ -79,7 +79,7 @@ pub(crate) fn initialize_camera_system(
     cam
         .insert(DistanceFog {
             color: Color::srgba(0.35, 0.48, 0.66, 1.0),
-            directional_light_color: Color::srgba(171.0 / 255.0, 183.0 / 255.0, 255.0 / 255.0, 1.0),
+            directional_light_color: Color::srgba(171.0 / 255.0, 183.0 / 255.0, 1.0, 1.0),
             directional_light_exponent: 30.0,
             falloff: FogFalloff::from_visibility_colors(
                 20.0,
@@ -116,10 +116,15 @@ impl Default for ZoomParameters {
     }
 }
 
+// Each of the three camera transforms, kept disjoint so they can be borrowed together
+type OnlyCameraSystem = (With<CameraSystem>, Without<MainCamera>, Without<CameraFocus>);
+type OnlyCameraFocus = (With<CameraFocus>, Without<MainCamera>, Without<CameraSystem>);
+type OnlyMainCamera = (With<MainCamera>, Without<CameraFocus>, Without<CameraSystem>);
+
 fn camera_controller_system(
-    mut system_query: Query<&mut Transform, (With<CameraSystem>, Without<MainCamera>, Without<CameraFocus>)>,
-    focus_query: Query<&Transform, (With<CameraFocus>, Without<MainCamera>, Without<CameraSystem>)>,
-    mut camera_query: Query<&mut Transform, (With<MainCamera>, Without<CameraFocus>, Without<CameraSystem>)>,
+    mut system_query: Query<&mut Transform, OnlyCameraSystem>,
+    focus_query: Query<&Transform, OnlyCameraFocus>,
+    mut camera_query: Query<&mut Transform, OnlyMainCamera>,
     mut cursor_moved_events: EventReader<CursorMoved>,
     mut mouse_wheel_events: EventReader<MouseWheel>,
     input: Res<InputStates>,
@@ -129,7 +134,7 @@ fn camera_controller_system(
     let mut cam_trans = camera_query.get_single_mut().expect("Camera transform not found!");
     let mut sys_trans = system_query.get_single_mut().expect("System transform not found!");
 
-    for event in mouse_wheel_events.read() {
+    if let Some(event) = mouse_wheel_events.read().next() {
         let scroll_increment = event.y * 0.1; // Adjust sensitivity as needed
         zoom_parameters.position += scroll_increment;
         zoom_parameters.position = zoom_parameters.position.clamp(0.1, zoom_parameters.curve.segments().len() as f32);
@@ -1,9 +1,9 @@
-use bevy::prelude::{Commands, Mesh, Vec2};
-use bevy_math::primitives::{Circle, Rectangle};
-use proc_gen::core::tmaterial::TMaterial;
-use proc_gen::spawning::euler_transform::EulerTransform;
-use proc_gen::spawn;
-use proc_gen::event_system::spawn_events::*;
+#[cfg(any(feature = "castle", feature = "tree"))]
+use bevy_math::primitives::Circle;
+#[cfg(feature = "map")]
+use bevy_math::primitives::Rectangle;
+#[cfg(any(feature = "castle", feature = "tree", feature = "map"))]
+use proc_gen::{core::tmaterial::TMaterial, event_system::spawn_events::*, spawn, spawning::euler_transform::EulerTransform};
 use bevy::prelude::*;
 use proc_gen::event_system::event_listeners::{
     GenerationState,
@@ -12,16 +12,12 @@ use proc_gen::event_system::event_listeners::{
     HighestPassIndex,
     PendingInPass,
 };
+use proc_gen::event_system::generation_phases::GenerationRoot;
 use proc_gen::spawning::helpers::GenRng;
 use std::time::{SystemTime, UNIX_EPOCH};
 use proc_gen::management::structure_management::clear_structure_cache;
 #[cfg(feature = "debug")]
 use proc_gen::event_system::event_listeners::AllPathsDebug;
-use bevy_pbr::StandardMaterial;
-use proc_gen::materials::path_blend::{PathBlendMaterial, PathBlendParams, falloff_mode, GroundPathMaterial, make_path_blend_material};
-
-#[derive(Component)]
-pub(crate) struct GeneratedRoot;
 
 fn send_generation_events(c: &mut Commands, parent: Option<Entity>) {
 
@@ -59,6 +55,9 @@ fn send_generation_events(c: &mut Commands, parent: Option<Entity>) {
                 near_metallic_roughness_path: Some("materials/Soil/TCom_Ground_Soil3_2x2_1K_metallicRoughness.png".to_string()),
                 near_ao_path: Some("materials/Soil/TCom_Ground_Soil3_2x2_1K_ao.png".to_string()),
             },
+            collider_type: None,
+            priority: None,
+            overlap: Default::default(),
             parent,
         });
     }
@@ -94,6 +93,9 @@ fn send_generation_events(c: &mut Commands, parent: Option<Entity>) {
                 material_name: "Grass".to_string(),
                 tiling_factor: Vec2::new(2.0, 2.0),
             },
+            collider_type: None,
+            priority: None,
+            overlap: Default::default(),
             parent,
         });
     }
@@ -126,6 +128,9 @@ fn send_generation_events(c: &mut Commands, parent: Option<Entity>) {
                 near_metallic_roughness_path: Some("materials/Soil/TCom_Ground_Soil3_2x2_1K_metallicRoughness.png".to_string()),
                 near_ao_path: Some("materials/Soil/TCom_Ground_Soil3_2x2_1K_ao.png".to_string()),
             },
+            collider_type: None,
+            priority: None,
+            overlap: Default::default(),
             parent,
         });
 
@@ -136,6 +141,12 @@ fn send_generation_events(c: &mut Commands, parent: Option<Entity>) {
             parent,
         });
     }
+
+    #[cfg(not(any(feature = "castle", feature = "tree", feature = "map")))]
+    {
+        let _ = (c, parent);
+        warn!("No scene to generate; run with --features castle, tree or map");
+    }
 }
 
 pub(crate) fn generate_map(mut c: Commands) {
@@ -145,7 +156,7 @@ pub(crate) fn generate_map(mut c: Commands) {
         .insert(Name::new("GeneratedRoot"))
         .insert(Transform::default())
         .insert(Visibility::default())
-        .insert(GeneratedRoot)
+        .insert(GenerationRoot)
         .id();
 
     send_generation_events(&mut c, Some(root));
@@ -155,20 +166,17 @@ pub(crate) fn reset_on_space(
     mut commands: Commands,
     keys: Res<ButtonInput<KeyCode>>,
     mut gen_rng: ResMut<GenRng>,
-    roots: Query<Entity, With<GeneratedRoot>>,
-    mut next_state: ResMut<NextState<GenerationState>>,
-    mut collision_timer: ResMut<CollisionResolutionTimer>,
+    roots: Query<Entity, With<GenerationRoot>>,
+    (mut next_state, mut collision_timer): (ResMut<NextState<GenerationState>>, ResMut<CollisionResolutionTimer>),
     // Generation pass resetting
-    mut cur_pass: ResMut<CurrentPass>,
-    mut highest_pass: ResMut<HighestPassIndex>,
-    mut pending_inpass: ResMut<PendingInPass>,
+    (mut cur_pass, mut highest_pass, mut pending_inpass): (ResMut<CurrentPass>, ResMut<HighestPassIndex>, ResMut<PendingInPass>),
     #[cfg(feature = "debug")] mut all_paths_debug: Option<ResMut<AllPathsDebug>>,
 ) {
     if !keys.just_pressed(KeyCode::Space) { return; }
 
     // Immediately clear debug overlay paths so the next frame draws nothing
     #[cfg(feature = "debug")]
-    if let Some(mut dbg) = all_paths_debug.as_mut() { dbg.paths.clear(); }
+    if let Some(dbg) = all_paths_debug.as_mut() { dbg.paths.clear(); }
 
     // Fresh, non-deterministic seed from system time
     let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
@@ -189,7 +197,7 @@ pub(crate) fn reset_on_space(
         .insert(Name::new("GeneratedRoot"))
         .insert(Transform::default())
         .insert(Visibility::default())
-        .insert(GeneratedRoot)
+        .insert(GenerationRoot)
         .id();
 
     send_generation_events(&mut commands, Some(new_root));
@@ -13,6 +13,7 @@ use bevy_rapier3d::render::{DebugRenderContext, ColliderDebug};
 use proc_gen::core::generator_plugin::GeneratorPlugin;
 use oxidized_navigation::{OxidizedNavigationPlugin, NavMeshSettings};
 use oxidized_navigation::debug_draw::OxidizedNavigationDebugDrawPlugin;
+#[cfg(feature = "atmosphere")]
 use proc_gen::core::components::MainDirectionalLight;
 use bevy_gizmos::config::{GizmoConfigStore, DefaultGizmoConfigGroup};
 
@@ -80,7 +81,7 @@ fn main() {
     }
 
     // Setup map generator (registers MaterialAutoloader and related systems)
-    app.add_plugins(GeneratorPlugin);
+    app.add_plugins(GeneratorPlugin::default());
 
     // Only register generation systems AFTER the generator/autoloader plugin,
     // so the autoloader's OnEnter(GameState::Playing) runs before generate_map.
@@ -171,7 +172,7 @@ fn sync_sun_to_main_dir_light(
 // Only compiled with the `debug` feature: toggle the existing DrawNavMesh resource on at startup
 #[cfg(feature = "debug")]
 fn enable_navmesh_debug(
-    mut maybe_draw: Option<ResMut<oxidized_navigation::debug_draw::DrawNavMesh>>,
+    maybe_draw: Option<ResMut<oxidized_navigation::debug_draw::DrawNavMesh>>,
 ) {
     if let Some(mut draw) = maybe_draw {
         draw.0 = true;